@@ -0,0 +1,117 @@
+import { bench, describe } from "vitest";
+import { SemanticStateEngine } from "../src/engine/SemanticStateEngine.js";
+import type { SemanticStateEngineConfig } from "../src/engine/SemanticStateEngine.js";
+import { mulberry32 } from "../src/math/projection.js";
+
+/**
+ * Update throughput benchmarks. Run with `npm run bench`; compare runs with
+ * `npm run bench -- --compare bench.json` after saving a baseline with
+ * `npm run bench -- --outputJson bench.json`.
+ *
+ * Each case pre-fills the engine so the measured updates run against a full
+ * history, and cycles through a fixed pool of seeded embeddings so results
+ * are comparable between runs.
+ */
+const DIMENSIONS = [384, 768, 1536, 4096];
+const HISTORY_SIZES = [100, 1_000, 10_000];
+const POOL_SIZE = 64;
+
+type Options = Omit<SemanticStateEngineConfig, "alpha" | "driftThreshold">;
+
+function embeddings(dimension: number, seed = dimension): Float32Array[] {
+  const next = mulberry32(seed);
+  return Array.from({ length: POOL_SIZE }, () =>
+    Float32Array.from({ length: dimension }, () => next() * 2 - 1),
+  );
+}
+
+/** An engine already holding `fill` updates, and a function applying the next one. */
+function prepared(
+  config: Options,
+  dimension: number,
+  fill: number,
+): () => void {
+  const engine = new SemanticStateEngine({
+    alpha: 0.2,
+    driftThreshold: 0.5,
+    ...config,
+  });
+  const pool = embeddings(dimension);
+  let timestamp = 0;
+  let i = 0;
+  const step = () => {
+    engine.updateEmbedding(pool[i++ % POOL_SIZE]!, (timestamp += 1_000));
+  };
+  for (let n = 0; n < fill; n++) step();
+  return step;
+}
+
+describe("updateEmbedding by dimension", () => {
+  for (const dimension of DIMENSIONS) {
+    const step = prepared({}, dimension, 100);
+    bench(`d=${dimension}`, step);
+  }
+});
+
+describe("updateEmbedding by history size (d=768)", () => {
+  for (const historySize of HISTORY_SIZES) {
+    const step = prepared({ historySize }, 768, historySize);
+    bench(`history=${historySize}`, step);
+  }
+});
+
+describe("fusion strategies (d=768)", () => {
+  for (const fusionStrategy of ["ema", "sma", "wma", "median", "kalman"] as const) {
+    const step = prepared({ fusionStrategy, fusionWindow: 32 }, 768, 100);
+    bench(fusionStrategy, step);
+  }
+});
+
+describe("drift metrics (d=768)", () => {
+  for (const driftMetric of ["cosine", "euclidean", "angular", "mahalanobis"] as const) {
+    const step = prepared({ driftMetric }, 768, 100);
+    bench(driftMetric, step);
+  }
+});
+
+describe("optional features (d=1536)", () => {
+  const cases: Record<string, Options> = {
+    baseline: {},
+    "window baseline": { driftBaseline: "window", driftWindow: 32 },
+    "change detector": { changeDetector: { type: "adwin" } },
+    attribution: { attributionTopK: 8 },
+    clustering: { clustering: { k: 8 } },
+    outliers: { outliers: { zScore: 3 } },
+    "pca compression": { snapshotCompression: { method: "pca", components: 4 } },
+    "stats window": { statsWindow: 64 },
+  };
+  for (const [name, config] of Object.entries(cases)) {
+    bench(name, prepared(config, 1536, 100));
+  }
+});
+
+describe("updateEmbeddingPacked by dimension", () => {
+  // The worker boundary path: a transferable result buffer instead of a
+  // result object.
+  for (const dimension of DIMENSIONS) {
+    const engine = new SemanticStateEngine({ alpha: 0.2, driftThreshold: 0.5 });
+    const pool = embeddings(dimension);
+    let i = 0;
+    bench(`d=${dimension}`, () => {
+      engine.updateEmbeddingPacked(pool[i++ % POOL_SIZE]!, i * 1_000);
+    });
+  }
+});
+
+describe("updateEmbeddingInto by dimension", () => {
+  // Writes the state into a registered output buffer instead of copying it.
+  for (const dimension of DIMENSIONS) {
+    const engine = new SemanticStateEngine({ alpha: 0.2, driftThreshold: 0.5 });
+    engine.setOutputBuffer(new Float32Array(dimension));
+    const pool = embeddings(dimension);
+    let i = 0;
+    bench(`d=${dimension}`, () => {
+      engine.updateEmbeddingInto(pool[i++ % POOL_SIZE]!, i * 1_000);
+    });
+  }
+});
@@ -0,0 +1,28 @@
+import { bench, describe } from "vitest";
+import { dot } from "../src/math/vector.js";
+import { mulberry32 } from "../src/math/projection.js";
+
+/**
+ * Dot product benchmarks: the four-accumulator `dot` against the
+ * single-accumulator `reduce` it replaced, at a small and a large embedding
+ * dimension. Run with `npm run bench`.
+ */
+const DIMENSIONS = [384, 3072];
+
+function reduceDot(a: number[], b: number[]): number {
+  return a.reduce((sum, val, i) => sum + val * b[i]!, 0);
+}
+
+for (const dimension of DIMENSIONS) {
+  describe(`dot (d=${dimension})`, () => {
+    const next = mulberry32(dimension);
+    const a = Array.from({ length: dimension }, () => next() * 2 - 1);
+    const b = Array.from({ length: dimension }, () => next() * 2 - 1);
+    bench("unrolled", () => {
+      dot(a, b);
+    });
+    bench("reduce", () => {
+      reduceDot(a, b);
+    });
+  });
+}
@@ -0,0 +1,174 @@
+/**
+ * semantic-state-estimator/core
+ *
+ * The environment-agnostic core: the engine, its math, and the change
+ * detectors, without the WebWorker embedding pipeline. Safe to import from
+ * Node.js services that compute embeddings elsewhere and feed them in with
+ * `updateEmbedding`.
+ */
+
+export {
+  dot,
+  magnitude,
+  emaFusion,
+  cosineSimilarity,
+  normalize,
+  add,
+  scale,
+  mean,
+  median,
+  weightedMean,
+  euclideanDistance,
+  manhattanDistance,
+  angularDistance,
+  mahalanobisDistance,
+  jsDivergence,
+  klDivergence,
+  standardize,
+  similarityMatrix,
+} from "./math/vector.js";
+export { kalmanUpdate, type KalmanStep } from "./math/kalman.js";
+export { P2Quantile } from "./math/P2Quantile.js";
+export { RandomProjection } from "./math/projection.js";
+export { OnlinePCA } from "./math/OnlinePCA.js";
+export { PackedVector, type StoragePrecision } from "./math/quantize.js";
+export { OnlineKMeans, type ClusterAssignment } from "./math/OnlineKMeans.js";
+export { RunningStats } from "./math/RunningStats.js";
+export {
+  sparseCosineSimilarity,
+  sparseDot,
+  toDense,
+  topK,
+  validateSparse,
+  type SparseVector,
+} from "./math/sparse.js";
+export {
+  SemanticStateEngine,
+  type AdaptiveAlphaConfig,
+  type AutoThresholdConfig,
+  type EmbeddingProvider,
+  type DriftBaseline,
+  type DriftEvent,
+  type DriftMetric,
+  type DriftStats,
+  type EngineLogLevel,
+  type EngineLogRecord,
+  type EmbeddingStats,
+  type EngineState,
+  type FusionStrategy,
+  type HealthConfig,
+  type HealthCurve,
+  type HealthFactor,
+  type HealthFactorName,
+  type ResolvedHealthConfig,
+  type HistoryEntry,
+  type InitMode,
+  type TimestampPolicy,
+  type ClockMode,
+  type NonFinitePolicy,
+  type ResultDetail,
+  type DimensionChangePolicy,
+  type DimensionChange,
+  type KalmanConfig,
+  type OutlierConfig,
+  type InputShiftConfig,
+  type HistorySimilarityConfig,
+  type HorizonConfig,
+  type ProjectionConfig,
+  type ClusteringConfig,
+  type ClusterStats,
+  type SnapshotCompressionConfig,
+  type TopKCompressionConfig,
+  type PcaCompressionConfig,
+  type CompressedSnapshot,
+  type CompressedVector,
+  type Pca2dConfig,
+  type HealthTrend,
+  type DriftLevel,
+  type DriftEvaluation,
+  type TraceEntry,
+  type UpdateTrace,
+  type EngineMetrics,
+  type NormalizationPolicy,
+  type StateArchiveConfig,
+  type EmbeddingSampleConfig,
+  type SampledEmbedding,
+  type ArchivedState,
+  type AnchorMatch,
+  type SimilarState,
+  type SemanticStateEngineConfig,
+  type RawSnapshot,
+  type RawSnapshot64,
+  type RestorableSnapshot,
+  type Snapshot,
+  type UpdateResult,
+} from "./engine/SemanticStateEngine.js";
+export { aggregateHistory, type HistoryBucket } from "./engine/aggregate.js";
+export {
+  attributeDrift,
+  type DimensionContribution,
+} from "./engine/attribution.js";
+export {
+  diffSnapshots,
+  compareEngines,
+  type DeltaStats,
+  type SnapshotDiff,
+  type SnapshotLike,
+} from "./engine/diff.js";
+export {
+  parseEngineConfig,
+  type EngineBindings,
+  type SerializableEngineConfig,
+} from "./engine/config.js";
+export {
+  classifyDrift,
+  type DriftObservation,
+  type DriftType,
+} from "./engine/driftType.js";
+export {
+  decodeState,
+  encodeState,
+  type BinaryPrecision,
+} from "./engine/binary.js";
+export {
+  forecastHistory,
+  type Forecast,
+  type HealthTimeEstimate,
+} from "./engine/forecast.js";
+export {
+  driftEventToOtel,
+  snapshotToOtel,
+  type OtelAttributeValue,
+  type OtelLogRecord,
+} from "./engine/otel.js";
+export {
+  packUpdateResult,
+  unpackUpdateResult,
+  PACKED_FLAGS,
+  type PackedUpdateResult,
+} from "./engine/packed.js";
+export {
+  formatPrometheus,
+  type PrometheusSeries,
+} from "./engine/prometheus.js";
+export { migrateState, STATE_VERSION } from "./engine/migrate.js";
+export {
+  EngineRegistry,
+  type FleetSnapshot,
+} from "./engine/EngineRegistry.js";
+export {
+  EngineError,
+  type EngineErrorCode,
+  type EngineErrorJSON,
+} from "./engine/errors.js";
+export {
+  createChangeDetector,
+  Adwin,
+  Cusum,
+  PageHinkley,
+  type AdwinConfig,
+  type ChangeDetector,
+  type ChangeDetectorConfig,
+  type CusumConfig,
+  type PageHinkleyConfig,
+} from "./detectors/index.js";
@@ -0,0 +1,42 @@
+import { describe, it, expect } from "vitest";
+import { Adwin } from "./Adwin.js";
+
+describe("Adwin", () => {
+  it("grows its window and raises no alarms on a stationary stream", () => {
+    const adwin = new Adwin();
+    for (let i = 0; i < 300; i++) {
+      expect(adwin.update(0.1 + 0.05 * Math.sin(i))).toBe(false);
+    }
+    expect(adwin.width).toBe(300);
+  });
+
+  it("detects a shift in the mean and shrinks to the new segment", () => {
+    const adwin = new Adwin();
+    for (let i = 0; i < 300; i++) adwin.update(0.1 + 0.05 * Math.sin(i));
+
+    let detectedAt = -1;
+    for (let i = 0; i < 100 && detectedAt < 0; i++) {
+      if (adwin.update(0.5 + 0.05 * Math.sin(i))) detectedAt = i;
+    }
+    expect(detectedAt).toBeGreaterThan(0);
+    expect(adwin.width).toBeLessThan(100);
+  });
+
+  it("caps the window at maxWindow", () => {
+    const adwin = new Adwin(0.002, 50);
+    for (let i = 0; i < 100; i++) adwin.update(0.1);
+    expect(adwin.width).toBe(50);
+  });
+
+  it("reset clears the window", () => {
+    const adwin = new Adwin();
+    adwin.update(1);
+    adwin.reset();
+    expect(adwin.width).toBe(0);
+  });
+
+  it("throws on invalid parameters", () => {
+    expect(() => new Adwin(0)).toThrow("ADWIN requires delta");
+    expect(() => new Adwin(0.01, 6, 5)).toThrow("maxWindow >= 2 * minSubWindow");
+  });
+});
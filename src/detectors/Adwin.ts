@@ -0,0 +1,111 @@
+import type { ChangeDetector } from "./types.js";
+
+/**
+ * ADWIN (ADaptive WINdowing) change detector — Bifet & Gavaldà, 2007.
+ *
+ * Keeps a window of recent observations and, after every observation, checks
+ * every split of the window into an older part W₀ and a newer part W₁. When
+ * the means of the two parts differ by more than the bound
+ *
+ *   ε = √(2/m · σ² · ln(2/δ′)) + 2/(3m) · ln(2/δ′),
+ *   m = 1 / (1/n₀ + 1/n₁),  δ′ = δ / n,
+ *
+ * the older part is dropped and a change is reported. The window therefore
+ * grows while the stream is stationary and shrinks to the post-change segment
+ * after a shift, with false-positive rate bounded by δ.
+ *
+ * This implementation keeps the exact window (capped at `maxWindow`) rather
+ * than ADWIN2's exponential histogram, trading O(n) work per observation for
+ * simplicity.
+ */
+export class Adwin implements ChangeDetector {
+  private readonly delta: number;
+  private readonly maxWindow: number;
+  private readonly minSubWindow: number;
+
+  private window: number[];
+
+  /**
+   * @param delta        Confidence parameter δ ∈ (0, 1); smaller means fewer
+   *                     false alarms and slower detection.
+   * @param maxWindow    Maximum number of observations retained.
+   * @param minSubWindow Minimum size of either part of a split.
+   */
+  constructor(
+    delta: number = 0.002,
+    maxWindow: number = 1000,
+    minSubWindow: number = 5,
+  ) {
+    if (!(delta > 0 && delta < 1)) {
+      throw new Error(`ADWIN requires delta in (0, 1), got ${delta}`);
+    }
+    if (!Number.isInteger(maxWindow) || !Number.isInteger(minSubWindow)) {
+      throw new Error(
+        `ADWIN requires integer window sizes, got maxWindow=${maxWindow}, minSubWindow=${minSubWindow}`,
+      );
+    }
+    if (minSubWindow < 1 || maxWindow < 2 * minSubWindow) {
+      throw new Error(
+        `ADWIN requires minSubWindow >= 1 and maxWindow >= 2 * minSubWindow, got maxWindow=${maxWindow}, minSubWindow=${minSubWindow}`,
+      );
+    }
+    this.delta = delta;
+    this.maxWindow = maxWindow;
+    this.minSubWindow = minSubWindow;
+    this.window = [];
+  }
+
+  /** Number of observations currently in the window. */
+  get width(): number {
+    return this.window.length;
+  }
+
+  update(value: number): boolean {
+    this.window.push(value);
+    if (this.window.length > this.maxWindow) {
+      this.window.shift();
+    }
+
+    let changed = false;
+    for (let cut = this.findCut(); cut > 0; cut = this.findCut()) {
+      this.window = this.window.slice(cut);
+      changed = true;
+    }
+    return changed;
+  }
+
+  reset(): void {
+    this.window = [];
+  }
+
+  /**
+   * Returns the index of the first split whose sub-window means differ by
+   * more than the ADWIN bound, or -1 if the window is homogeneous.
+   */
+  private findCut(): number {
+    const n = this.window.length;
+    if (n < 2 * this.minSubWindow) return -1;
+
+    const total = this.window.reduce((sum, v) => sum + v, 0);
+    const mean = total / n;
+    const variance =
+      this.window.reduce((sum, v) => sum + (v - mean) ** 2, 0) / n;
+    const logTerm = Math.log((2 * n) / this.delta);
+
+    let prefix = 0;
+    for (let i = 1; i < n; i++) {
+      prefix += this.window[i - 1]!;
+      if (i < this.minSubWindow || n - i < this.minSubWindow) continue;
+
+      const n0 = i;
+      const n1 = n - i;
+      const m = 1 / (1 / n0 + 1 / n1);
+      const epsilon =
+        Math.sqrt((2 / m) * variance * logTerm) + (2 / (3 * m)) * logTerm;
+      if (Math.abs(prefix / n0 - (total - prefix) / n1) > epsilon) {
+        return i;
+      }
+    }
+    return -1;
+  }
+}
@@ -0,0 +1,37 @@
+import { describe, it, expect } from "vitest";
+import { Cusum } from "./Cusum.js";
+
+describe("Cusum", () => {
+  it("ignores noise within the slack", () => {
+    const cusum = new Cusum(0.05, 0.5);
+    const fired = Array.from({ length: 200 }, (_, i) =>
+      cusum.update(0.2 + (i % 2 === 0 ? 0.04 : -0.04)),
+    );
+    expect(fired.some(Boolean)).toBe(false);
+  });
+
+  it("detects a sustained upward shift", () => {
+    const cusum = new Cusum(0.01, 0.5);
+    for (let i = 0; i < 50; i++) expect(cusum.update(0.1)).toBe(false);
+    const fired = Array.from({ length: 10 }, () => cusum.update(0.3));
+    expect(fired.indexOf(true)).toBeGreaterThan(0);
+  });
+
+  it("detects a sustained downward shift", () => {
+    const cusum = new Cusum(0.01, 0.5);
+    for (let i = 0; i < 50; i++) cusum.update(0.5);
+    const fired = Array.from({ length: 10 }, () => cusum.update(0.2));
+    expect(fired).toContain(true);
+  });
+
+  it("does not flag a single spike", () => {
+    const cusum = new Cusum(0.01, 0.5);
+    for (let i = 0; i < 50; i++) cusum.update(0.1);
+    expect(cusum.update(0.5)).toBe(false);
+    for (let i = 0; i < 50; i++) expect(cusum.update(0.1)).toBe(false);
+  });
+
+  it("throws on invalid parameters", () => {
+    expect(() => new Cusum(-1, 1)).toThrow("CUSUM requires");
+  });
+});
@@ -0,0 +1,62 @@
+import type { ChangeDetector } from "./types.js";
+
+/**
+ * Two-sided tabular CUSUM detector.
+ *
+ * Tracks a running mean of the stream and accumulates deviations from it in
+ * both directions:
+ *   S⁺ = max(0, S⁺ + x − x̄ − k)
+ *   S⁻ = max(0, S⁻ + x̄ − x − k)
+ * A change is reported when either sum exceeds h. Small deviations (below the
+ * slack k) are ignored, so only sustained shifts in the mean accumulate.
+ */
+export class Cusum implements ChangeDetector {
+  private readonly slack: number;
+  private readonly threshold: number;
+
+  private count: number;
+  private mean: number;
+  private upper: number;
+  private lower: number;
+
+  /**
+   * @param slack     Allowed deviation k per observation before it accumulates.
+   * @param threshold Detection threshold h on either cumulative sum.
+   */
+  constructor(slack: number = 0.01, threshold: number = 0.5) {
+    if (!(slack >= 0) || !(threshold > 0)) {
+      throw new Error(
+        `CUSUM requires slack >= 0 and threshold > 0, got slack=${slack}, threshold=${threshold}`,
+      );
+    }
+    this.slack = slack;
+    this.threshold = threshold;
+    this.count = 0;
+    this.mean = 0;
+    this.upper = 0;
+    this.lower = 0;
+  }
+
+  update(value: number): boolean {
+    if (this.count > 0) {
+      const deviation = value - this.mean;
+      this.upper = Math.max(0, this.upper + deviation - this.slack);
+      this.lower = Math.max(0, this.lower - deviation - this.slack);
+    }
+    this.count++;
+    this.mean += (value - this.mean) / this.count;
+
+    if (this.upper > this.threshold || this.lower > this.threshold) {
+      this.reset();
+      return true;
+    }
+    return false;
+  }
+
+  reset(): void {
+    this.count = 0;
+    this.mean = 0;
+    this.upper = 0;
+    this.lower = 0;
+  }
+}
@@ -0,0 +1,48 @@
+import { describe, it, expect } from "vitest";
+import { PageHinkley } from "./PageHinkley.js";
+
+describe("PageHinkley", () => {
+  it("does not fire on a stationary stream", () => {
+    const ph = new PageHinkley(0.01, 1);
+    const fired = Array.from({ length: 200 }, (_, i) =>
+      ph.update(0.1 + (i % 2 === 0 ? 0.02 : -0.02)),
+    );
+    expect(fired.some(Boolean)).toBe(false);
+  });
+
+  it("detects a sustained increase in the mean", () => {
+    const ph = new PageHinkley(0.01, 1);
+    for (let i = 0; i < 50; i++) expect(ph.update(0.1)).toBe(false);
+
+    let detectedAt = -1;
+    for (let i = 0; i < 50; i++) {
+      if (ph.update(0.4)) {
+        detectedAt = i;
+        break;
+      }
+    }
+    expect(detectedAt).toBeGreaterThan(0);
+    expect(detectedAt).toBeLessThan(10);
+  });
+
+  it("catches slow cumulative drift that never spikes", () => {
+    const ph = new PageHinkley(0.001, 0.5);
+    let detected = false;
+    for (let i = 0; i < 500 && !detected; i++) {
+      detected = ph.update(0.1 + i * 0.002);
+    }
+    expect(detected).toBe(true);
+  });
+
+  it("resets after reporting a change", () => {
+    const ph = new PageHinkley(0, 0.5);
+    ph.update(0);
+    expect(ph.update(2)).toBe(true);
+    // Fresh statistics: a single value cannot exceed lambda.
+    expect(ph.update(5)).toBe(false);
+  });
+
+  it("throws on invalid parameters", () => {
+    expect(() => new PageHinkley(0.01, 0)).toThrow("Page-Hinkley requires");
+  });
+});
@@ -0,0 +1,56 @@
+import type { ChangeDetector } from "./types.js";
+
+/**
+ * Page-Hinkley test for detecting an increase in the mean of a stream.
+ *
+ * Accumulates m_t = Σ (x_i − x̄_i − δ) and reports a change when
+ * m_t − min(m) exceeds λ. Unlike a fixed threshold on individual values, this
+ * catches slow cumulative drift that never produces a single large spike.
+ */
+export class PageHinkley implements ChangeDetector {
+  private readonly delta: number;
+  private readonly lambda: number;
+
+  private count: number;
+  private mean: number;
+  private cumulative: number;
+  private minimum: number;
+
+  /**
+   * @param delta  Magnitude of change tolerated before deviations accumulate.
+   * @param lambda Detection threshold on the cumulative deviation.
+   */
+  constructor(delta: number = 0.01, lambda: number = 1) {
+    if (!(delta >= 0) || !(lambda > 0)) {
+      throw new Error(
+        `Page-Hinkley requires delta >= 0 and lambda > 0, got delta=${delta}, lambda=${lambda}`,
+      );
+    }
+    this.delta = delta;
+    this.lambda = lambda;
+    this.count = 0;
+    this.mean = 0;
+    this.cumulative = 0;
+    this.minimum = 0;
+  }
+
+  update(value: number): boolean {
+    this.count++;
+    this.mean += (value - this.mean) / this.count;
+    this.cumulative += value - this.mean - this.delta;
+    this.minimum = Math.min(this.minimum, this.cumulative);
+
+    if (this.cumulative - this.minimum > this.lambda) {
+      this.reset();
+      return true;
+    }
+    return false;
+  }
+
+  reset(): void {
+    this.count = 0;
+    this.mean = 0;
+    this.cumulative = 0;
+    this.minimum = 0;
+  }
+}
@@ -0,0 +1,29 @@
+import { Adwin } from "./Adwin.js";
+import { Cusum } from "./Cusum.js";
+import { PageHinkley } from "./PageHinkley.js";
+import type { ChangeDetector, ChangeDetectorConfig } from "./types.js";
+
+export { Adwin } from "./Adwin.js";
+export { Cusum } from "./Cusum.js";
+export { PageHinkley } from "./PageHinkley.js";
+export type {
+  AdwinConfig,
+  ChangeDetector,
+  ChangeDetectorConfig,
+  CusumConfig,
+  PageHinkleyConfig,
+} from "./types.js";
+
+/** Instantiates the change detector described by `config`. */
+export function createChangeDetector(
+  config: ChangeDetectorConfig,
+): ChangeDetector {
+  switch (config.type) {
+    case "page-hinkley":
+      return new PageHinkley(config.delta, config.lambda);
+    case "cusum":
+      return new Cusum(config.slack, config.threshold);
+    case "adwin":
+      return new Adwin(config.delta, config.maxWindow, config.minSubWindow);
+  }
+}
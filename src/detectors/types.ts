@@ -0,0 +1,79 @@
+/**
+ * Shared contract for change detectors that run over the scalar stream of
+ * drift scores produced by the SemanticStateEngine.
+ */
+
+/** A sequential change detector over a stream of scalar observations. */
+export interface ChangeDetector {
+  /**
+   * Feeds the next observation into the detector.
+   *
+   * @returns `true` if a change point is detected at this observation. The
+   *          detector discards the pre-change statistics after reporting.
+   */
+  update(value: number): boolean;
+
+  /** Clears all accumulated statistics. */
+  reset(): void;
+}
+
+/** Configuration of the Page-Hinkley detector. */
+export interface PageHinkleyConfig {
+  type: "page-hinkley";
+
+  /**
+   * Magnitude of change tolerated before deviations accumulate.
+   * @default 0.01
+   */
+  delta?: number;
+
+  /**
+   * Detection threshold on the cumulative deviation.
+   * @default 1
+   */
+  lambda?: number;
+}
+
+/** Configuration of the two-sided CUSUM detector. */
+export interface CusumConfig {
+  type: "cusum";
+
+  /**
+   * Allowed deviation from the running mean per observation before it
+   * accumulates.
+   * @default 0.01
+   */
+  slack?: number;
+
+  /**
+   * Detection threshold on either cumulative sum.
+   * @default 0.5
+   */
+  threshold?: number;
+}
+
+/** Configuration of the ADWIN adaptive-windowing detector. */
+export interface AdwinConfig {
+  type: "adwin";
+
+  /**
+   * Confidence parameter δ ∈ (0, 1); bounds the false-positive rate.
+   * @default 0.002
+   */
+  delta?: number;
+
+  /**
+   * Maximum number of drift scores retained in the window.
+   * @default 1000
+   */
+  maxWindow?: number;
+
+  /**
+   * Minimum size of either sub-window when testing a split.
+   * @default 5
+   */
+  minSubWindow?: number;
+}
+
+/** Configuration accepted by {@link createChangeDetector}. */
+export type ChangeDetectorConfig = PageHinkleyConfig | CusumConfig | AdwinConfig;
@@ -0,0 +1,195 @@
+import { describe, it, expect, vi } from "vitest";
+import { EngineRegistry } from "./EngineRegistry.js";
+import { SemanticStateEngine } from "./SemanticStateEngine.js";
+import type { EmbeddingProvider } from "./SemanticStateEngine.js";
+import { EngineError } from "./errors.js";
+
+function makeProvider(vector: number[]): EmbeddingProvider {
+  return {
+    getEmbedding: vi.fn().mockResolvedValue(new Float32Array(vector)),
+  } as unknown as EmbeddingProvider;
+}
+
+function makeRegistry(): EngineRegistry {
+  return new EngineRegistry({
+    alpha: 0.5,
+    driftThreshold: 0.75,
+    provider: makeProvider([1, 0, 0, 0]),
+  });
+}
+
+describe("EngineRegistry", () => {
+  it("creates, lists and removes engines by key", () => {
+    const registry = makeRegistry();
+    const engine = registry.create("thread-1");
+
+    expect(engine).toBeInstanceOf(SemanticStateEngine);
+    expect(registry.get("thread-1")).toBe(engine);
+    expect(registry.has("thread-1")).toBe(true);
+    expect(registry.keys()).toEqual(["thread-1"]);
+    expect(registry.size).toBe(1);
+
+    expect(registry.remove("thread-1")).toBe(true);
+    expect(registry.remove("thread-1")).toBe(false);
+    expect(registry.size).toBe(0);
+  });
+
+  it("applies per-engine overrides on top of the defaults", () => {
+    const registry = makeRegistry();
+    expect(registry.create("fast", { alpha: 1 }).exportState().alpha).toBe(1);
+    expect(registry.create("default").exportState().alpha).toBe(0.5);
+  });
+
+  it("keeps engine state independent per key", async () => {
+    const registry = makeRegistry();
+    registry.create("a");
+    registry.create("b");
+
+    await registry.update("a", "hello");
+    registry.updateEmbedding("b", [0, 1, 0, 0]);
+
+    expect(registry.getSnapshot("a").vector).toEqual([0.5, 0, 0, 0]);
+    expect(registry.getSnapshot("b").vector).toEqual([0, 0.5, 0, 0]);
+  });
+
+  it("getOrCreate returns the existing engine or creates one", () => {
+    const registry = makeRegistry();
+    const first = registry.getOrCreate("k");
+    expect(registry.getOrCreate("k")).toBe(first);
+    expect(registry.size).toBe(1);
+  });
+
+  it("throws ENGINE_EXISTS when creating a duplicate key", () => {
+    const registry = makeRegistry();
+    registry.create("dup");
+    expect(() => registry.create("dup")).toThrow(EngineError);
+    expect(() => registry.create("dup")).toThrow('already registered under "dup"');
+  });
+
+  it("throws ENGINE_NOT_FOUND for unknown keys", () => {
+    const registry = makeRegistry();
+    try {
+      registry.getSnapshot("missing");
+      expect.unreachable();
+    } catch (err) {
+      expect((err as EngineError).code).toBe("ENGINE_NOT_FOUND");
+    }
+  });
+
+  it("compares two registered engines", () => {
+    const registry = makeRegistry();
+    registry.create("staging");
+    registry.create("production");
+    registry.updateEmbedding("staging", [1, 0, 0, 0]);
+    registry.updateEmbedding("production", [0, 1, 0, 0]);
+
+    const diff = registry.compare("staging", "production");
+    expect(diff.similarity).toBe(0);
+    expect(diff.euclideanDistance).toBeCloseTo(Math.SQRT1_2);
+    expect(() => registry.compare("staging", "missing")).toThrow(EngineError);
+  });
+
+  it("aggregates children into a fleet view", () => {
+    const registry = makeRegistry();
+    registry.create("a", { alpha: 1 });
+    registry.create("b", { alpha: 1 });
+    registry.create("idle");
+    registry.updateEmbedding("a", [1, 0, 0, 0]);
+    registry.updateEmbedding("b", [0, 1, 0, 0]);
+
+    const fleet = registry.aggregate(["a", "b"]);
+    expect(fleet.vector).toEqual([0.5, 0.5, 0, 0]);
+    expect(Object.keys(fleet.children)).toEqual(["a", "b"]);
+    expect(fleet.healthScore).toBeCloseTo(
+      (fleet.children["a"]! + fleet.children["b"]!) / 2,
+    );
+    expect(fleet.mostDegraded).not.toBeNull();
+
+    // Engines without a state are left out of the fused vector.
+    expect(registry.aggregate().vector).toEqual([0.5, 0.5, 0, 0]);
+    expect(() => registry.aggregate(["missing"])).toThrow(EngineError);
+  });
+
+  it("reports the most-degraded child", () => {
+    const registry = makeRegistry();
+    registry.create("steady", { alpha: 1 });
+    registry.create("drifting", { alpha: 1 });
+    registry.updateEmbedding("steady", [1, 0, 0, 0]);
+    registry.updateEmbedding("steady", [1, 0, 0, 0]);
+    registry.updateEmbedding("drifting", [1, 0, 0, 0]);
+    registry.updateEmbedding("drifting", [0, 1, 0, 0]);
+
+    expect(registry.aggregate().mostDegraded?.key).toBe("drifting");
+  });
+
+  it("returns an empty fleet view with no engines", () => {
+    const fleet = makeRegistry().aggregate();
+    expect(fleet).toEqual({
+      vector: [],
+      healthScore: 1,
+      children: {},
+      mostDegraded: null,
+    });
+  });
+
+  it("rolls children up into a parent engine", () => {
+    const registry = makeRegistry();
+    const parent = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+    expect(registry.rollUp(parent)).toBeNull();
+
+    registry.create("a", { alpha: 1 });
+    registry.create("b", { alpha: 1 });
+    registry.updateEmbedding("a", [1, 0, 0, 0]);
+    registry.updateEmbedding("b", [0, 1, 0, 0]);
+
+    expect(registry.rollUp(parent)).not.toBeNull();
+    expect(parent.getSnapshot().vector[0]).toBeCloseTo(Math.SQRT1_2);
+    expect(parent.getSnapshot().vector[1]).toBeCloseTo(Math.SQRT1_2);
+  });
+
+  it("exports every engine's gauges for Prometheus", () => {
+    const registry = makeRegistry();
+    registry.create("a");
+    registry.create("b");
+    registry.updateEmbedding("a", [1, 0, 0, 0]);
+
+    const text = registry.exportPrometheus();
+    expect(text).toContain(
+      'semantic_state_update_count{engine="a"} 1\nsemantic_state_update_count{engine="b"} 0\n',
+    );
+  });
+
+  it("returns every engine's snapshot keyed by registry key", () => {
+    const registry = makeRegistry();
+    registry.create("a");
+    registry.create("b");
+    registry.updateEmbedding("a", [1, 0, 0, 0]);
+
+    const snapshots = registry.getAllSnapshots();
+    expect(Object.keys(snapshots)).toEqual(["a", "b"]);
+    expect(snapshots.a!.vector).toEqual(registry.getSnapshot("a").vector);
+    expect(snapshots.b!.updateCount).toBe(0);
+    expect(Object.keys(registry.getAllSnapshots(["b"]))).toEqual(["b"]);
+    expect(() => registry.getAllSnapshots(["missing"])).toThrow(
+      EngineError,
+    );
+  });
+
+  it("evaluates every snapshot at the same instant", () => {
+    const registry = new EngineRegistry({
+      alpha: 1,
+      driftThreshold: 0.5,
+      stateHalfLifeMs: 1000,
+    });
+    registry.create("a").updateEmbedding([1, 0], 0);
+    registry.create("b").updateEmbedding([0, 1], 1000);
+
+    const snapshots = registry.getAllSnapshots(undefined, 2000);
+    expect(snapshots.a!.vector).toEqual([0.25, 0]);
+    expect(snapshots.b!.vector).toEqual([0, 0.5]);
+    expect(registry.aggregate(undefined, 2000).vector).toEqual([0.125, 0.25]);
+    expect(registry.exportPrometheus(undefined, undefined, 2000)).toContain(
+      'semantic_state_seconds_since_update{engine="a"} 2\n',
+    );
+  });
+});
@@ -0,0 +1,250 @@
+import {
+  SemanticStateEngine,
+  type SemanticStateEngineConfig,
+  type Snapshot,
+  type UpdateResult,
+} from "./SemanticStateEngine.js";
+import { EngineError } from "./errors.js";
+import { compareEngines, type SnapshotDiff } from "./diff.js";
+import { mean } from "../math/vector.js";
+import { formatPrometheus } from "./prometheus.js";
+
+/** Fleet-level roll-up of several engines, as returned by {@link EngineRegistry.aggregate}. */
+export interface FleetSnapshot {
+  /**
+   * Element-wise mean of the children's state vectors. Children that have
+   * not received an update yet are left out; empty if none has.
+   */
+  vector: number[];
+
+  /** Mean health score of the children (1 for an empty fleet). */
+  healthScore: number;
+
+  /** Health score of each child, by key. */
+  children: Record<string, number>;
+
+  /** The child with the lowest health score, or `null` for an empty fleet. */
+  mostDegraded: { key: string; healthScore: number } | null;
+}
+
+/**
+ * EngineRegistry manages many named {@link SemanticStateEngine} instances —
+ * e.g. one per conversation thread — behind a single object.
+ *
+ * Every engine is created from the registry's default configuration (which
+ * typically carries the shared embedding provider) merged with optional
+ * per-engine overrides.
+ */
+export class EngineRegistry {
+  private readonly defaults: SemanticStateEngineConfig;
+  private readonly engines = new Map<string, SemanticStateEngine>();
+
+  constructor(defaults: SemanticStateEngineConfig) {
+    this.defaults = defaults;
+  }
+
+  /** Number of registered engines. */
+  get size(): number {
+    return this.engines.size;
+  }
+
+  /**
+   * Creates and registers a new engine under `key`.
+   *
+   * @throws {EngineError} `ENGINE_EXISTS` if `key` is already registered.
+   */
+  create(
+    key: string,
+    overrides: Partial<SemanticStateEngineConfig> = {},
+  ): SemanticStateEngine {
+    if (this.engines.has(key)) {
+      throw new EngineError(
+        "ENGINE_EXISTS",
+        `An engine is already registered under "${key}"`,
+        { key },
+      );
+    }
+    const engine = new SemanticStateEngine({ ...this.defaults, ...overrides });
+    this.engines.set(key, engine);
+    return engine;
+  }
+
+  /** Returns the engine registered under `key`, creating it if necessary. */
+  getOrCreate(
+    key: string,
+    overrides: Partial<SemanticStateEngineConfig> = {},
+  ): SemanticStateEngine {
+    return this.engines.get(key) ?? this.create(key, overrides);
+  }
+
+  /** Returns the engine registered under `key`, if any. */
+  get(key: string): SemanticStateEngine | undefined {
+    return this.engines.get(key);
+  }
+
+  /** Whether an engine is registered under `key`. */
+  has(key: string): boolean {
+    return this.engines.has(key);
+  }
+
+  /** Keys of all registered engines, in insertion order. */
+  keys(): string[] {
+    return [...this.engines.keys()];
+  }
+
+  /**
+   * Removes the engine registered under `key`.
+   *
+   * @returns `true` if an engine was removed.
+   */
+  remove(key: string): boolean {
+    return this.engines.delete(key);
+  }
+
+  /** Embeds `text` and fuses it into the engine registered under `key`. */
+  update(key: string, text: string): Promise<UpdateResult | null> {
+    return this.require(key).update(text);
+  }
+
+  /** Fuses a pre-computed embedding into the engine registered under `key`. */
+  updateEmbedding(
+    key: string,
+    embedding: ArrayLike<number>,
+    timestamp?: number,
+  ): UpdateResult {
+    return this.require(key).updateEmbedding(embedding, timestamp);
+  }
+
+  /** Returns a snapshot of the engine registered under `key`. */
+  getSnapshot(key: string): Snapshot {
+    return this.require(key).getSnapshot();
+  }
+
+  /**
+   * Returns a snapshot of every engine registered under `keys` (default: all
+   * of them) as one plain object keyed by registry key, ready to be posted
+   * across a Worker boundary or serialized for a fleet view in a single step
+   * instead of one call per engine.
+   *
+   * @param now Unix timestamp (ms) every snapshot is evaluated at, so the
+   *            fleet view reflects one instant. Defaults to now.
+   * @throws {EngineError} `ENGINE_NOT_FOUND` for an unknown key.
+   */
+  getAllSnapshots(
+    keys: string[] = this.keys(),
+    now: number = Date.now(),
+  ): Record<string, Snapshot> {
+    const snapshots: Record<string, Snapshot> = {};
+    for (const key of keys) {
+      snapshots[key] = this.require(key).getSnapshot(now);
+    }
+    return snapshots;
+  }
+
+  /**
+   * Compares the current states of the engines registered under `keyA` and
+   * `keyB`, with deltas taken as `b − a`.
+   */
+  compare(keyA: string, keyB: string): SnapshotDiff {
+    return compareEngines(this.require(keyA), this.require(keyB));
+  }
+
+  /**
+   * Rolls the engines registered under `keys` (default: all of them) up into
+   * a single fleet-level view: their fused state vector, mean health and the
+   * most-degraded child.
+   *
+   * @param now Unix timestamp (ms) every child is evaluated at. Defaults to now.
+   * @throws {EngineError} `ENGINE_NOT_FOUND` for an unknown key, or
+   *   `DIMENSION_MISMATCH` if the children's states differ in dimension.
+   */
+  aggregate(
+    keys: string[] = this.keys(),
+    now: number = Date.now(),
+  ): FleetSnapshot {
+    const children: Record<string, number> = {};
+    const vectors: number[][] = [];
+    let healthSum = 0;
+    let mostDegraded: FleetSnapshot["mostDegraded"] = null;
+    for (const key of keys) {
+      const snapshot = this.require(key).getSnapshot(now);
+      children[key] = snapshot.healthScore;
+      healthSum += snapshot.healthScore;
+      if (
+        mostDegraded === null ||
+        snapshot.healthScore < mostDegraded.healthScore
+      ) {
+        mostDegraded = { key, healthScore: snapshot.healthScore };
+      }
+      if (snapshot.vector.length === 0) continue;
+      if (
+        vectors.length > 0 &&
+        snapshot.vector.length !== vectors[0]!.length
+      ) {
+        throw new EngineError(
+          "DIMENSION_MISMATCH",
+          `Engine "${key}" has dimension ${snapshot.vector.length}, expected ${vectors[0]!.length}`,
+          { key, expected: vectors[0]!.length, actual: snapshot.vector.length },
+        );
+      }
+      vectors.push(snapshot.vector);
+    }
+    return {
+      vector: vectors.length > 0 ? mean(vectors) : [],
+      healthScore: keys.length > 0 ? healthSum / keys.length : 1,
+      children,
+      mostDegraded,
+    };
+  }
+
+  /**
+   * Fuses the children's aggregated state vector (see {@link aggregate})
+   * into `parent`, so a parent engine tracks the fleet as a whole. The
+   * children are evaluated at `timestamp` (default: now).
+   *
+   * @returns The parent's update result, or `null` if no child has a state yet.
+   */
+  rollUp(
+    parent: SemanticStateEngine,
+    keys: string[] = this.keys(),
+    timestamp?: number,
+  ): UpdateResult | null {
+    const { vector } = this.aggregate(keys, timestamp);
+    if (vector.length === 0) return null;
+    return parent.updateEmbedding(vector, timestamp);
+  }
+
+  /**
+   * Renders the gauges of every registered engine in one Prometheus text
+   * exposition, each sample labelled with the engine's metadata and its key
+   * under `label`. See {@link SemanticStateEngine.exportPrometheus}.
+   *
+   * @param prefix Metric name prefix.
+   * @param label  Name of the label carrying the registry key.
+   * @param now    Unix timestamp (ms) every engine is evaluated at. Defaults
+   *               to now.
+   */
+  exportPrometheus(
+    prefix = "semantic_state",
+    label = "engine",
+    now: number = Date.now(),
+  ): string {
+    const series = [...this.engines].map(([key, engine]) => ({
+      labels: { ...engine.getMetadata(), [label]: key },
+      snapshot: engine.getSnapshot(now),
+    }));
+    return formatPrometheus(series, prefix, now);
+  }
+
+  private require(key: string): SemanticStateEngine {
+    const engine = this.engines.get(key);
+    if (engine === undefined) {
+      throw new EngineError(
+        "ENGINE_NOT_FOUND",
+        `No engine is registered under "${key}"`,
+        { key },
+      );
+    }
+    return engine;
+  }
+}
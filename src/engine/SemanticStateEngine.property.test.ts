@@ -0,0 +1,139 @@
+import { describe, it, expect } from "vitest";
+import { SemanticStateEngine } from "./SemanticStateEngine.js";
+import type { EngineState } from "./SemanticStateEngine.js";
+
+/**
+ * Seeded state-machine checks: each run applies a random sequence of engine
+ * operations and compares the engine against a tiny model of what the
+ * operations should do to the update count, checking the snapshot invariants
+ * after every step. Failures report the seed and step to reproduce them.
+ */
+const RUNS = 200;
+const STEPS = 40;
+const DIM = 4;
+
+function mulberry32(seed: number): () => number {
+  let a = seed;
+  return () => {
+    a = (a + 0x6d2b79f5) | 0;
+    let t = Math.imul(a ^ (a >>> 15), 1 | a);
+    t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t;
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+/** A random non-zero vector at a random scale between 1e-100 and 1e100. */
+function vector(next: () => number): number[] {
+  const scale = 10 ** (Math.floor(next() * 200) - 100);
+  const v = Array.from({ length: DIM }, () => (next() * 2 - 1) * scale);
+  v[Math.floor(next() * DIM)] = scale;
+  return v;
+}
+
+type Operation =
+  | "update"
+  | "evaluate"
+  | "checkpoint"
+  | "rollback"
+  | "reset"
+  | "resetBaseline"
+  | "exportImport";
+
+const OPERATIONS: Operation[] = [
+  "update",
+  "update",
+  "update",
+  "evaluate",
+  "checkpoint",
+  "rollback",
+  "reset",
+  "resetBaseline",
+  "exportImport",
+];
+
+function checkInvariants(engine: SemanticStateEngine, updateCount: number): void {
+  const snapshot = engine.getSnapshot();
+  expect(snapshot.updateCount).toBe(updateCount);
+  expect(snapshot.healthScore).toBeGreaterThanOrEqual(0);
+  expect(snapshot.healthScore).toBeLessThanOrEqual(1);
+  expect(snapshot.lastSimilarity).toBeGreaterThanOrEqual(-1);
+  expect(snapshot.lastSimilarity).toBeLessThanOrEqual(1);
+  expect(snapshot.lastDriftScore).toBeGreaterThanOrEqual(0);
+  if (updateCount === 0) return;
+  expect(snapshot.vector).toHaveLength(DIM);
+  expect(snapshot.vector.every(Number.isFinite)).toBe(true);
+}
+
+describe("SemanticStateEngine state machine properties", () => {
+  it("keeps its snapshot invariants under random operation sequences", () => {
+    for (let seed = 1; seed <= RUNS; seed++) {
+      const next = mulberry32(seed);
+      const engine = new SemanticStateEngine({
+        alpha: 0.05 + next() * 0.95,
+        driftThreshold: next(),
+      });
+      const checkpoints: { id: number; updateCount: number }[] = [];
+      let updateCount = 0;
+      let timestamp = 1_000_000;
+
+      for (let step = 0; step < STEPS; step++) {
+        const op = OPERATIONS[Math.floor(next() * OPERATIONS.length)]!;
+        timestamp += Math.floor(next() * 10_000);
+        try {
+          switch (op) {
+            case "update": {
+              const result = engine.updateEmbedding(vector(next), timestamp);
+              expect(result.similarity).toBeGreaterThanOrEqual(-1);
+              expect(result.similarity).toBeLessThanOrEqual(1);
+              expect(result.driftScore).toBeGreaterThanOrEqual(0);
+              updateCount++;
+              break;
+            }
+            case "evaluate": {
+              if (updateCount === 0) break;
+              const before = engine.exportState();
+              engine.evaluate(vector(next), timestamp);
+              expect(engine.exportState()).toEqual(before);
+              break;
+            }
+            case "checkpoint":
+              checkpoints.push({ id: engine.checkpoint(), updateCount });
+              break;
+            case "rollback": {
+              const retained = new Set(engine.getCheckpoints());
+              const live = checkpoints.filter((c) => retained.has(c.id));
+              if (live.length === 0) break;
+              const target = live[Math.floor(next() * live.length)]!;
+              engine.rollback(target.id);
+              updateCount = target.updateCount;
+              break;
+            }
+            case "reset":
+              engine.reset();
+              updateCount = 0;
+              break;
+            case "resetBaseline":
+              engine.resetBaseline(vector(next), timestamp);
+              updateCount = 1;
+              break;
+            case "exportImport": {
+              const state: EngineState = engine.exportState();
+              const copy = new SemanticStateEngine({
+                alpha: engine.getAlpha(),
+                driftThreshold: engine.getDriftThreshold(),
+              });
+              copy.importState(state);
+              expect(copy.exportState()).toEqual(state);
+              break;
+            }
+          }
+          checkInvariants(engine, updateCount);
+        } catch (err) {
+          throw new Error(
+            `seed ${seed}, step ${step} (${op}) failed: ${String(err)}`,
+          );
+        }
+      }
+    }
+  });
+});
@@ -1,6 +1,9 @@
 import { describe, it, expect, vi, beforeEach, afterEach } from "vitest";
 import { SemanticStateEngine } from "./SemanticStateEngine.js";
 import type { EmbeddingProvider } from "./SemanticStateEngine.js";
+import { EngineError } from "./errors.js";
+import { unpackUpdateResult } from "./packed.js";
+import { jsDivergence, klDivergence } from "../math/vector.js";
 
 const DIM = 4;
 
@@ -321,5 +324,4194 @@ describe("SemanticStateEngine (engine/)", () => {
       expect(typeof snap.timestamp).toBe("number");
     });
   });
-});
 
+  // ─── exportState / importState ──────────────────────────────────────────────
+
+  describe("exportState / importState", () => {
+    it("restarts the estimators when importing a state of another dimension", () => {
+      const config = {
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        driftMetric: "mahalanobis" as const,
+        historySimilarity: { size: 4 },
+        clustering: { k: 2 },
+      };
+      const engine = new SemanticStateEngine(config);
+      for (let t = 0; t < 5; t++) engine.updateEmbedding(vec([1, t, 0, 0]), t);
+
+      const other = new SemanticStateEngine(config);
+      other.updateEmbedding([1, 0], 0);
+      other.updateEmbedding([1, 1], 1);
+      engine.importState(other.exportState());
+
+      const result = engine.updateEmbedding([0, 1], 2);
+      expect(Number.isFinite(result.driftScore)).toBe(true);
+      expect(engine.getSnapshot().vector).toHaveLength(2);
+    });
+
+    it("round-trips the full internal state into a fresh engine", async () => {
+      const wm = makeProvider([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      const source = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: wm,
+      });
+      await source.update("first");
+      await source.update("second");
+
+      const exported = JSON.parse(JSON.stringify(source.exportState()));
+      const restored = new SemanticStateEngine({
+        alpha: 0.9,
+        driftThreshold: 0.1,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      restored.importState(exported);
+
+      expect(restored.exportState()).toEqual(source.exportState());
+      expect(restored.getSnapshot().vector).toEqual(source.getSnapshot().vector);
+    });
+
+    it("resumes fusion from the imported state rather than a zero origin", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([0, 1, 0, 0])]),
+      });
+      engine.importState({
+        stateVector: vec([1, 0, 0, 0]),
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        updateCount: 3,
+        lastDrift: 0,
+        lastUpdatedAt: Date.now(),
+      });
+
+      await engine.update("next");
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0.5);
+      expect(vector[1]).toBeCloseTo(0.5);
+    });
+
+    it("notifies subscribers on import", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.importState(engine.exportState());
+      expect(listener).toHaveBeenCalledTimes(1);
+    });
+
+    it("rejects a malformed state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      expect(() =>
+        engine.importState({ ...engine.exportState(), alpha: 0 }),
+      ).toThrow("Alpha must be in the range");
+      expect(() =>
+        engine.importState({ ...engine.exportState(), updateCount: 2 }),
+      ).toThrow("stateVector is empty");
+    });
+  });
+
+  // ─── Fusion strategies ──────────────────────────────────────────────────────
+
+  describe("fusion strategies", () => {
+    const inputs = [vec([1, 0, 0, 0]), vec([0, 1, 0, 0]), vec([0, 0, 1, 0])];
+
+    it("sma averages the last fusionWindow embeddings", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider(inputs),
+        fusionStrategy: "sma",
+        fusionWindow: 2,
+      });
+      await engine.update("a");
+      expect(engine.getSnapshot().vector).toEqual(vec([1, 0, 0, 0]));
+      await engine.update("b");
+      await engine.update("c");
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0);
+      expect(vector[1]).toBeCloseTo(0.5);
+      expect(vector[2]).toBeCloseTo(0.5);
+    });
+
+    it("wma weights the most recent embedding highest", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider(inputs),
+        fusionStrategy: "wma",
+        fusionWindow: 3,
+      });
+      await engine.update("a");
+      await engine.update("b");
+      await engine.update("c");
+      // Weights 1, 2, 3 over a total of 6.
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(1 / 6);
+      expect(vector[1]).toBeCloseTo(2 / 6);
+      expect(vector[2]).toBeCloseTo(3 / 6);
+    });
+
+    it("cumulative computes the running mean of every embedding", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider(inputs),
+        fusionStrategy: "cumulative",
+        fusionWindow: 1,
+      });
+      await engine.update("a");
+      await engine.update("b");
+      await engine.update("c");
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(1 / 3);
+      expect(vector[1]).toBeCloseTo(1 / 3);
+      expect(vector[2]).toBeCloseTo(1 / 3);
+    });
+
+    it("median takes the per-dimension median of the window", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0,
+        fusionStrategy: "median",
+        fusionWindow: 3,
+      });
+      engine.updateBatch([
+        vec([1, 0, 0, 0]),
+        vec([100, 0, 0, 0]),
+        vec([2, 1, 0, 0]),
+      ]);
+      expect(engine.getSnapshot().vector).toEqual([2, 0, 0, 0]);
+    });
+
+    it("setFusionStrategy switches strategy while keeping state", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider(inputs),
+      });
+      await engine.update("a");
+      await engine.update("b");
+      engine.setFusionStrategy("sma");
+      await engine.update("c");
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(1 / 3);
+      expect(vector[1]).toBeCloseTo(1 / 3);
+      expect(vector[2]).toBeCloseTo(1 / 3);
+    });
+
+    it("throws when fusionWindow is not a positive integer", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider: makeProvider(inputs),
+            fusionWindow: 0,
+          }),
+      ).toThrow("fusionWindow must be a positive integer");
+    });
+  });
+
+  // ─── Sliding-window drift baseline ──────────────────────────────────────────
+
+  describe("driftBaseline: window", () => {
+    // Three updates on one topic, then three on an orthogonal one.
+    const stream = [
+      vec([1, 0, 0, 0]),
+      vec([1, 0, 0, 0]),
+      vec([1, 0, 0, 0]),
+      vec([0, 1, 0, 0]),
+      vec([0, 1, 0, 0]),
+      vec([0, 1, 0, 0]),
+    ];
+
+    it("measures drift against the mean of the last driftWindow embeddings", async () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 0.1,
+        driftThreshold: 0.7,
+        onDriftDetected,
+        provider: makeProvider(stream),
+        driftBaseline: "window",
+        driftWindow: 2,
+      });
+
+      for (let i = 0; i < 4; i++) await engine.update(`event ${i}`);
+      expect(onDriftDetected).toHaveBeenCalledTimes(1);
+
+      // The window catches up with the new topic within two updates.
+      await engine.update("event 4");
+      await engine.update("event 5");
+      expect(onDriftDetected).toHaveBeenCalledTimes(1);
+    });
+
+    it("keeps firing for the same stream when measured against the EMA state", async () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 0.1,
+        driftThreshold: 0.7,
+        onDriftDetected,
+        provider: makeProvider(stream),
+      });
+
+      for (let i = 0; i < 6; i++) await engine.update(`event ${i}`);
+      expect(onDriftDetected).toHaveBeenCalledTimes(3);
+    });
+
+    it("throws when driftWindow is not a positive integer", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider: makeProvider([vec([1, 0, 0, 0])]),
+            driftWindow: 1.5,
+          }),
+      ).toThrow("driftWindow must be a positive integer");
+    });
+  });
+
+  // ─── UpdateResult / updateEmbedding / updateBatch ───────────────────────────
+
+  describe("update results", () => {
+    it("update resolves with an UpdateResult describing the fusion", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]),
+      });
+
+      const first = await engine.update("first");
+      expect(first).toMatchObject({
+        vector: vec([1, 0, 0, 0]),
+        similarity: 1,
+        driftScore: 0,
+        driftDetected: false,
+      });
+
+      const second = await engine.update("second");
+      expect(second!.driftDetected).toBe(true);
+      expect(second!.similarity).toBeCloseTo(0);
+      expect(second!.driftScore).toBeCloseTo(1);
+    });
+
+    it("update resolves with null when the provider drops the request", async () => {
+      const provider = {
+        getEmbedding: vi.fn().mockResolvedValue(null),
+      } as unknown as EmbeddingProvider;
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider,
+      });
+      await expect(engine.update("early")).resolves.toBeNull();
+      expect(engine.exportState().updateCount).toBe(0);
+    });
+
+    it("updateEmbedding fuses a vector without calling the provider", () => {
+      const wm = makeProvider([vec([1, 0, 0, 0])]);
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: wm,
+      });
+      const result = engine.updateEmbedding(new Float32Array(vec([1, 0, 0, 0])), 1234);
+      expect(wm.getEmbedding).not.toHaveBeenCalled();
+      expect(result.timestamp).toBe(1234);
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(0.5);
+    });
+
+    it("returns a vector that does not alias the retained windows", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        fusionStrategy: "sma",
+        fusionWindow: 2,
+        historySimilarity: { size: 2 },
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const first = engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      first.vector!.fill(99);
+
+      expect(engine.updateEmbedding(vec([1, 0, 0, 0]), 1).historySimilarity).toBeCloseTo(1);
+      expect(engine.getSnapshot().vector).toEqual(vec([1, 0, 0, 0]));
+    });
+  });
+
+  describe("updateBatch", () => {
+    it("fuses every embedding in order and returns one result per embedding", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const results = engine.updateBatch(
+        [vec([1, 0, 0, 0]), vec([0, 1, 0, 0])],
+        [100, 200],
+      );
+
+      expect(results).toHaveLength(2);
+      expect(results.map((r) => r.timestamp)).toEqual([100, 200]);
+      expect(results[1]!.driftDetected).toBe(true);
+      const { vector, timestamp } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0.25);
+      expect(vector[1]).toBeCloseTo(0.5);
+      expect(timestamp).toBe(200);
+    });
+
+    it("notifies subscribers once per batch", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([1, 1, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(listener).toHaveBeenCalledTimes(1);
+    });
+
+    it("keeps earlier embeddings and notifies when a later one is rejected", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      expect(() => engine.updateBatch([vec([1, 0, 0, 0]), [1, 0]])).toThrow(
+        "Embedding dimension mismatch",
+      );
+      expect(engine.exportState().updateCount).toBe(1);
+      expect(listener).toHaveBeenCalledTimes(1);
+    });
+
+    it("throws when timestamps do not match the embedding count", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      expect(() => engine.updateBatch([vec([1, 0, 0, 0])], [1, 2])).toThrow(
+        "Timestamp count mismatch",
+      );
+    });
+  });
+
+  // ─── Adaptive alpha ─────────────────────────────────────────────────────────
+
+  describe("adaptiveAlpha", () => {
+    it("uses minAlpha when the incoming embedding matches the state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        adaptiveAlpha: { minAlpha: 0.1, maxAlpha: 0.9 },
+      });
+      const [first, second] = engine.updateBatch([vec([1, 0, 0, 0]), vec([1, 0, 0, 0])]);
+      expect(first!.alpha).toBe(0.5);
+      expect(second!.alpha).toBeCloseTo(0.1);
+    });
+
+    it("uses maxAlpha for orthogonal embeddings so the state reacts quickly", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        adaptiveAlpha: { minAlpha: 0.1, maxAlpha: 0.9 },
+      });
+      const [, second] = engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(second!.alpha).toBeCloseTo(0.9);
+      // S_2 = 0.9·[0,1,0,0] + 0.1·[0.5,0,0,0]
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0.05);
+      expect(vector[1]).toBeCloseTo(0.9);
+    });
+
+    it("interpolates between the bounds for intermediate drift", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.1,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        adaptiveAlpha: { minAlpha: 0.2, maxAlpha: 0.6 },
+      });
+      const [, second] = engine.updateBatch([vec([1, 0, 0, 0]), vec([1, 1, 0, 0])]);
+      const drift = 1 - Math.SQRT1_2;
+      expect(second!.driftScore).toBeCloseTo(drift);
+      expect(second!.alpha).toBeCloseTo(0.2 + 0.4 * drift);
+    });
+
+    it("throws on invalid bounds", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider: makeProvider([vec([1, 0, 0, 0])]),
+            adaptiveAlpha: { minAlpha: 0.8, maxAlpha: 0.2 },
+          }),
+      ).toThrow("adaptiveAlpha bounds");
+    });
+  });
+
+  // ─── Drift metrics ──────────────────────────────────────────────────────────
+
+  describe("driftMetric", () => {
+    it("euclidean detects magnitude shifts that cosine ignores", () => {
+      const cosine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const euclidean = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 1,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        driftMetric: "euclidean",
+      });
+      const stream = [vec([1, 0, 0, 0]), vec([4, 0, 0, 0])];
+
+      expect(cosine.updateBatch(stream)[1]!.driftDetected).toBe(false);
+      const result = euclidean.updateBatch(stream)[1]!;
+      expect(result.driftScore).toBeCloseTo(3);
+      expect(result.driftDetected).toBe(true);
+    });
+
+    it("manhattan reports the L1 distance as the drift score", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 10,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        driftMetric: "manhattan",
+      });
+      const [, result] = engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 2, 0, 0])]);
+      expect(result!.driftScore).toBeCloseTo(3);
+      expect(result!.driftDetected).toBe(false);
+    });
+
+    it("angular reports angle / π and still exposes cosine similarity", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.4,
+        onDriftDetected,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        driftMetric: "angular",
+      });
+      const [, result] = engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(result!.driftScore).toBeCloseTo(0.5);
+      expect(result!.similarity).toBeCloseTo(0);
+      expect(onDriftDetected).toHaveBeenCalledWith(vec([0, 1, 0, 0]), result!.driftScore);
+    });
+  });
+
+  // ─── Structured errors ──────────────────────────────────────────────────────
+
+  describe("EngineError codes", () => {
+    function codeOf(fn: () => unknown): string | undefined {
+      try {
+        fn();
+      } catch (err) {
+        return err instanceof EngineError ? err.code : undefined;
+      }
+      return undefined;
+    }
+
+    it("reports DIMENSION_MISMATCH with expected / actual details", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      try {
+        engine.updateEmbedding([1, 0]);
+        expect.unreachable();
+      } catch (err) {
+        expect(err).toBeInstanceOf(EngineError);
+        expect((err as EngineError).code).toBe("DIMENSION_MISMATCH");
+        expect((err as EngineError).details).toEqual({ expected: 4, actual: 2 });
+      }
+    });
+
+    it("rejects update with DIMENSION_MISMATCH through the provider path", async () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0]), [1, 0]]),
+      });
+      await engine.update("first");
+      await expect(engine.update("second")).rejects.toMatchObject({
+        code: "DIMENSION_MISMATCH",
+      });
+    });
+
+    it("reports EMPTY_EMBEDDING for zero-length embeddings", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      expect(codeOf(() => engine.updateEmbedding([]))).toBe("EMPTY_EMBEDDING");
+      expect(engine.exportState().updateCount).toBe(0);
+    });
+
+    it("reports INVALID_CONFIG for an out-of-range alpha", () => {
+      expect(
+        codeOf(
+          () =>
+            new SemanticStateEngine({
+              alpha: 1.5,
+              driftThreshold: 0.75,
+              provider: makeProvider([vec([1, 0, 0, 0])]),
+            }),
+        ),
+      ).toBe("INVALID_CONFIG");
+    });
+
+    it("reports INVALID_STATE for a malformed imported state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      expect(
+        codeOf(() => engine.importState({ ...engine.exportState(), updateCount: -1 })),
+      ).toBe("INVALID_STATE");
+    });
+  });
+
+  // ─── Drift hysteresis / cool-down ───────────────────────────────────────────
+
+  describe("drift debouncing", () => {
+    const a = vec([1, 0, 0, 0]);
+    const b = vec([0, 1, 0, 0]);
+
+    it("requires driftConsecutive exceedances before reporting drift", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 0.1,
+        driftThreshold: 0.75,
+        onDriftDetected,
+        provider: makeProvider([a]),
+        driftConsecutive: 2,
+      });
+      // Settle on a, then b (1st exceedance), a (streak reset), b, b (2nd in a row).
+      const results = engine
+        .updateBatch([a, a, a, a, a, b, a, b, b])
+        .slice(4);
+      expect(results.map((r) => r.thresholdExceeded)).toEqual([
+        false,
+        true,
+        false,
+        true,
+        true,
+      ]);
+      expect(results.map((r) => r.driftDetected)).toEqual([
+        false,
+        false,
+        false,
+        false,
+        true,
+      ]);
+      expect(onDriftDetected).toHaveBeenCalledTimes(1);
+    });
+
+    it("suppresses further reports during the cool-down period", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 0.1,
+        driftThreshold: 0.75,
+        onDriftDetected,
+        provider: makeProvider([a]),
+        driftCooldownMs: 1000,
+      });
+      const results = engine.updateBatch(
+        [a, b, a, b, a],
+        [0, 100, 200, 900, 1200],
+      );
+      expect(results.map((r) => r.driftDetected)).toEqual([
+        false,
+        true,
+        false,
+        false,
+        true,
+      ]);
+      expect(onDriftDetected).toHaveBeenCalledTimes(2);
+    });
+
+    it("throws on an invalid driftConsecutive", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider: makeProvider([a]),
+            driftConsecutive: 0,
+          }),
+      ).toThrow("driftConsecutive must be a positive integer");
+    });
+  });
+
+  // ─── History ────────────────────────────────────────────────────────────────
+
+  describe("getHistory", () => {
+    const a = vec([1, 0, 0, 0]);
+    const b = vec([0, 1, 0, 0]);
+
+    it("records timestamp, drift and post-update health for every update", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+      });
+      engine.updateBatch([a, b], [1000, 2000]);
+
+      const history = engine.getHistory();
+      expect(history).toHaveLength(2);
+      expect(history[0]).toEqual({ timestamp: 1000, driftScore: 0, healthScore: 1 });
+      expect(history[1]!.timestamp).toBe(2000);
+      expect(history[1]!.driftScore).toBeCloseTo(1);
+      expect(history[1]!.healthScore).toBeCloseTo(0.5);
+    });
+
+    it("evicts the oldest entries beyond historySize", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+        historySize: 2,
+      });
+      engine.updateBatch([a, a, a], [1, 2, 3]);
+      expect(engine.getHistory().map((h) => h.timestamp)).toEqual([2, 3]);
+    });
+
+    it("is disabled when historySize is 0", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+        historySize: 0,
+      });
+      engine.updateBatch([a, a]);
+      expect(engine.getHistory()).toEqual([]);
+    });
+
+    it("returns a copy", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+      });
+      engine.updateEmbedding(a, 5);
+      engine.getHistory()[0]!.timestamp = 999;
+      expect(engine.getHistory()[0]!.timestamp).toBe(5);
+    });
+  });
+
+  // ─── reset / resetBaseline ──────────────────────────────────────────────────
+
+  describe("reset", () => {
+    const a = vec([1, 0, 0, 0]);
+    const b = vec([0, 1, 0, 0]);
+
+    it("clears all state but keeps configuration", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+        fusionStrategy: "sma",
+      });
+      engine.updateBatch([a, b]);
+      engine.reset();
+
+      const state = engine.exportState();
+      expect(state.updateCount).toBe(0);
+      expect(state.stateVector).toEqual([]);
+      expect(state.history).toEqual([]);
+      expect(state.fusionStrategy).toBe("sma");
+
+      // The next update establishes a fresh baseline of any dimension.
+      const result = engine.updateEmbedding([0, 0, 1]);
+      expect(result.driftScore).toBe(0);
+      expect(engine.getSnapshot().vector).toEqual([0, 0, 1]);
+    });
+
+    it("notifies subscribers", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+      });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.reset();
+      expect(listener).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe("resetBaseline", () => {
+    const a = vec([1, 0, 0, 0]);
+    const b = vec([0, 1, 0, 0]);
+
+    it("re-seeds the state from the embedding without blending", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        onDriftDetected,
+        provider: makeProvider([a]),
+      });
+      engine.updateBatch([a, a]);
+      engine.resetBaseline(b, 42);
+
+      expect(engine.getSnapshot().vector).toEqual(b);
+      expect(engine.getSnapshot().timestamp).toBe(42);
+      expect(engine.updateEmbedding(b).driftDetected).toBe(false);
+      expect(onDriftDetected).not.toHaveBeenCalled();
+    });
+
+    it("keeps the update history", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+      });
+      engine.updateBatch([a, a]);
+      engine.resetBaseline(b);
+      expect(engine.getHistory()).toHaveLength(2);
+    });
+
+    it("rejects an empty embedding", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+      });
+      expect(() => engine.resetBaseline([])).toThrow(EngineError);
+    });
+
+    it("rejects a baseline of another dimension before changing anything", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.updateEmbedding(a, 1000);
+      const before = engine.exportState();
+      expect(() => engine.resetBaseline([0, 1], 2000)).toThrow(
+        "expected 4, got 2",
+      );
+      expect(engine.exportState()).toEqual(before);
+
+      const sized = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        dimension: 4,
+      });
+      expect(() => sized.resetBaseline([0, 1])).toThrow(EngineError);
+    });
+  });
+
+  // ─── getSnapshotRaw ─────────────────────────────────────────────────────────
+
+  describe("getSnapshotRaw", () => {
+    it("returns the state vector as a Float32Array alongside the usual fields", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+
+      const raw = engine.getSnapshotRaw();
+      const plain = engine.getSnapshot();
+      expect(raw.vector).toBeInstanceOf(Float32Array);
+      expect(Array.from(raw.vector)).toEqual(plain.vector);
+      expect(raw.timestamp).toBe(plain.timestamp);
+      expect(raw.semanticSummary).toBe(plain.semanticSummary);
+    });
+
+    it("returns a copy, not a view of the internal state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      engine.getSnapshotRaw().vector[0] = 999;
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(0.5);
+    });
+
+    it("getSnapshotRaw64 keeps double precision end to end", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      const precise = 1 + 1e-12;
+      engine.updateEmbedding(new Float64Array([precise, 0, 0, 0]));
+
+      const raw = engine.getSnapshotRaw64();
+      expect(raw.vector).toBeInstanceOf(Float64Array);
+      expect(raw.vector[0]).toBe(precise);
+      expect(engine.getSnapshotRaw().vector[0]).toBe(1);
+    });
+  });
+
+  // ─── Kalman fusion ──────────────────────────────────────────────────────────
+
+  describe("fusionStrategy: kalman", () => {
+    const a = vec([1, 0, 0, 0]);
+
+    function makeKalmanEngine(): SemanticStateEngine {
+      return new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+        fusionStrategy: "kalman",
+        kalman: { processNoise: 0, measurementNoise: 1 },
+      });
+    }
+
+    it("seeds the state from the first embedding with measurement-level uncertainty", () => {
+      const engine = makeKalmanEngine();
+      engine.updateEmbedding(a);
+      const snap = engine.getSnapshot();
+      expect(snap.vector).toEqual(a);
+      expect(snap.uncertainty).toBeCloseTo(1);
+    });
+
+    it("reports shrinking uncertainty as the state is observed more", () => {
+      const engine = makeKalmanEngine();
+      engine.updateEmbedding(a);
+      engine.updateEmbedding(a);
+      const after2 = engine.getSnapshot().uncertainty!;
+      engine.updateEmbedding(a);
+      const after3 = engine.getSnapshot().uncertainty!;
+      expect(after2).toBeCloseTo(1 / 2);
+      expect(after3).toBeCloseTo(1 / 3);
+    });
+
+    it("blends new embeddings by the Kalman gain", () => {
+      const engine = makeKalmanEngine();
+      engine.updateEmbedding(a);
+      // P⁻ = 1, K = 0.5
+      engine.updateEmbedding(vec([0, 1, 0, 0]));
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0.5);
+      expect(vector[1]).toBeCloseTo(0.5);
+    });
+
+    it("omits uncertainty for other strategies", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([a]),
+      });
+      engine.updateEmbedding(a);
+      expect(engine.getSnapshot().uncertainty).toBeUndefined();
+    });
+
+    it("throws on invalid noise parameters", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider: makeProvider([a]),
+            kalman: { measurementNoise: 0 },
+          }),
+      ).toThrow("kalman noise must satisfy");
+    });
+  });
+
+  // ─── Change detectors ───────────────────────────────────────────────────────
+
+  describe("changeDetector", () => {
+    it("page-hinkley reports slow cumulative drift the threshold misses", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        changeDetector: { type: "page-hinkley", delta: 0.001, lambda: 0.3 },
+      });
+
+      // Rotate the input by a slowly growing angle on every update (α = 1, so
+      // the state is the previous input): drift ramps up but the similarity
+      // never drops below 0.7.
+      let angle = 0;
+      const stream = Array.from({ length: 40 }, (_, i) => {
+        angle += 0.02 * i;
+        return vec([Math.cos(angle), Math.sin(angle), 0, 0]);
+      });
+      const results = engine.updateBatch(stream);
+
+      expect(results.some((r) => r.driftDetected)).toBe(false);
+      expect(results.some((r) => r.changePointDetected)).toBe(true);
+    });
+
+    it("cusum flags a sustained shift in drift scores", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        changeDetector: { type: "cusum", slack: 0.01, threshold: 0.5 },
+      });
+      // Alternate between two inputs: first 30° apart, then 50° apart.
+      const at = (deg: number) =>
+        vec([Math.cos((deg * Math.PI) / 180), Math.sin((deg * Math.PI) / 180), 0, 0]);
+      const calm = Array.from({ length: 20 }, (_, i) => at(i % 2 === 0 ? 0 : 30));
+      const shifted = Array.from({ length: 10 }, (_, i) => at(i % 2 === 0 ? 0 : 50));
+
+      const calmResults = engine.updateBatch(calm);
+      expect(calmResults.some((r) => r.changePointDetected)).toBe(false);
+      const shiftedResults = engine.updateBatch(shifted);
+      expect(shiftedResults.some((r) => r.driftDetected)).toBe(false);
+      // Needs a few shifted samples to accumulate, not just the first one.
+      const firstChange = shiftedResults.findIndex((r) => r.changePointDetected);
+      expect(firstChange).toBeGreaterThan(1);
+    });
+
+    it("adwin can be selected as the change-detection backend", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        changeDetector: { type: "adwin", delta: 0.01 },
+      });
+      const at = (deg: number) =>
+        vec([Math.cos((deg * Math.PI) / 180), Math.sin((deg * Math.PI) / 180), 0, 0]);
+      const calm = Array.from({ length: 60 }, (_, i) => at(i % 2 === 0 ? 0 : 20));
+      const shifted = Array.from({ length: 60 }, (_, i) => at(i % 2 === 0 ? 0 : 70));
+
+      expect(engine.updateBatch(calm).some((r) => r.changePointDetected)).toBe(false);
+      expect(engine.updateBatch(shifted).some((r) => r.changePointDetected)).toBe(true);
+    });
+
+    it("is always false without a configured detector", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const results = engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(results.map((r) => r.changePointDetected)).toEqual([false, false]);
+    });
+
+    it("reports invalid detector parameters as INVALID_CONFIG", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider: makeProvider([vec([1, 0, 0, 0])]),
+            changeDetector: { type: "page-hinkley", lambda: -1 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  // ─── Drift attribution ──────────────────────────────────────────────────────
+
+  describe("attributionTopK", () => {
+    it("reports the top-k dimensions behind the drift", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        attributionTopK: 2,
+      });
+      const [first, second] = engine.updateBatch([
+        vec([1, 0, 0, 0]),
+        vec([0, 0, 1, 0]),
+      ]);
+
+      expect(first!.attribution).toBeUndefined();
+      expect(second!.attribution).toHaveLength(2);
+      expect(second!.attribution!.map((c) => c.index).sort()).toEqual([0, 2]);
+      const total = second!.attribution!.reduce((sum, c) => sum + c.contribution, 0);
+      expect(total).toBeCloseTo(second!.driftScore);
+    });
+
+    it("is omitted by default", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+      });
+      const [, second] = engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(second!.attribution).toBeUndefined();
+    });
+  });
+
+  // ─── Health configuration ───────────────────────────────────────────────────
+
+  describe("health config", () => {
+    afterEach(() => {
+      vi.useRealTimers();
+    });
+
+    it("uses the configured age decay rate", () => {
+      vi.useFakeTimers();
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        health: { ageDecayRate: 1 / 3_600_000 },
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+
+      vi.advanceTimersByTime(30 * 60_000);
+
+      expect(engine.getSnapshot().healthScore).toBeCloseTo(0.5);
+    });
+
+    it("clamps to the configured floor and ceiling", () => {
+      vi.useFakeTimers();
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        health: { floor: 0.2, ceiling: 0.9 },
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().healthScore).toBeCloseTo(0.9);
+
+      vi.advanceTimersByTime(60_000);
+      expect(engine.getSnapshot().healthScore).toBeCloseTo(0.2);
+    });
+
+    it("setHealthConfig changes individual parameters and notifies", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        provider: makeProvider([vec([1, 0, 0, 0])]),
+        health: { ageDecayRate: 0 },
+      });
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(engine.getSnapshot().healthScore).toBeCloseTo(0.5);
+
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.setHealthConfig({ driftWeight: 0.25 });
+
+      expect(listener).toHaveBeenCalledTimes(1);
+      expect(engine.getSnapshot().healthScore).toBeCloseTo(0.75);
+      expect(engine.getHealthConfig()).toEqual({
+        ageDecayRate: 0,
+        driftWeight: 0.25,
+        floor: 0,
+        ceiling: 1,
+        factors: {
+          age: { weight: 0, curve: "linear" },
+          drift: { weight: 0.25, curve: "linear" },
+          variance: { weight: 0, curve: "linear" },
+          updateRate: { weight: 0, curve: "linear" },
+        },
+      });
+    });
+
+    it("rejects invalid parameters", () => {
+      const provider = makeProvider([vec([1, 0, 0, 0])]);
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            provider,
+            health: { ageDecayRate: -1 },
+          }),
+      ).toThrow(EngineError);
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        provider,
+      });
+      expect(() => engine.setHealthConfig({ floor: 0.8, ceiling: 0.5 })).toThrow(
+        /floor <= ceiling/,
+      );
+    });
+  });
+
+  // ─── Provider-less engines ──────────────────────────────────────────────────
+
+  describe("without a provider", () => {
+    it("accepts precomputed embeddings", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      const result = engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      expect(result.timestamp).toBe(1000);
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(0.5);
+    });
+
+    it("rejects update(text) with NO_PROVIDER", async () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      await expect(engine.update("hello")).rejects.toMatchObject({
+        code: "NO_PROVIDER",
+      });
+    });
+  });
+
+  // ─── Weighted updates ───────────────────────────────────────────────────────
+
+  describe("updateWeighted", () => {
+    it("scales the EMA α by the weight", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.2, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+
+      // α' = 0.2 · 3 = 0.6 → S = 0.6·[0,1,0,0] + 0.4·[0.2,0,0,0]
+      const result = engine.updateWeighted(vec([0, 1, 0, 0]), 3);
+      expect(result.alpha).toBeCloseTo(0.6);
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0.08);
+      expect(vector[1]).toBeCloseTo(0.6);
+    });
+
+    it("caps the effective α at 1", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      const result = engine.updateWeighted(vec([0, 1, 0, 0]), 10);
+      expect(result.alpha).toBe(1);
+      expect(engine.getSnapshot().vector).toEqual([0, 1, 0, 0]);
+    });
+
+    it("rejects a zero weight before changing anything", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      const before = engine.exportState();
+
+      expect(() => engine.updateWeighted(vec([0, 1, 0, 0]), 0)).toThrow(
+        "weight must be a finite positive number",
+      );
+      expect(engine.exportState()).toEqual(before);
+    });
+
+    it("trusts low-noise Kalman measurements more with a higher weight", () => {
+      const make = () => {
+        const engine = new SemanticStateEngine({
+          alpha: 0.5,
+          driftThreshold: 0,
+          fusionStrategy: "kalman",
+        });
+        engine.updateEmbedding(vec([1, 0, 0, 0]));
+        return engine;
+      };
+      const normal = make();
+      const confident = make();
+      normal.updateEmbedding(vec([0, 1, 0, 0]));
+      confident.updateWeighted(vec([0, 1, 0, 0]), 4);
+
+      expect(confident.getSnapshot().vector[1]).toBeGreaterThan(
+        normal.getSnapshot().vector[1]!,
+      );
+    });
+
+    it("rejects negative or non-finite weights", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      expect(() => engine.updateWeighted(vec([1, 0, 0, 0]), -1)).toThrow(EngineError);
+      expect(() => engine.updateWeighted(vec([1, 0, 0, 0]), NaN)).toThrow(
+        /finite positive/,
+      );
+    });
+  });
+
+  // ─── Auto threshold ─────────────────────────────────────────────────────────
+
+  describe("autoThreshold", () => {
+    const angle = (theta: number) => vec([Math.cos(theta), Math.sin(theta), 0, 0]);
+    // Jitter between 0 and 0.1 rad: every drift score is 1 − cos(0.1) ≈ 0.005.
+    const jitter = (count: number) =>
+      Array.from({ length: count }, (_, i) => angle(i % 2 === 0 ? 0 : 0.1));
+
+    it("keeps the fixed threshold until minSamples drift scores are seen", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        autoThreshold: { quantile: 0.9, minSamples: 10 },
+      });
+      engine.updateBatch(jitter(10));
+      expect(engine.getAutoThreshold()).toBeNull();
+
+      engine.updateEmbedding(angle(0));
+      expect(engine.getAutoThreshold()).toBeCloseTo(1 - Math.cos(0.1), 6);
+    });
+
+    it("reports drift beyond the learned quantile of drift scores", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0, // a cosine threshold of 0 alone never fires
+        autoThreshold: { minSamples: 10 },
+      });
+      const results = engine.updateBatch([...jitter(30), angle(1)]);
+
+      expect(results.slice(0, 30).some((r) => r.driftDetected)).toBe(false);
+      expect(results[30]!.driftDetected).toBe(true);
+    });
+
+    it("is disabled by default", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateBatch(jitter(30));
+      expect(engine.getAutoThreshold()).toBeNull();
+    });
+
+    it("rejects out-of-range options", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0,
+            autoThreshold: { quantile: 1 },
+          }),
+      ).toThrow(EngineError);
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0,
+            autoThreshold: { minSamples: 0 },
+          }),
+      ).toThrow(/minSamples/);
+    });
+  });
+
+  // ─── compareTo ──────────────────────────────────────────────────────────────
+
+  describe("compareTo", () => {
+    it("diffs an earlier snapshot against the current state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        health: { ageDecayRate: 0 },
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      const before = engine.getSnapshot();
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 2000);
+
+      const diff = engine.compareTo(before);
+      expect(diff.similarity).toBeCloseTo(0);
+      expect(diff.euclideanDistance).toBeCloseTo(Math.SQRT2);
+      expect(diff.timeDelta).toBe(1000);
+      expect(diff.healthDelta).toBeCloseTo(-0.5);
+    });
+  });
+
+  // ─── Reference baselines ────────────────────────────────────────────────────
+
+  describe("references", () => {
+    it("reports the state's similarity to each named reference", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.addReference("golden", vec([1, 0, 0, 0]));
+      engine.addReference("offTopic", vec([0, 1, 0, 0]));
+
+      const result = engine.updateEmbedding(vec([1, 1, 0, 0]));
+      expect(result.referenceSimilarities!.golden).toBeCloseTo(Math.SQRT1_2);
+      expect(result.referenceSimilarities!.offTopic).toBeCloseTo(Math.SQRT1_2);
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      const { referenceSimilarities } = engine.getSnapshot();
+      expect(referenceSimilarities).toEqual({ golden: 1, offTopic: 0 });
+    });
+
+    it("is omitted when no references are registered", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      const result = engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(result.referenceSimilarities).toBeUndefined();
+      expect(engine.getSnapshot().referenceSimilarities).toBeUndefined();
+    });
+
+    it("removeReference unregisters a reference", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.addReference("golden", vec([1, 0, 0, 0]));
+      expect(engine.getReferenceNames()).toEqual(["golden"]);
+      expect(engine.removeReference("golden")).toBe(true);
+      expect(engine.removeReference("golden")).toBe(false);
+      expect(engine.getReferenceNames()).toEqual([]);
+    });
+
+    it("enforces a single dimension across references and embeddings", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.addReference("golden", vec([1, 0, 0, 0]));
+      expect(() => engine.addReference("other", [1, 0])).toThrow(EngineError);
+      expect(() => engine.updateEmbedding([1, 0])).toThrow(
+        "Embedding dimension mismatch",
+      );
+      // Replacing the only reference may change its dimension.
+      engine.addReference("golden", [1, 0]);
+      expect(() => engine.updateEmbedding([1, 0])).not.toThrow();
+    });
+
+    it("survives exportState / importState and reset()", () => {
+      const source = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      source.addReference("golden", vec([1, 0, 0, 0]));
+      source.updateEmbedding(vec([1, 0, 0, 0]));
+
+      const restored = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      restored.importState(source.exportState());
+      expect(restored.getReferenceNames()).toEqual(["golden"]);
+
+      restored.reset();
+      expect(restored.getReferenceNames()).toEqual(["golden"]);
+    });
+  });
+
+  // ─── State decay ────────────────────────────────────────────────────────────
+
+  describe("stateHalfLifeMs", () => {
+    afterEach(() => {
+      vi.useRealTimers();
+    });
+
+    it("halves the reported state vector every half-life without updates", () => {
+      vi.useFakeTimers();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        stateHalfLifeMs: 60_000,
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(1);
+
+      vi.advanceTimersByTime(60_000);
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(0.5);
+      expect(engine.getSnapshotRaw().vector[0]).toBeCloseTo(0.5);
+
+      vi.advanceTimersByTime(60_000);
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(0.25);
+    });
+
+    it("fuses the next update into the decayed state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        stateHalfLifeMs: 1000,
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      // S = 0.5 decays to 0.25 after one half-life; 0.5·1 + 0.5·0.25 = 0.625
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      expect(engine.exportState().stateVector[0]).toBeCloseTo(0.625);
+    });
+
+    it("rejects non-positive half-lives", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.75,
+            stateHalfLifeMs: 0,
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  // ─── fromConfig ─────────────────────────────────────────────────────────────
+
+  describe("fromConfig", () => {
+    it("builds an engine from JSON plus separately supplied bindings", async () => {
+      const onDriftDetected = vi.fn();
+      const engine = SemanticStateEngine.fromConfig(
+        JSON.stringify({ alpha: 1, driftThreshold: 0.75, historySize: 1 }),
+        {
+          provider: makeProvider([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]),
+          onDriftDetected,
+        },
+      );
+      await engine.update("first");
+      await engine.update("second");
+
+      expect(onDriftDetected).toHaveBeenCalledTimes(1);
+      expect(engine.getHistory()).toHaveLength(1);
+    });
+
+    it("still applies the constructor's range checks", () => {
+      expect(() =>
+        SemanticStateEngine.fromConfig({ alpha: 2, driftThreshold: 0.75 }),
+      ).toThrow("Alpha must be in the range (0, 1], got 2");
+    });
+  });
+
+  // ─── Runtime tuning ─────────────────────────────────────────────────────────
+
+  describe("getters and setters", () => {
+    it("exposes the update count and dimension", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      expect(engine.getUpdateCount()).toBe(0);
+      expect(engine.getDimension()).toBeNull();
+
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([1, 0, 0, 0])]);
+      expect(engine.getUpdateCount()).toBe(2);
+      expect(engine.getDimension()).toBe(4);
+    });
+
+    it("setDriftThreshold applies to subsequent updates without losing state", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.updateEmbedding(vec([1, 1, 0, 0])).driftDetected).toBe(true);
+
+      engine.setDriftThreshold(0.5);
+      expect(engine.getDriftThreshold()).toBe(0.5);
+      expect(engine.updateEmbedding(vec([1, 1, 1, 0])).driftDetected).toBe(false);
+      expect(engine.getUpdateCount()).toBe(3);
+    });
+
+    it("setAlpha changes the EMA weight of subsequent updates", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      engine.setAlpha(0.25);
+      expect(engine.getAlpha()).toBe(0.25);
+
+      const result = engine.updateEmbedding(vec([0, 1, 0, 0]));
+      expect(result.alpha).toBe(0.25);
+      expect(engine.getSnapshot().vector[1]).toBeCloseTo(0.25);
+    });
+
+    it("rejects invalid values", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      expect(() => engine.setAlpha(0)).toThrow(EngineError);
+      expect(() => engine.setDriftThreshold(NaN)).toThrow(/finite number/);
+      expect(engine.getAlpha()).toBe(0.5);
+    });
+  });
+
+  // ─── Warm-up ────────────────────────────────────────────────────────────────
+
+  describe("warmupUpdates", () => {
+    it("suppresses drift reports during warm-up while fusing normally", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        warmupUpdates: 3,
+        onDriftDetected,
+      });
+      const results = engine.updateBatch([
+        vec([1, 0, 0, 0]),
+        vec([0, 1, 0, 0]),
+        vec([0, 0, 1, 0]),
+        vec([0, 0, 0, 1]),
+      ]);
+
+      expect(results.map((r) => r.warmingUp)).toEqual([true, true, true, false]);
+      expect(results.map((r) => r.driftDetected)).toEqual([false, false, false, true]);
+      expect(results[1]!.driftScore).toBeCloseTo(1);
+      expect(results[1]!.thresholdExceeded).toBe(false);
+      expect(onDriftDetected).toHaveBeenCalledTimes(1);
+      expect(engine.getSnapshot().vector).toEqual([0, 0, 0, 1]);
+    });
+
+    it("defaults to exempting only the baseline update", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      const [first, second] = engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(first!.warmingUp).toBe(true);
+      expect(second!.warmingUp).toBe(false);
+      expect(second!.driftDetected).toBe(true);
+    });
+
+    it("rejects non-positive warm-up counts", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.75,
+            warmupUpdates: 0,
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  // ─── Init mode ──────────────────────────────────────────────────────────────
+
+  describe("initMode", () => {
+    it('"first" seeds the EMA state with the first embedding', () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        initMode: "first",
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().vector).toEqual([1, 0, 0, 0]);
+
+      // S_2 = 0.5·[0,1,0,0] + 0.5·[1,0,0,0]
+      engine.updateEmbedding(vec([0, 1, 0, 0]));
+      const { vector } = engine.getSnapshot();
+      expect(vector[0]).toBeCloseTo(0.5);
+      expect(vector[1]).toBeCloseTo(0.5);
+    });
+
+    it('"zero" (the default) blends the first embedding with a zero origin', () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().vector[0]).toBeCloseTo(0.5);
+    });
+
+    it('"first" keeps euclidean drift unbiased by a shrunk baseline', () => {
+      const make = (initMode: "zero" | "first") =>
+        new SemanticStateEngine({
+          alpha: 0.2,
+          driftThreshold: 0.5,
+          driftMetric: "euclidean",
+          initMode,
+        });
+      const zero = make("zero");
+      const first = make("first");
+      zero.updateEmbedding(vec([1, 0, 0, 0]));
+      first.updateEmbedding(vec([1, 0, 0, 0]));
+
+      expect(zero.updateEmbedding(vec([1, 0, 0, 0])).driftDetected).toBe(true);
+      expect(first.updateEmbedding(vec([1, 0, 0, 0])).driftScore).toBe(0);
+    });
+  });
+
+  // ─── Drift classification ───────────────────────────────────────────────────
+
+  describe("classifyDrift", () => {
+    const a = vec([1, 0, 0, 0]);
+    const b = vec([0, 1, 0, 0]);
+    const make = (driftThreshold = 0.75) =>
+      new SemanticStateEngine({ alpha: 1, driftThreshold, classifyDrift: true });
+
+    it("labels a jump after a stable period as sudden", () => {
+      const results = make().updateBatch([a, a, a, a, b]);
+      expect(results[4]!.driftType).toBe("sudden");
+      expect(results.slice(0, 4).every((r) => r.driftType === undefined)).toBe(true);
+    });
+
+    it("labels back-and-forth switching as gradual", () => {
+      const results = make().updateBatch([a, b, a]);
+      expect(results[1]!.driftType).toBe("sudden");
+      expect(results[2]!.driftType).toBe("gradual");
+    });
+
+    it("labels a return to an earlier concept as recurring", () => {
+      const stream = [a, a, a, ...Array.from({ length: 11 }, () => b), a];
+      const results = make().updateBatch(stream);
+      expect(results[3]!.driftType).toBe("sudden");
+      expect(results[14]!.driftType).toBe("recurring");
+    });
+
+    it("labels a steady move away as incremental", () => {
+      const angles = [0, 0.1, 0.3, 0.6, 1.0, 1.5];
+      const results = make(0.9).updateBatch(
+        angles.map((t) => vec([Math.cos(t), Math.sin(t), 0, 0])),
+      );
+      expect(results.map((r) => r.driftDetected)).toEqual([
+        false, false, false, false, false, true,
+      ]);
+      expect(results[5]!.driftType).toBe("incremental");
+    });
+
+    it("is omitted unless enabled", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      const [, second] = engine.updateBatch([a, b]);
+      expect(second!.driftDetected).toBe(true);
+      expect(second!.driftType).toBeUndefined();
+    });
+  });
+
+  // ─── Binary persistence ─────────────────────────────────────────────────────
+
+  describe("exportStateBytes / importStateBytes", () => {
+    it("restores an engine from its binary state", () => {
+      const source = new SemanticStateEngine({ alpha: 0.3, driftThreshold: 0.75 });
+      source.updateBatch([vec([1, 0, 0, 0]), vec([0.5, 0.5, 0, 0])], [1000, 2000]);
+
+      const restored = new SemanticStateEngine({ alpha: 0.3, driftThreshold: 0.75 });
+      restored.importStateBytes(source.exportStateBytes());
+
+      expect(restored.exportState()).toEqual(source.exportState());
+    });
+
+    it("rejects bytes that are not a binary state", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.3, driftThreshold: 0.75 });
+      expect(() => engine.importStateBytes(new Uint8Array(16))).toThrow(
+        "Invalid binary engine state: missing SSEB header",
+      );
+    });
+  });
+
+  // ─── Outliers ───────────────────────────────────────────────────────────────
+
+  describe("outliers", () => {
+    // Small, varied jitter: drift scores between ~0.001 and ~0.005.
+    const jitter = Array.from({ length: 20 }, (_, i) => {
+      const theta = 0.05 * (i % 3);
+      return vec([Math.cos(theta), Math.sin(theta), 0, 0]);
+    });
+    const garbage = vec([0, 0, 1, 0]);
+
+    it("rejects an anomalous embedding without fusing it", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        outliers: {},
+        onDriftDetected,
+      });
+      engine.updateBatch(jitter);
+      const before = engine.exportState();
+
+      const result = engine.updateEmbedding(garbage);
+      expect(result.outlier).toBe(true);
+      expect(result.rejected).toBe(true);
+      expect(result.driftDetected).toBe(false);
+      expect(result.alpha).toBe(0);
+      expect(onDriftDetected).not.toHaveBeenCalled();
+      expect(engine.exportState()).toEqual(before);
+    });
+
+    it("only flags outliers when reject is false", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        outliers: { reject: false },
+      });
+      engine.updateBatch(jitter);
+
+      const result = engine.updateEmbedding(garbage);
+      expect(result.outlier).toBe(true);
+      expect(result.rejected).toBe(false);
+      expect(result.driftDetected).toBe(true);
+      expect(engine.getSnapshot().vector).toEqual([0, 0, 1, 0]);
+    });
+
+    it("does not flag ordinary updates or anything before minSamples", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        outliers: { minSamples: 10 },
+      });
+      const results = engine.updateBatch([...jitter.slice(0, 5), garbage]);
+      expect(results.some((r) => r.outlier)).toBe(false);
+      expect(engine.updateBatch(jitter).some((r) => r.outlier)).toBe(false);
+    });
+
+    it("rejects invalid options", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.75,
+            outliers: { minSamples: 20, window: 10 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  // ─── Rolling statistics ─────────────────────────────────────────────────────
+
+  describe("statsWindow", () => {
+    it("summarizes the drift scores of the last updates in the snapshot", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        statsWindow: 3,
+      });
+      engine.updateBatch(
+        [
+          vec([1, 0, 0, 0]),
+          vec([0, 1, 0, 0]), // drift 1 — evicted below
+          vec([0, 1, 0, 0]), // drift 0
+          vec([0, 1, 0, 0]), // drift 0
+          vec([0, 0, 1, 0]), // drift 1
+        ],
+        [0, 10_000, 20_000, 30_000, 40_000],
+      );
+
+      const stats = engine.getSnapshot().driftStats!;
+      expect(stats.count).toBe(3);
+      expect(stats.mean).toBeCloseTo(1 / 3);
+      expect(stats.variance).toBeCloseTo(2 / 9);
+      expect(stats.min).toBeCloseTo(0);
+      expect(stats.max).toBeCloseTo(1);
+      // 2 intervals over 20 s
+      expect(stats.updatesPerMinute).toBeCloseTo(6);
+    });
+
+    it("is omitted by default and before any drift is measured", () => {
+      const disabled = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      disabled.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(disabled.getSnapshot().driftStats).toBeUndefined();
+
+      const enabled = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        statsWindow: 10,
+      });
+      enabled.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(enabled.getSnapshot().driftStats).toBeUndefined();
+    });
+  });
+
+  // ─── Drift event log ────────────────────────────────────────────────────────
+
+  describe("drift events", () => {
+    it("records every reported drift with its reason", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.updateBatch(
+        [vec([1, 0, 0, 0]), vec([1, 0, 0, 0]), vec([0, 1, 0, 0])],
+        [1000, 2000, 3000],
+      );
+
+      const events = engine.getDriftEvents();
+      expect(events).toHaveLength(1);
+      expect(events[0]).toMatchObject({
+        timestamp: 3000,
+        metric: "cosine",
+        threshold: 0.75,
+        reason: "similarity 0.000 < 0.75 (cosine)",
+      });
+      expect(events[0]!.driftScore).toBeCloseTo(1);
+    });
+
+    it("describes distance thresholds for other metrics", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftMetric: "euclidean",
+      });
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(engine.getDriftEvents()[0]!.reason).toBe("drift 1.414 > 0.5 (euclidean)");
+    });
+
+    it("is bounded, clearable, and persisted", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        driftEventLogSize: 2,
+      });
+      const a = vec([1, 0, 0, 0]);
+      const b = vec([0, 1, 0, 0]);
+      engine.updateBatch([a, b, a, b], [1, 2, 3, 4]);
+      expect(engine.getDriftEvents().map((e) => e.timestamp)).toEqual([3, 4]);
+
+      const restored = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      restored.importState(engine.exportState());
+      expect(restored.getDriftEvents()).toEqual(engine.getDriftEvents());
+
+      engine.clearDriftEvents();
+      expect(engine.getDriftEvents()).toEqual([]);
+      expect(engine.getUpdateCount()).toBe(4);
+    });
+  });
+
+  describe("projection", () => {
+    it("accepts embeddings of any dimension", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        projection: { dimension: 8, seed: 1 },
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(() =>
+        engine.updateEmbedding([0.1, 0.2, 0.3, 0.4, 0.5, 0.6]),
+      ).not.toThrow();
+      expect(engine.getDimension()).toBe(8);
+      expect(engine.getSnapshot().vector).toHaveLength(8);
+    });
+
+    it("projects deterministically for a given seed", () => {
+      const make = (seed: number) => {
+        const engine = new SemanticStateEngine({
+          alpha: 1,
+          driftThreshold: 0.5,
+          projection: { dimension: 8, seed },
+        });
+        engine.updateEmbedding(vec([1, 2, 3, 4]));
+        return engine.getSnapshot().vector;
+      };
+      expect(make(7)).toEqual(make(7));
+      expect(make(7)).not.toEqual(make(8));
+    });
+
+    it("rejects imported states of another dimension", () => {
+      const source = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      source.updateEmbedding(vec([1, 0, 0, 0]));
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        projection: { dimension: 8 },
+      });
+      expect(() => engine.importState(source.exportState())).toThrow(
+        "projection dimension is 8",
+      );
+    });
+
+    it("rejects an invalid projection dimension", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.5,
+            projection: { dimension: 0 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("clustering", () => {
+    it("reports each update's cluster and churn", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        clustering: { k: 4 },
+      });
+      const a = vec([1, 0, 0, 0]);
+      const b = vec([0, 1, 0, 0]);
+      const clusters = engine
+        .updateBatch([a, a, b, a])
+        .map((result) => result.cluster);
+      expect(clusters).toEqual([0, 0, 1, 0]);
+      expect(engine.getSnapshot().clusters).toEqual({
+        count: 2,
+        sizes: [3, 1],
+        current: 0,
+        switches: 2,
+        churnRate: 2 / 3,
+      });
+      expect(engine.getCentroids()).toEqual([a, b]);
+    });
+
+    it("is omitted without a clustering config and cleared by reset()", () => {
+      const plain = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      expect(plain.updateEmbedding(vec([1, 0, 0, 0])).cluster).toBeUndefined();
+      expect(plain.getSnapshot().clusters).toBeUndefined();
+
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        clustering: {},
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      engine.reset();
+      expect(engine.getSnapshot().clusters).toBeUndefined();
+      expect(engine.getCentroids()).toEqual([]);
+    });
+  });
+
+  describe("forecast", () => {
+    it("returns null until the history holds two updates", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(engine.forecast(1000)).toBeNull();
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      expect(engine.forecast(1000, 0)).toBeNull();
+    });
+
+    it("extrapolates rising drift against the cosine threshold", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const a = vec([1, 0, 0, 0]);
+      engine.updateBatch([a, a, vec([0, 1, 0, 0])], [0, 1000, 2000]);
+
+      const forecast = engine.forecast(1000, 2000)!;
+      expect(forecast.driftTrend).toBeCloseTo(30);
+      expect(forecast.driftScore).toBeCloseTo(1 / 3 + 1);
+      expect(forecast.timeToThresholdMs).toBe(0);
+    });
+
+    it("rejects a negative horizon", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.forecast(-1)).toThrow(EngineError);
+    });
+  });
+
+  describe("health trend", () => {
+    it("reports a degrading trend when drift rises", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const a = vec([1, 0, 0, 0]);
+      engine.updateBatch([a, a, vec([0, 1, 0, 0])], [0, 60_000, 120_000]);
+
+      const snapshot = engine.getSnapshot();
+      expect(snapshot.healthSlope).toBeCloseTo(-0.25);
+      expect(snapshot.healthTrend).toBe("degrading");
+    });
+
+    it("reports a flat trend for a steady stream", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const a = vec([1, 0, 0, 0]);
+      engine.updateBatch([a, a, a], [0, 1000, 2000]);
+      expect(engine.getSnapshot().healthTrend).toBe("flat");
+    });
+
+    it("is omitted before two updates", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().healthTrend).toBeUndefined();
+    });
+  });
+
+  describe("updateWithThreshold", () => {
+    it("judges a single update against the override threshold", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const a = vec([1, 0, 0, 0]);
+      const near = vec([0.8, 0.6, 0, 0]);
+      engine.updateEmbedding(a);
+
+      expect(engine.updateEmbedding(near).thresholdExceeded).toBe(false);
+      engine.updateEmbedding(a);
+      const strict = engine.updateWithThreshold(near, 0.9);
+      expect(strict.driftDetected).toBe(true);
+      expect(engine.getDriftEvents()[0]!.reason).toBe(
+        "similarity 0.800 < 0.9 (cosine)",
+      );
+      expect(engine.getDriftThreshold()).toBe(0.5);
+    });
+
+    it("rejects a non-finite threshold", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.updateWithThreshold(vec([1, 0, 0, 0]), NaN)).toThrow(
+        EngineError,
+      );
+    });
+  });
+
+  describe("updateFrom", () => {
+    it("keeps a state per source and reports cross-source similarity", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      const a = vec([1, 0, 0, 0]);
+      const b = vec([0, 1, 0, 0]);
+
+      const first = engine.updateFrom("user", a);
+      expect(first.source).toBe("user");
+      expect(first.sourceSimilarities).toBeUndefined();
+
+      expect(engine.updateFrom("assistant", b).sourceSimilarities).toEqual({
+        user: 0,
+      });
+      const converging = engine.updateFrom("assistant", a);
+      expect(converging.sourceSimilarities!.user).toBeCloseTo(Math.SQRT1_2);
+
+      expect(engine.getSources()).toEqual(["user", "assistant"]);
+      expect(engine.getSourceVector("assistant")).toEqual([0.5, 0.5, 0, 0]);
+      expect(engine.getSourceVector("other")).toBeNull();
+      expect(engine.getUpdateCount()).toBe(3);
+    });
+
+    it("persists source states and clears them on reset()", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateFrom("user", vec([1, 0, 0, 0]));
+
+      const restored = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      restored.importState(engine.exportState());
+      expect(restored.getSourceVector("user")).toEqual([1, 0, 0, 0]);
+
+      engine.reset();
+      expect(engine.getSources()).toEqual([]);
+    });
+  });
+
+  describe("embedding buffer", () => {
+    it("fuses the buffer contents in place", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const buffer = engine.allocEmbeddingBuffer(DIM);
+
+      buffer.set([1, 0, 0, 0]);
+      engine.updateFromBuffer(1000);
+      buffer.set([0, 1, 0, 0]);
+      const result = engine.updateFromBuffer(2000);
+
+      expect(result.driftDetected).toBe(true);
+      expect(engine.getSnapshot().vector).toEqual([0, 1, 0, 0]);
+      expect(engine.getUpdateCount()).toBe(2);
+    });
+
+    it("throws NOT_INITIALIZED before a buffer is allocated", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.updateFromBuffer()).toThrow(
+        "requires a buffer from allocEmbeddingBuffer()",
+      );
+    });
+  });
+
+  describe("trace replay", () => {
+    const config = {
+      alpha: 0.5,
+      driftThreshold: 0.75,
+      recordTrace: true,
+      clustering: {},
+    };
+
+    it("reproduces the exact final state from a recorded trace", () => {
+      const engine = new SemanticStateEngine(config);
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      engine.updateWeighted(vec([0.8, 0.6, 0, 0]), 0.5, 2000);
+      engine.updateWithThreshold(vec([0, 1, 0, 0]), 0.9, 3000);
+      engine.updateFrom("assistant", vec([0, 0, 1, 0]), 4000);
+
+      const trace = JSON.parse(JSON.stringify(engine.getTrace()));
+      expect(trace.entries).toHaveLength(4);
+      expect(trace.entries[1]).toEqual({
+        embedding: [0.8, 0.6, 0, 0],
+        timestamp: 2000,
+        weight: 0.5,
+      });
+
+      const replica = new SemanticStateEngine(config);
+      const results = replica.replay(trace);
+      expect(results.map((r) => r.driftDetected)).toEqual([false, false, true, true]);
+      expect(replica.exportState()).toEqual(engine.exportState());
+      expect(replica.getSnapshot().clusters).toEqual(engine.getSnapshot().clusters);
+    });
+
+    it("restarts the trace on reset()", () => {
+      const engine = new SemanticStateEngine(config);
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      engine.reset();
+      expect(engine.getTrace()!.entries).toEqual([]);
+    });
+
+    it("records nothing unless enabled", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getTrace()).toBeNull();
+    });
+  });
+
+  describe("state versioning", () => {
+    it("stamps exported states with the current version", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      expect(engine.exportState().version).toBe(1);
+    });
+
+    it("imports unversioned states and rejects newer ones", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.importState({
+        stateVector: [1, 0, 0, 0],
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        updateCount: 3,
+        lastDrift: 0.1,
+        lastUpdatedAt: 1000,
+      });
+      expect(engine.getUpdateCount()).toBe(3);
+
+      const future = { ...engine.exportState(), version: 99 };
+      expect(() => engine.importState(future)).toThrow(EngineError);
+    });
+  });
+
+  describe("getMetrics", () => {
+    it("times updates and estimates retained memory", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])], [1000, 2000]);
+
+      const metrics = engine.getMetrics();
+      expect(metrics.updates).toBe(2);
+      expect(metrics.maxUpdateUs).toBeGreaterThanOrEqual(metrics.meanUpdateUs);
+      // State vector, the two embeddings retained for windowed fusion, and
+      // the running mean and variance of the embeddings.
+      expect(metrics.stateBytes).toBe(5 * DIM * 8);
+      expect(metrics.peakStateBytes).toBe(5 * DIM * 8);
+      expect(metrics.historyBytes).toBe(2 * 3 * 8 + 4 * 8);
+    });
+
+    it("resetMetrics() clears the counters", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      engine.resetMetrics();
+      expect(engine.getMetrics()).toMatchObject({ updates: 0, meanUpdateUs: 0 });
+    });
+  });
+
+  describe("mahalanobis drift metric", () => {
+    it("discounts dimensions that are inherently noisy", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 10,
+        driftMetric: "mahalanobis",
+        attributionTopK: 1,
+      });
+      const up = vec([2, 1, 0, 0]);
+      const down = vec([-2, 1, 0, 0]);
+      engine.updateBatch([up, down, up, down]);
+
+      // Dimension 0 has a sample variance of 16/3, so a jump of 4 scores √3
+      // rather than the Euclidean 4.
+      const result = engine.updateEmbedding(up);
+      expect(result.driftScore).toBeCloseTo(Math.sqrt(3));
+      expect(result.attribution![0]!.index).toBe(0);
+      expect(result.attribution![0]!.contribution).toBeCloseTo(3);
+    });
+
+    it("falls back to Euclidean distance before two embeddings are seen", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 10,
+        driftMetric: "mahalanobis",
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.updateEmbedding(vec([0, 1, 0, 0])).driftScore).toBeCloseTo(
+        Math.SQRT2,
+      );
+    });
+  });
+
+  describe("staleAfterMs", () => {
+    afterEach(() => {
+      vi.useRealTimers();
+    });
+
+    it("reports the state as stale once the TTL elapses", () => {
+      vi.useFakeTimers();
+      vi.setSystemTime(10_000);
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.75,
+        staleAfterMs: 1000,
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot()).toMatchObject({ isStale: false });
+      expect(engine.getSnapshot().staleForMs).toBeUndefined();
+
+      vi.advanceTimersByTime(1500);
+      expect(engine.getSnapshot()).toMatchObject({
+        isStale: true,
+        staleForMs: 500,
+      });
+    });
+
+    it("is omitted without a TTL", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.75 });
+      expect(engine.getSnapshot().isStale).toBeUndefined();
+    });
+  });
+
+  describe("normalizeInputs", () => {
+    it("L2-normalizes embeddings and references before use", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftMetric: "euclidean",
+        normalizeInputs: "l2",
+      });
+      engine.addReference("golden", [0, 10, 0, 0]);
+      engine.updateEmbedding([3, 4, 0, 0]);
+      expect(engine.getSnapshot().vector).toEqual([0.6, 0.8, 0, 0]);
+      expect(engine.updateEmbedding([30, 40, 0, 0]).driftScore).toBeCloseTo(0);
+      expect(engine.getSnapshot().referenceSimilarities!.golden).toBeCloseTo(0.8);
+    });
+
+    it("standardizes embeddings with the zscore policy", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        normalizeInputs: "zscore",
+      });
+      engine.updateEmbedding([1, 3, 1, 3]);
+      expect(engine.getSnapshot().vector).toEqual([-1, 1, -1, 1]);
+    });
+  });
+
+  describe("updateSparse", () => {
+    it("fuses a sparse embedding like its dense equivalent", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateSparse({ indices: [2], values: [1], dimension: DIM });
+      expect(engine.getSnapshot().vector).toEqual([0, 0, 1, 0]);
+      const result = engine.updateSparse({ indices: [0], values: [1], dimension: DIM });
+      expect(result.driftDetected).toBe(true);
+    });
+
+    it("rejects malformed sparse embeddings", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() =>
+        engine.updateSparse({ indices: [9], values: [1], dimension: DIM }),
+      ).toThrow("Invalid sparse embedding: index 9 is out of range");
+    });
+  });
+
+  describe("maxDriftEventsPerMinute", () => {
+    it("suppresses drift reports beyond the per-minute limit", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        maxDriftEventsPerMinute: 2,
+        onDriftDetected,
+      });
+      const a = vec([1, 0, 0, 0]);
+      const b = vec([0, 1, 0, 0]);
+      const results = engine.updateBatch(
+        [a, b, a, b, a],
+        [0, 1000, 2000, 3000, 61_500],
+      );
+
+      expect(results.map((r) => r.driftDetected)).toEqual([
+        false,
+        true,
+        true,
+        false,
+        true,
+      ]);
+      expect(results[3]!.driftSuppressed).toBe(true);
+      expect(onDriftDetected).toHaveBeenCalledTimes(3);
+      expect(engine.getDriftEvents()).toHaveLength(3);
+      expect(engine.getSuppressedDriftCount()).toBe(1);
+
+      engine.reset();
+      expect(engine.getSuppressedDriftCount()).toBe(0);
+    });
+  });
+
+  describe("checkpoint / rollback", () => {
+    it("restores the state recorded by a checkpoint", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      const id = engine.checkpoint();
+      const before = engine.exportState();
+
+      engine.updateBatch([vec([0, 1, 0, 0]), vec([0, 0, 1, 0])], [2000, 3000]);
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.rollback(id);
+
+      expect(engine.exportState()).toEqual(before);
+      expect(engine.getUpdateCount()).toBe(1);
+      expect(listener).toHaveBeenCalledTimes(1);
+      expect(engine.getCheckpoints()).toEqual([id]);
+    });
+
+    it("retains only the most recent maxCheckpoints", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        maxCheckpoints: 2,
+      });
+      const first = engine.checkpoint();
+      const second = engine.checkpoint();
+      const third = engine.checkpoint();
+
+      expect(engine.getCheckpoints()).toEqual([second, third]);
+      expect(() => engine.rollback(first)).toThrow(
+        `No checkpoint with ID ${first}`,
+      );
+      expect(engine.releaseCheckpoint(second)).toBe(true);
+      expect(engine.getCheckpoints()).toEqual([third]);
+    });
+
+    it("rejects a non-positive maxCheckpoints", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0,
+            maxCheckpoints: 0,
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("findSimilarStates", () => {
+    it("returns the archived states most similar to the query", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        stateArchive: { interval: 2 },
+      });
+      engine.updateBatch(
+        [
+          vec([1, 0, 0, 0]),
+          vec([1, 0, 0, 0]),
+          vec([0, 1, 0, 0]),
+          vec([0, 1, 0, 0]),
+          vec([0, 0, 1, 0]),
+        ],
+        [1000, 2000, 3000, 4000, 5000],
+      );
+
+      const matches = engine.findSimilarStates(vec([0, 1, 0, 0]), 2);
+      expect(matches.map((m) => [m.timestamp, m.updateCount])).toEqual([
+        [4000, 4],
+        [2000, 2],
+      ]);
+      expect(matches[0]!.similarity).toBeCloseTo(1);
+      expect(matches[1]!.similarity).toBeCloseTo(0);
+    });
+
+    it("evicts the oldest archived state beyond size and persists the archive", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        stateArchive: { interval: 1, size: 2 },
+      });
+      engine.updateBatch(
+        [vec([1, 0, 0, 0]), vec([0, 1, 0, 0]), vec([0, 0, 1, 0])],
+        [1000, 2000, 3000],
+      );
+      expect(engine.exportState().archive?.map((e) => e.timestamp)).toEqual([
+        2000, 3000,
+      ]);
+
+      const restored = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        stateArchive: { interval: 1, size: 2 },
+      });
+      restored.importState(engine.exportState());
+      expect(restored.findSimilarStates(vec([0, 0, 1, 0]), 1)[0]!.timestamp).toBe(
+        3000,
+      );
+    });
+
+    it("is empty without stateArchive", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.findSimilarStates(vec([1, 0, 0, 0]), 5)).toEqual([]);
+    });
+  });
+
+  describe("health factors", () => {
+    it("applies the exponential curve to a factor", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        health: {
+          factors: {
+            age: { weight: 0 },
+            drift: { weight: 2, curve: "exponential" },
+          },
+        },
+      });
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(engine.getSnapshot().healthScore).toBeCloseTo(Math.exp(-2));
+    });
+
+    it("penalizes drift variance and slow updates when weighted", () => {
+      const factors = { age: { weight: 0 }, drift: { weight: 0 } };
+      const calm = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        health: { factors: { ...factors, variance: { weight: 1 } } },
+      });
+      const noisy = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        health: { factors: { ...factors, variance: { weight: 1 } } },
+      });
+      const slow = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        health: { factors: { ...factors, updateRate: { weight: 0.0001 } } },
+      });
+      const a = vec([1, 0, 0, 0]);
+      const b = vec([0, 1, 0, 0]);
+      calm.updateBatch([a, a, a, a], [0, 1000, 2000, 3000]);
+      noisy.updateBatch([a, a, b, b], [0, 1000, 2000, 3000]);
+      slow.updateBatch([a, a, a], [0, 1000, 2000]);
+
+      expect(calm.getSnapshot().healthScore).toBeCloseTo(1);
+      // Drift scores 0, 0, 1, 0: variance 0.1875.
+      expect(noisy.getSnapshot().healthScore).toBeCloseTo(1 - 0.1875);
+      // Mean interval of 1000 ms.
+      expect(slow.getSnapshot().healthScore).toBeCloseTo(0.9);
+    });
+
+    it("rejects a negative factor weight", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0,
+            health: { factors: { variance: { weight: -1 } } },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("embeddingStats", () => {
+    it("reports the running variance of the incoming embeddings", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().embeddingStats).toBeUndefined();
+
+      engine.updateEmbedding(vec([-1, 0, 0, 0]));
+      // Dimension 0 has sample variance 2; the others do not vary.
+      expect(engine.getSnapshot().embeddingStats).toEqual({
+        count: 2,
+        variance: 0.5,
+        stdDev: Math.sqrt(0.5),
+        entropy: 0,
+      });
+    });
+
+    it("reports full entropy when every dimension varies equally", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0 });
+      engine.updateBatch([vec([1, 1, 1, 1]), vec([-1, -1, -1, -1])]);
+      expect(engine.getSnapshot().embeddingStats!.entropy).toBeCloseTo(1);
+    });
+  });
+
+  describe("getAggregates", () => {
+    it("buckets the retained history", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0,
+        health: { ageDecayRate: 0 },
+      });
+      engine.updateBatch(
+        [vec([1, 0, 0, 0]), vec([0, 1, 0, 0]), vec([0, 1, 0, 0])],
+        [1000, 59_000, 61_000],
+      );
+
+      const buckets = engine.getAggregates(60_000, 120_000);
+      expect(buckets).toEqual([
+        { start: 0, count: 2, meanDrift: 0.5, maxDrift: 1, meanHealth: 0.75 },
+        { start: 60_000, count: 1, meanDrift: 0, maxDrift: 0, meanHealth: 1 },
+      ]);
+      expect(() => engine.getAggregates(0)).toThrow(EngineError);
+    });
+  });
+
+  describe("criticalThreshold", () => {
+    it("grades threshold crossings as warning or critical", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.9,
+        criticalThreshold: 0.5,
+      });
+      const results = engine.updateBatch([
+        vec([1, 0, 0, 0]),
+        vec([1, 0, 0, 0]),
+        vec([1, 1, 0, 0]),
+        vec([0, 0, 1, 0]),
+      ]);
+      expect(results.map((r) => r.driftLevel)).toEqual([
+        "none",
+        "none",
+        "warning",
+        "critical",
+      ]);
+    });
+
+    it("rejects a critical threshold laxer than driftThreshold", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.5,
+            criticalThreshold: 0.9,
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("dimension", () => {
+    it("validates the first update against the declared dimension", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        dimension: DIM,
+      });
+      expect(engine.getDimension()).toBe(DIM);
+      expect(() => engine.updateEmbedding([1, 0, 0])).toThrow(
+        "Embedding dimension mismatch: expected 4, got 3",
+      );
+      expect(engine.getUpdateCount()).toBe(0);
+      expect(() => engine.addReference("short", [1, 0])).toThrow(EngineError);
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getUpdateCount()).toBe(1);
+    });
+
+    it("rejects an imported state of another dimension", () => {
+      const source = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      source.updateEmbedding([1, 0, 0]);
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        dimension: DIM,
+      });
+      expect(() => engine.importState(source.exportState())).toThrow(
+        "configured for dimension 4",
+      );
+    });
+
+    it("cannot be combined with projection", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.5,
+            dimension: DIM,
+            projection: { dimension: 2 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("metadata", () => {
+    it("echoes metadata into snapshots and drift events", () => {
+      const metadata = { tenant: "acme", model: "minilm" };
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        metadata,
+      });
+      metadata.tenant = "changed";
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+
+      const expected = { tenant: "acme", model: "minilm" };
+      expect(engine.getMetadata()).toEqual(expected);
+      expect(engine.getSnapshot().metadata).toEqual(expected);
+      expect(engine.getDriftEvents()[0]!.metadata).toEqual(expected);
+    });
+
+    it("is absent when not configured", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().metadata).toBeUndefined();
+      expect(engine.getMetadata()).toEqual({});
+    });
+  });
+
+  describe("inputShift", () => {
+    it("flags an abrupt change in input magnitude once", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        inputShift: { minSamples: 3 },
+      });
+      const direction = [1, 1, 0, 0];
+      const unit = Array.from({ length: 4 }, () => vec(direction));
+      const doubled = Array.from({ length: 3 }, () =>
+        vec(direction.map((x) => 2 * x)),
+      );
+      const results = engine.updateBatch([...unit, ...doubled]);
+
+      expect(results.map((r) => r.inputShift)).toEqual([
+        false,
+        false,
+        false,
+        false,
+        true,
+        false,
+        false,
+      ]);
+      expect(results.every((r) => !r.thresholdExceeded)).toBe(true);
+    });
+
+    it("never flags without the option", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      const results = engine.updateBatch([vec([1, 0, 0, 0]), vec([9, 0, 0, 0])]);
+      expect(results.map((r) => r.inputShift)).toEqual([false, false]);
+    });
+  });
+
+  describe("minUpdatesForHealth", () => {
+    it("reports insufficient data until enough updates have been fused", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        minUpdatesForHealth: 2,
+      });
+      expect(engine.getSnapshot()).toMatchObject({
+        insufficientData: true,
+        semanticSummary: "insufficient data",
+      });
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(engine.getSnapshot().insufficientData).toBe(true);
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      const snapshot = engine.getSnapshot();
+      expect(snapshot.insufficientData).toBeUndefined();
+      expect(snapshot.semanticSummary).toBe("stable");
+    });
+  });
+
+  describe("evaluate", () => {
+    it("measures drift without changing the state", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        criticalThreshold: 0.25,
+      });
+      expect(engine.evaluate(vec([1, 0, 0, 0])).thresholdExceeded).toBe(false);
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      const before = engine.exportState();
+      const listener = vi.fn();
+      engine.subscribe(listener);
+
+      const evaluation = engine.evaluate(vec([0, 1, 0, 0]), 2000);
+      expect(evaluation).toEqual({
+        similarity: 0,
+        driftScore: 1,
+        thresholdExceeded: true,
+        driftLevel: "critical",
+        warmingUp: false,
+      });
+      expect(engine.exportState()).toEqual(before);
+      expect(listener).not.toHaveBeenCalled();
+
+      expect(engine.updateEmbedding(vec([0, 1, 0, 0]), 2000).driftScore).toBe(
+        evaluation.driftScore,
+      );
+    });
+
+    it("rejects an embedding of the wrong dimension", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]));
+      expect(() => engine.evaluate([1, 0])).toThrow(EngineError);
+    });
+  });
+
+  describe("snapshot counters", () => {
+    it("reports the last drift, similarity, update count and dimension", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(engine.getSnapshot()).toMatchObject({
+        lastDriftScore: 0,
+        lastSimilarity: 1,
+        updateCount: 0,
+        dimension: null,
+      });
+
+      engine.updateBatch([vec([1, 0, 0, 0]), vec([0, 1, 0, 0])]);
+      expect(engine.getSnapshot()).toMatchObject({
+        lastDriftScore: 1,
+        lastSimilarity: 0,
+        updateCount: 2,
+        dimension: DIM,
+      });
+
+      const restored = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      restored.importState(engine.exportState());
+      expect(restored.getSnapshot().lastSimilarity).toBe(0);
+    });
+  });
+
+  describe("historySimilarity", () => {
+    it("is absent unless configured", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      expect(engine.updateEmbedding(vec([1, 0]), 1).historySimilarity).toBeUndefined();
+    });
+
+    it("compares with the decay-weighted recent embeddings", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        historySimilarity: { size: 2, halfLifeMs: 1000 },
+      });
+      expect(engine.updateEmbedding(vec([1, 0]), 0).historySimilarity).toBeUndefined();
+      engine.updateEmbedding(vec([0, 1]), 1000);
+
+      // Weights 0.5 (older) and 1 (newer): combination ∝ (0.5, 1).
+      const result = engine.updateEmbedding(vec([0, 1]), 1000);
+      expect(result.historySimilarity).toBeCloseTo(1 / Math.sqrt(1.25));
+    });
+
+    it("forgets the old regime after size updates", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.1,
+        driftThreshold: 0.5,
+        historySimilarity: { size: 2 },
+      });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1);
+      engine.updateEmbedding(vec([0, 1]), 2);
+
+      const result = engine.updateEmbedding(vec([0, 1]), 3);
+      expect(result.historySimilarity).toBeCloseTo(1);
+      expect(result.similarity).toBeLessThan(0.99);
+    });
+
+    it("rejects invalid settings", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.5,
+            historySimilarity: { size: 0 },
+          }),
+      ).toThrow(EngineError);
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.5,
+            historySimilarity: { halfLifeMs: 0 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("seedState", () => {
+    it("compares the first live update against the seeded centroid", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.seedState(vec([1, 0]), 1000);
+
+      expect(engine.getUpdateCount()).toBe(1);
+      expect(engine.getSnapshot().timestamp).toBe(1000);
+      expect(engine.getSnapshot().vector).toEqual(vec([1, 0]));
+      const result = engine.updateEmbedding(vec([0, 1]), 2000);
+      expect(result.similarity).toBeCloseTo(0);
+      expect(result.thresholdExceeded).toBe(true);
+    });
+
+    it("discards previously accumulated state", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1);
+      engine.seedState(vec([0, 0, 1]), 2);
+
+      expect(engine.getHistory()).toEqual([]);
+      expect(engine.getDriftEvents()).toEqual([]);
+      expect(engine.getSnapshot().vector).toEqual(vec([0, 0, 1]));
+    });
+
+    it("validates the centroid before discarding anything", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        dimension: DIM,
+      });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      expect(() => engine.seedState([1, 0])).toThrow(EngineError);
+      expect(() => engine.seedState([])).toThrow(EngineError);
+      expect(engine.getUpdateCount()).toBe(1);
+    });
+
+    it("notifies subscribers", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.seedState(vec([1, 0]));
+      expect(listener).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe("storagePrecision", () => {
+    it("keeps the state at reduced precision", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        storagePrecision: "f16",
+      });
+      engine.updateEmbedding(vec([0.6, 0.8]), 0);
+      const [x, y] = engine.getSnapshot().vector;
+      expect(x).not.toBe(0.6);
+      expect(x).toBeCloseTo(0.6, 3);
+      expect(y).toBeCloseTo(0.8, 3);
+      expect(engine.updateEmbedding(vec([0.6, 0.8]), 1).similarity).toBeCloseTo(
+        1,
+        5,
+      );
+    });
+
+    it("reduces the reported state memory", () => {
+      const make = (storagePrecision: "f64" | "int8") =>
+        new SemanticStateEngine({
+          alpha: 0.5,
+          driftThreshold: 0.5,
+          fusionStrategy: "sma",
+          fusionWindow: 4,
+          storagePrecision,
+        });
+      const full = make("f64");
+      const packed = make("int8");
+      for (let t = 0; t < 4; t++) {
+        full.updateEmbedding(vec([1, t]), t);
+        packed.updateEmbedding(vec([1, t]), t);
+      }
+      expect(packed.getMetrics().stateBytes).toBeLessThan(
+        full.getMetrics().stateBytes,
+      );
+      packed.getSnapshot().vector.forEach((v, i) => {
+        expect(v).toBeCloseTo(full.getSnapshot().vector[i]!, 2);
+      });
+    });
+
+    it("exports plain arrays and restores them at its own precision", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        stateArchive: { interval: 1 },
+        storagePrecision: "int8",
+      });
+      engine.updateEmbedding(vec([1, 2]), 0);
+      const state = engine.exportState();
+      expect(Array.isArray(state.stateVector)).toBe(true);
+      expect(Array.isArray(state.archive![0]!.vector)).toBe(true);
+
+      const restored = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        stateArchive: { interval: 1 },
+        storagePrecision: "int8",
+      });
+      restored.importState(state);
+      restored.exportState().stateVector.forEach((v, i) => {
+        expect(v).toBeCloseTo(state.stateVector[i]!, 12);
+      });
+      expect(restored.findSimilarStates(vec([1, 2]), 1)[0]!.similarity).toBeCloseTo(
+        1,
+        3,
+      );
+    });
+  });
+
+  describe("driftDirection", () => {
+    it("is absent unless enabled", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      expect(engine.updateEmbedding(vec([0, 1]), 1).driftDirection).toBeUndefined();
+    });
+
+    it("points from the baseline towards the embedding", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftDirection: true,
+      });
+      expect(engine.updateEmbedding(vec([1, 0]), 0).driftDirection).toBeUndefined();
+
+      const direction = engine.updateEmbedding(vec([0, 1]), 1).driftDirection!;
+      expect(direction[0]).toBeCloseTo(-Math.SQRT1_2);
+      expect(direction[1]).toBeCloseTo(Math.SQRT1_2);
+      expect(direction.slice(2)).toEqual([0, 0]);
+
+      expect(engine.updateEmbedding(vec([0, 1]), 2).driftDirection).toEqual(
+        vec([]),
+      );
+    });
+  });
+
+  describe("fork", () => {
+    it("copies the current state and configuration", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        metadata: { tenant: "acme" },
+      });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1);
+      engine.setDriftThreshold(0.3);
+
+      const fork = engine.fork();
+      expect(fork.exportState()).toEqual(engine.exportState());
+      expect(fork.getDriftThreshold()).toBe(0.3);
+      expect(fork.getMetadata()).toEqual({ tenant: "acme" });
+    });
+
+    it("evolves independently of the original", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      const before = engine.exportState();
+
+      const fork = engine.fork();
+      fork.updateEmbedding(vec([0, 1]), 1);
+      expect(engine.exportState()).toEqual(before);
+      expect(fork.getUpdateCount()).toBe(2);
+    });
+
+    it("applies overrides on top of the copied state", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 0);
+
+      const fork = engine.fork({ alpha: 0.1 });
+      expect(fork.getAlpha()).toBe(0.1);
+      expect(engine.getAlpha()).toBe(0.5);
+      expect(fork.getSnapshot().vector).toEqual(engine.getSnapshot().vector);
+    });
+
+    it("carries the runtime health configuration over", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.setHealthConfig({ floor: 0.2 });
+      expect(engine.fork().getHealthConfig()).toEqual(engine.getHealthConfig());
+    });
+  });
+
+  describe("timestampPolicy and clock", () => {
+    it("accepts out-of-order timestamps by default", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 2000);
+      expect(engine.updateEmbedding(vec([1, 0]), 1000).timestamp).toBe(1000);
+    });
+
+    it("clamps out-of-order timestamps to the previous update", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        timestampPolicy: "clamp",
+      });
+      engine.updateEmbedding(vec([1, 0]), 2000);
+      expect(engine.updateEmbedding(vec([1, 0]), 1000).timestamp).toBe(2000);
+      expect(engine.updateEmbedding(vec([1, 0]), 3000).timestamp).toBe(3000);
+    });
+
+    it("rejects out-of-order timestamps under the error policy", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        timestampPolicy: "error",
+      });
+      engine.updateEmbedding(vec([1, 0]), 2000);
+      expect(() => engine.updateEmbedding(vec([1, 0]), 1000)).toThrow(
+        "precedes the previous update",
+      );
+      expect(engine.getUpdateCount()).toBe(1);
+      expect(() => engine.updateEmbedding(vec([1, 0]), 2000)).not.toThrow();
+    });
+
+    it("ignores caller timestamps with the monotonic clock", () => {
+      vi.useFakeTimers();
+      try {
+        vi.setSystemTime(5000);
+        const engine = new SemanticStateEngine({
+          alpha: 0.5,
+          driftThreshold: 0.5,
+          clock: "monotonic",
+        });
+        expect(engine.updateEmbedding(vec([1, 0]), 123).timestamp).toBe(5000);
+
+        // The system clock steps back; the engine's clock does not.
+        vi.setSystemTime(4000);
+        expect(engine.updateEmbedding(vec([1, 0])).timestamp).toBe(5000);
+        expect(engine.getSnapshot().healthScore).toBeLessThanOrEqual(1);
+
+        vi.setSystemTime(6000);
+        expect(engine.updateEmbedding(vec([1, 0])).timestamp).toBe(6000);
+      } finally {
+        vi.useRealTimers();
+      }
+    });
+  });
+
+  describe("exportPrometheus", () => {
+    it("renders the engine's gauges labelled with its metadata", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        metadata: { tenant: "acme" },
+      });
+      engine.updateEmbedding(vec([1, 0]));
+      const text = engine.exportPrometheus("sse");
+      expect(text).toContain("# TYPE sse_health_score gauge\n");
+      expect(text).toContain('sse_update_count{tenant="acme"} 1\n');
+      expect(text).toContain('sse_drift_score{tenant="acme"} 0\n');
+    });
+  });
+
+  describe("OpenTelemetry output", () => {
+    it("reports the snapshot with severity from the last drift level", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        criticalThreshold: 0.25,
+        metadata: { tenant: "acme" },
+      });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      expect(engine.getSnapshotOtel().severityText).toBe("INFO");
+
+      engine.updateEmbedding(vec([1, 1]), 1);
+      const warn = engine.getSnapshotOtel();
+      expect(warn.severityText).toBe("WARN");
+      expect(warn.attributes["tenant"]).toBe("acme");
+      expect(warn.attributes["semantic_state.update_count"]).toBe(2);
+
+      engine.updateEmbedding(vec([-1, 0]), 2);
+      expect(engine.getSnapshotOtel().severityText).toBe("ERROR");
+    });
+
+    it("converts recorded drift events", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.75,
+        criticalThreshold: 0.25,
+      });
+      engine.updateBatch([vec([1, 0]), vec([1, 1]), vec([-1, 0])], [0, 1, 2]);
+
+      expect(engine.getDriftEvents().map((e) => e.driftLevel)).toEqual([
+        "warning",
+        "critical",
+      ]);
+      expect(engine.getDriftEventsOtel().map((r) => r.severityText)).toEqual([
+        "WARN",
+        "ERROR",
+      ]);
+    });
+  });
+
+  describe("updateEmbeddingPacked", () => {
+    it("fuses the embedding and returns a packed result", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.75 });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.updateEmbedding(vec([1, 0]), 0);
+
+      const buffer = engine.updateEmbeddingPacked(vec([0, 1]), 1000);
+      expect(buffer).toBeInstanceOf(ArrayBuffer);
+      expect(buffer.byteLength).toBe((8 + DIM) * 8);
+      expect(engine.getUpdateCount()).toBe(2);
+      expect(listener).toHaveBeenCalledTimes(2);
+
+      const result = unpackUpdateResult(buffer);
+      expect([...result.vector]).toEqual(vec([0, 1]));
+      expect(result.timestamp).toBe(1000);
+      expect(result.thresholdExceeded).toBe(true);
+      expect(result.driftLevel).toBe("warning");
+    });
+  });
+
+  describe("nonFinitePolicy", () => {
+    it("rejects non-finite embeddings by default without touching the state", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      const before = engine.exportState();
+
+      expect(() => engine.updateEmbedding(vec([NaN, 1]), 1000)).toThrow(
+        EngineError,
+      );
+      expect(() => engine.updateEmbedding(vec([0, Infinity]), 1000)).toThrow(
+        /component 1 is Infinity/,
+      );
+      expect(() => engine.resetBaseline(vec([NaN]))).toThrow(EngineError);
+      expect(() => engine.addReference("bad", vec([-Infinity]))).toThrow(
+        EngineError,
+      );
+      expect(engine.exportState()).toEqual(before);
+    });
+
+    it("reports the NON_FINITE_EMBEDDING code and offending component", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      try {
+        engine.updateEmbedding(vec([1, 2, NaN]));
+        expect.unreachable();
+      } catch (err) {
+        expect(err).toBeInstanceOf(EngineError);
+        expect((err as EngineError).code).toBe("NON_FINITE_EMBEDDING");
+        expect((err as EngineError).details).toMatchObject({ index: 2 });
+      }
+    });
+
+    it("replaces non-finite components with zeros under \"zero\"", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        nonFinitePolicy: "zero",
+      });
+      engine.updateEmbedding(vec([2, NaN, Infinity, -Infinity]));
+      expect(engine.getSnapshot().vector).toEqual(vec([2]));
+    });
+
+    it("clamps infinities and zeroes NaN under \"clamp\"", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        nonFinitePolicy: "clamp",
+      });
+      engine.updateEmbedding(vec([2, NaN, Infinity, -Infinity]));
+      expect(engine.getSnapshot().vector).toEqual(
+        vec([2, 0, Number.MAX_VALUE, -Number.MAX_VALUE]),
+      );
+      const result = engine.updateEmbedding(vec([0, 0, Infinity, -Infinity]));
+      expect(result.similarity).toBeCloseTo(1);
+    });
+  });
+
+  describe("magnitudeDrift", () => {
+    it("reports the relative change in length next to the directional drift", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(engine.updateEmbedding(vec([3, 4]), 0).magnitudeDrift).toBe(0);
+
+      const longer = engine.updateEmbedding(vec([6, 8]), 1000);
+      expect(longer.driftScore).toBeCloseTo(0);
+      expect(longer.magnitudeDrift).toBeCloseTo(1);
+
+      const shorter = engine.updateEmbedding(vec([0, 3]), 2000);
+      expect(shorter.driftScore).toBeCloseTo(0.2);
+      expect(shorter.magnitudeDrift).toBeCloseTo(-0.7);
+    });
+
+    it("is 0 against a zero baseline and with l2-normalized inputs", () => {
+      const zero = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      zero.updateEmbedding(vec([0, 0]), 0);
+      expect(zero.updateEmbedding(vec([1, 0]), 1000).magnitudeDrift).toBe(0);
+
+      const normalized = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        normalizeInputs: "l2",
+      });
+      normalized.updateEmbedding(vec([3, 4]), 0);
+      expect(
+        normalized.updateEmbedding(vec([30, 40]), 1000).magnitudeDrift,
+      ).toBeCloseTo(0);
+    });
+  });
+
+  describe("snapshotCompression", () => {
+    it("replaces the vector with its top-k components", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        snapshotCompression: { method: "topk", k: 2 },
+      });
+      engine.updateEmbedding(vec([0.1, -5, 3, 0]));
+      const snapshot = engine.getSnapshotCompressed();
+      expect(snapshot).not.toHaveProperty("vector");
+      expect(snapshot.updateCount).toBe(1);
+      expect(snapshot.compressed).toEqual({
+        method: "topk",
+        indices: [1, 2],
+        values: [-5, 3],
+        dimension: DIM,
+      });
+    });
+
+    it("projects the state onto principal components learned from the embeddings", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        snapshotCompression: { method: "pca", components: 2 },
+      });
+      const before = engine.getSnapshotCompressed().compressed;
+      expect(before).toEqual({ method: "pca", coordinates: [0, 0], dimension: 0 });
+
+      for (let i = 0; i < 200; i++) {
+        engine.updateEmbedding(vec([1, Math.sin(i), 0.3 * Math.cos(i)]), i);
+      }
+      const { compressed } = engine.getSnapshotCompressed();
+      expect(compressed.method).toBe("pca");
+      expect(compressed.dimension).toBe(DIM);
+      if (compressed.method === "pca") {
+        expect(compressed.coordinates).toHaveLength(2);
+        expect(compressed.coordinates.every(Number.isFinite)).toBe(true);
+      }
+
+      engine.reset();
+      expect(engine.getSnapshotCompressed().compressed).toMatchObject({
+        coordinates: [0, 0],
+      });
+    });
+
+    it("requires the option and validates it", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.getSnapshotCompressed()).toThrow(EngineError);
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.5,
+            snapshotCompression: { method: "topk", k: 0 },
+          }),
+      ).toThrow(EngineError);
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.5,
+            snapshotCompression: { method: "pca", learningRate: 2 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("pca2d", () => {
+    it("projects embeddings and the state onto the learned plane", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        pca2d: {},
+      });
+      expect(engine.getState2d()).toEqual([0, 0]);
+      expect(engine.project2d(vec([1, 0]))).toEqual([0, 0]);
+
+      for (let i = 0; i < 1000; i++) {
+        engine.updateEmbedding(
+          vec([1, 2 * Math.sin(i * 0.7), 0.5 * Math.cos(i * 1.3)]),
+          i,
+        );
+      }
+      // The leading component is the high-variance second axis.
+      const [high] = engine.project2d(vec([1, 2]));
+      const [low] = engine.project2d(vec([1, 0, 2]));
+      expect(Math.abs(high)).toBeGreaterThan(1.8);
+      expect(Math.abs(low)).toBeLessThan(0.2);
+
+      const state = engine.getState2d();
+      expect(state).toHaveLength(2);
+      expect(state.every(Number.isFinite)).toBe(true);
+    });
+
+    it("restarts on reset and validates its input", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        pca2d: {},
+      });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1);
+      expect(() => engine.project2d([1, 0])).toThrow(EngineError);
+      engine.reset();
+      expect(engine.getState2d()).toEqual([0, 0]);
+    });
+
+    it("requires the option", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.getState2d()).toThrow(/pca2d/);
+      expect(() => engine.project2d(vec([1]))).toThrow(EngineError);
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.5,
+            pca2d: { learningRate: 0 },
+          }),
+      ).toThrow(EngineError);
+    });
+  });
+
+  describe("concept anchors", () => {
+    it("reports the anchor most similar to each embedding", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      expect(engine.updateEmbedding(vec([1, 0]), 0).anchor).toBeUndefined();
+
+      engine.addAnchor("billing", vec([1, 0]));
+      engine.addAnchor("support", vec([0, 1]));
+      expect(engine.getAnchorLabels()).toEqual(["billing", "support"]);
+
+      const result = engine.updateEmbedding(vec([1, 3]), 1000);
+      expect(result.anchor?.label).toBe("support");
+      expect(result.anchor?.similarity).toBeCloseTo(3 / Math.sqrt(10));
+      expect(engine.updateEmbedding(vec([2, 1]), 2000).anchor?.label).toBe(
+        "billing",
+      );
+
+      expect(engine.removeAnchor("support")).toBe(true);
+      expect(engine.removeAnchor("support")).toBe(false);
+      expect(engine.updateEmbedding(vec([0, 1]), 3000).anchor).toEqual({
+        label: "billing",
+        similarity: 0,
+      });
+    });
+
+    it("checks dimensions and survives reset and export", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.addAnchor("spam", vec([0, 0, 1]));
+      expect(engine.getDimension()).toBe(DIM);
+      expect(() => engine.addAnchor("short", [1, 0])).toThrow(EngineError);
+      expect(() => engine.updateEmbedding([1, 0])).toThrow(EngineError);
+
+      engine.updateEmbedding(vec([1]));
+      engine.reset();
+      expect(engine.getAnchorLabels()).toEqual(["spam"]);
+
+      const restored = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      restored.importState(engine.exportState());
+      expect(restored.updateEmbedding(vec([0, 0, 2])).anchor).toEqual({
+        label: "spam",
+        similarity: 1,
+      });
+    });
+  });
+
+  describe("driftSmoothing", () => {
+    it("applies the threshold to the smoothed drift score", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftSmoothing: 0.2,
+      });
+      expect(engine.updateEmbedding(vec([1, 0]), 0).smoothedDriftScore).toBeUndefined();
+      expect(engine.updateEmbedding(vec([1, 0]), 1).smoothedDriftScore).toBe(0);
+      expect(engine.evaluate(vec([0, 1])).thresholdExceeded).toBe(false);
+
+      const results = [1, 2, 3, 4].map((i) =>
+        engine.updateEmbedding(vec(i % 2 === 1 ? [0, 1] : [1, 0]), 1 + i),
+      );
+      expect(results.map((r) => r.driftScore)).toEqual([1, 1, 1, 1]);
+      [0.2, 0.36, 0.488, 0.5904].forEach((expected, i) =>
+        expect(results[i]!.smoothedDriftScore).toBeCloseTo(expected),
+      );
+      expect(results.map((r) => r.thresholdExceeded)).toEqual([
+        false,
+        false,
+        false,
+        true,
+      ]);
+      expect(results[3]!.driftDetected).toBe(true);
+      expect(engine.getDriftEvents()[0]!.driftScore).toBeCloseTo(0.5904);
+    });
+
+    it("restarts from the first measured drift after reset", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftSmoothing: 0.2,
+      });
+      engine.updateEmbedding(vec([1, 0]), 0);
+      engine.updateEmbedding(vec([1, 0]), 1);
+      engine.reset();
+      engine.updateEmbedding(vec([1, 0]), 2);
+      const result = engine.updateEmbedding(vec([0, 1]), 3);
+      expect(result.smoothedDriftScore).toBe(1);
+      expect(result.thresholdExceeded).toBe(true);
+    });
+
+    it("rejects a smoothing factor outside (0, 1]", () => {
+      for (const driftSmoothing of [0, 1.5, NaN]) {
+        expect(
+          () =>
+            new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5, driftSmoothing }),
+        ).toThrow(EngineError);
+      }
+    });
+  });
+
+  describe("fromSnapshot", () => {
+    it("restores the state vector and counters from a snapshot", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1, 0]), 1000);
+      engine.updateEmbedding(vec([1, 1]), 2000);
+      const snapshot = JSON.parse(JSON.stringify(engine.getSnapshot()));
+
+      const restored = SemanticStateEngine.fromSnapshot(snapshot, {
+        alpha: 0.5,
+        driftThreshold: 0.5,
+      });
+      expect(restored.getSnapshot()).toMatchObject({
+        vector: snapshot.vector,
+        timestamp: 2000,
+        updateCount: 2,
+        lastDriftScore: snapshot.lastDriftScore,
+        lastSimilarity: snapshot.lastSimilarity,
+      });
+
+      const next = vec([0, 1]);
+      expect(restored.updateEmbedding(next, 3000).similarity).toBeCloseTo(
+        engine.updateEmbedding(next, 3000).similarity,
+      );
+      expect(restored.getSnapshot().vector).toEqual(engine.getSnapshot().vector);
+    });
+
+    it("accepts raw snapshots and validates against the config", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([0, 2]), 1000);
+      const raw = engine.getSnapshotRaw64();
+
+      const restored = SemanticStateEngine.fromSnapshot(raw, {
+        alpha: 1,
+        driftThreshold: 0.5,
+        dimension: DIM,
+      });
+      expect(restored.getSnapshot().vector).toEqual(vec([0, 2]));
+      expect(() =>
+        SemanticStateEngine.fromSnapshot(raw, {
+          alpha: 1,
+          driftThreshold: 0.5,
+          dimension: 8,
+        }),
+      ).toThrow(EngineError);
+      expect(() =>
+        SemanticStateEngine.fromSnapshot(
+          { vector: [], timestamp: 0, updateCount: 3 },
+          { alpha: 1, driftThreshold: 0.5 },
+        ),
+      ).toThrow(EngineError);
+    });
+
+    it("restores an empty engine from a snapshot taken before any update", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const restored = SemanticStateEngine.fromSnapshot(engine.getSnapshot(), {
+        alpha: 1,
+        driftThreshold: 0.5,
+      });
+      expect(restored.getUpdateCount()).toBe(0);
+      expect(restored.updateEmbedding(vec([1]), 0).similarity).toBe(1);
+    });
+  });
+
+  describe("driftVelocity and driftAcceleration", () => {
+    /** Unit vectors whose consecutive drift scores are `drifts`. */
+    function walk(drifts: number[]): number[][] {
+      let angle = 0;
+      return [0, ...drifts].map((drift) => {
+        angle += Math.acos(1 - drift);
+        return vec([Math.cos(angle), Math.sin(angle)]);
+      });
+    }
+
+    it("reports the derivatives of the drift score over the recent history", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const [first, ...rest] = walk([0.01, 0.04, 0.09, 0.16]);
+      engine.updateEmbedding(first!, 0);
+      expect(engine.getSnapshot().driftVelocity).toBeUndefined();
+
+      engine.updateEmbedding(rest[0]!, 60_000);
+      expect(engine.getSnapshot().driftVelocity).toBeCloseTo(0.01);
+      expect(engine.getSnapshot().driftAcceleration).toBeUndefined();
+
+      rest.slice(1).forEach((v, i) => engine.updateEmbedding(v, (i + 2) * 60_000));
+      const snapshot = engine.getSnapshot();
+      expect(snapshot.driftVelocity).toBeCloseTo(0.08);
+      expect(snapshot.driftAcceleration).toBeCloseTo(0.02);
+    });
+
+    it("distinguishes accelerating from steady drift", () => {
+      const steady = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      walk([0.1, 0.1, 0.1, 0.1]).forEach((v, i) =>
+        steady.updateEmbedding(v, i * 60_000),
+      );
+      expect(steady.getSnapshot().driftAcceleration).toBeLessThan(0);
+
+      const accelerating = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      walk([0, 0, 0.05, 0.2]).forEach((v, i) =>
+        accelerating.updateEmbedding(v, i * 60_000),
+      );
+      expect(accelerating.getSnapshot().driftAcceleration).toBeGreaterThan(0);
+    });
+  });
+
+  describe("resultDetail", () => {
+    function engineWith(resultDetail?: "minimal" | "standard" | "full") {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        attributionTopK: 2,
+        driftDirection: true,
+        resultDetail,
+      });
+      engine.addReference("golden", vec([1]));
+      engine.updateEmbedding(vec([1]), 0);
+      return engine;
+    }
+
+    it("includes the embedding and vector diagnostics by default", () => {
+      const result = engineWith().updateEmbedding(vec([0, 1]), 1000);
+      expect(result.vector).toEqual(vec([0, 1]));
+      expect(result.attribution).toHaveLength(2);
+      expect(result.driftDirection).toHaveLength(DIM);
+      expect(result.referenceSimilarities).toBeDefined();
+    });
+
+    it("omits the vector-valued fields at standard detail", () => {
+      const result = engineWith("standard").updateEmbedding(vec([0, 1]), 1000);
+      expect(result).not.toHaveProperty("vector");
+      expect(result).not.toHaveProperty("attribution");
+      expect(result).not.toHaveProperty("driftDirection");
+      expect(result.referenceSimilarities).toBeDefined();
+      expect(result.driftDetected).toBe(true);
+    });
+
+    it("keeps only scores and flags at minimal detail", () => {
+      const engine = engineWith("minimal");
+      const result = engine.updateFrom("user", vec([0, 1]), 1000);
+      expect(Object.keys(result).sort()).toEqual(
+        [
+          "similarity",
+          "driftScore",
+          "magnitudeDrift",
+          "driftDetected",
+          "thresholdExceeded",
+          "inputShift",
+          "driftLevel",
+          "changePointDetected",
+          "alpha",
+          "warmingUp",
+          "outlier",
+          "rejected",
+          "timestamp",
+        ].sort(),
+      );
+      expect(result.driftScore).toBeCloseTo(1);
+      expect(engine.getSnapshot().vector).toEqual(vec([0, 1]));
+    });
+
+    it("still tracks sources at minimal detail", () => {
+      const engine = engineWith("minimal");
+      engine.updateFrom("user", vec([0, 1]), 1000);
+      const result = engine.updateFrom("agent", vec([1]), 2000);
+      expect(result).not.toHaveProperty("source");
+      expect(result).not.toHaveProperty("sourceSimilarities");
+      expect(engine.getSourceVector("user")).toEqual(vec([0, 1]));
+      expect(engine.getSources()).toEqual(["user", "agent"]);
+    });
+
+    it("packs an empty vector when the embedding is omitted", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        resultDetail: "standard",
+      });
+      const result = unpackUpdateResult(engine.updateEmbeddingPacked(vec([1]), 0));
+      expect(result.vector).toHaveLength(0);
+      expect(result.similarity).toBe(1);
+    });
+  });
+
+  describe("setDimensionMask", () => {
+    it("excludes masked dimensions from drift and fusion", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.1 });
+      engine.setDimensionMask([true, true, true, false]);
+      engine.updateEmbedding(vec([1, 0, 0, 1]), 0);
+      const result = engine.updateEmbedding(vec([1, 0, 0, -1]), 1000);
+      expect(result.similarity).toBeCloseTo(1);
+      expect(result.driftDetected).toBe(false);
+      expect(engine.getSnapshot().vector).toEqual(vec([1]));
+    });
+
+    it("scales dimensions by weight and can be removed", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.setDimensionMask([0.5, 1, 1, 1]);
+      expect(engine.getDimensionMask()).toEqual([0.5, 1, 1, 1]);
+      engine.updateEmbedding(vec([2, 1]), 0);
+      expect(engine.getSnapshot().vector).toEqual(vec([1, 1]));
+
+      engine.setDimensionMask(null);
+      expect(engine.getDimensionMask()).toBeNull();
+      engine.updateEmbedding(vec([2, 1]), 1000);
+      expect(engine.getSnapshot().vector).toEqual(vec([2, 1]));
+    });
+
+    it("is inherited by forks", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.setDimensionMask([1, 1, 1, 0]);
+      engine.updateEmbedding(vec([1, 0, 0, 1]), 0);
+      expect(engine.fork().getDimensionMask()).toEqual([1, 1, 1, 0]);
+    });
+
+    it("rejects invalid masks and mismatched embeddings", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.setDimensionMask([])).toThrow("must not be empty");
+      expect(() => engine.setDimensionMask([1, -1])).toThrow(
+        "got -1 at index 1",
+      );
+      engine.setDimensionMask([1, 0]);
+      expect(() => engine.updateEmbedding(vec([1]), 0)).toThrow(
+        "the dimension mask covers 2 dimensions, got 4",
+      );
+      expect(engine.getUpdateCount()).toBe(0);
+    });
+  });
+
+  describe("horizons", () => {
+    it("reports the drift of the fast horizon against the slow one", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        horizons: { alphas: { fast: 1, slow: 0.1 }, threshold: 0.3 },
+      });
+      const first = engine.updateEmbedding(vec([1]), 0);
+      expect(first.horizonDriftScore).toBe(0);
+      expect(first.regimeShift).toBe(false);
+
+      const second = engine.updateEmbedding(vec([0, 1]), 1000);
+      // fast = [0, 1], slow = [0.9, 0.1]
+      expect(second.horizonDriftScore).toBeCloseTo(1 - 0.1 / Math.hypot(0.9, 0.1));
+      expect(second.regimeShift).toBe(true);
+      expect(engine.getHorizonStates()).toEqual({
+        fast: vec([0, 1]),
+        slow: [expect.closeTo(0.9), expect.closeTo(0.1), 0, 0],
+      });
+    });
+
+    it("uses the default horizons and the live drift threshold", () => {
+      const make = () =>
+        new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5, horizons: {} });
+      const engine = make();
+      engine.updateEmbedding(vec([1]), 0);
+      expect(Object.keys(engine.getHorizonStates())).toEqual(["fast", "medium", "slow"]);
+      // A minimum similarity of 0.5 is a drift score threshold of 0.5.
+      const result = engine.updateEmbedding(vec([0, 1]), 1000);
+      expect(result.horizonDriftScore).toBeGreaterThan(0.2);
+      expect(result.horizonDriftScore).toBeLessThan(0.5);
+      expect(result.regimeShift).toBe(false);
+
+      // Raising the minimum similarity to 0.8 lowers it to 0.2.
+      const stricter = make();
+      stricter.updateEmbedding(vec([1]), 0);
+      stricter.setDriftThreshold(0.8);
+      expect(stricter.updateEmbedding(vec([0, 1]), 1000).regimeShift).toBe(true);
+    });
+
+    it("persists horizon states and clears them on reset", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        horizons: {},
+      });
+      engine.updateEmbedding(vec([1]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1000);
+      const copy = engine.fork();
+      expect(copy.getHorizonStates()).toEqual(engine.getHorizonStates());
+      engine.reset();
+      expect(engine.getHorizonStates()).toEqual({});
+    });
+
+    it("rejects fewer than two distinct alphas", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.5,
+            horizons: { alphas: { only: 0.5 } },
+          }),
+      ).toThrow("horizons must have at least two alphas");
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 0.5,
+            driftThreshold: 0.5,
+            horizons: { alphas: { a: 0.5, b: 0.5 } },
+          }),
+      ).toThrow("not all equal");
+    });
+  });
+
+  describe("onDrift", () => {
+    it("delivers the drift event to every listener until unsubscribed", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        metadata: { tenant: "a" },
+      });
+      const first = vi.fn();
+      const second = vi.fn();
+      const unsubscribe = engine.onDrift(first);
+      engine.onDrift(second);
+
+      engine.updateEmbedding(vec([1]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1000);
+      expect(first).toHaveBeenCalledTimes(1);
+      expect(first).toHaveBeenCalledWith(engine.getDriftEvents()[0]);
+      expect(first.mock.calls[0]![0]).toMatchObject({
+        timestamp: 1000,
+        driftLevel: "warning",
+        metadata: { tenant: "a" },
+      });
+      expect(second).toHaveBeenCalledTimes(1);
+
+      unsubscribe();
+      engine.updateEmbedding(vec([1]), 2000);
+      expect(first).toHaveBeenCalledTimes(1);
+      expect(second).toHaveBeenCalledTimes(2);
+    });
+
+    it("fires even when the drift event log is disabled", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftEventLogSize: 0,
+      });
+      const listener = vi.fn();
+      engine.onDrift(listener);
+      engine.updateEmbedding(vec([1]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1000);
+      expect(listener).toHaveBeenCalledWith(
+        expect.objectContaining({ timestamp: 1000, driftScore: expect.closeTo(1) }),
+      );
+      expect(engine.getDriftEvents()).toEqual([]);
+    });
+
+    it("hands each listener its own copy", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        metadata: { tenant: "a" },
+      });
+      engine.onDrift((event) => {
+        event.metadata!.tenant = "changed";
+      });
+      engine.updateEmbedding(vec([1]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1000);
+      expect(engine.getDriftEvents()[0]!.metadata).toEqual({ tenant: "a" });
+    });
+  });
+
+  describe("dimensionChangePolicy", () => {
+    it("throws on a new dimension by default", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding([1, 0], 0);
+      expect(() => engine.updateEmbedding([1, 0, 0], 1000)).toThrow(
+        "Embedding dimension mismatch: expected 2, got 3",
+      );
+    });
+
+    it("resets and reseeds the engine under reset", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        dimensionChangePolicy: "reset",
+      });
+      engine.addReference("old", [1, 0]);
+      engine.updateEmbedding([1, 0], 0);
+      engine.updateEmbedding([0, 1], 1000);
+
+      const result = engine.updateEmbedding([0, 0, 2], 2000);
+      expect(result.dimensionChange).toEqual({ from: 2, to: 3, action: "reset" });
+      expect(result.driftDetected).toBe(false);
+      expect(engine.getUpdateCount()).toBe(1);
+      expect(engine.getDimension()).toBe(3);
+      expect(engine.getReferenceNames()).toEqual([]);
+      expect(engine.updateEmbedding([0, 0, 1], 3000).dimensionChange).toBeUndefined();
+    });
+
+    it("pads or truncates the embedding under pad", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        dimensionChangePolicy: "pad",
+      });
+      engine.updateEmbedding([1, 0, 0], 0);
+
+      const padded = engine.updateEmbedding([0, 1], 1000);
+      expect(padded.dimensionChange).toEqual({ from: 3, to: 2, action: "pad" });
+      expect(engine.getSnapshot().vector).toEqual([0, 1, 0]);
+
+      const truncated = engine.updateEmbedding([1, 0, 0, 5], 2000);
+      expect(truncated.dimensionChange).toEqual({ from: 3, to: 4, action: "truncate" });
+      expect(engine.getSnapshot().vector).toEqual([1, 0, 0]);
+      expect(engine.getUpdateCount()).toBe(3);
+    });
+
+    it("cannot reset a configured dimension", () => {
+      expect(
+        () =>
+          new SemanticStateEngine({
+            alpha: 1,
+            driftThreshold: 0.5,
+            dimension: 4,
+            dimensionChangePolicy: "reset",
+          }),
+      ).toThrow("cannot be combined with dimension");
+    });
+  });
+
+  describe("embeddingSample", () => {
+    const feed = (engine: SemanticStateEngine, n: number) => {
+      for (let i = 1; i <= n; i++) engine.updateEmbedding(vec([1, i]), i * 1000);
+    };
+
+    it("keeps every stride-th raw embedding, evicting the oldest", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        normalizeInputs: "l2",
+        embeddingSample: { method: "stride", stride: 3, size: 2 },
+      });
+      feed(engine, 10);
+      expect(engine.getEmbeddingSample()).toEqual([
+        { timestamp: 6000, index: 6, vector: vec([1, 6]) },
+        { timestamp: 9000, index: 9, vector: vec([1, 9]) },
+      ]);
+    });
+
+    it("keeps a bounded, ordered and reproducible reservoir sample", () => {
+      const make = () =>
+        new SemanticStateEngine({
+          alpha: 0.5,
+          driftThreshold: 0.5,
+          embeddingSample: { size: 10, seed: 7 },
+        });
+      const engine = make();
+      feed(engine, 5);
+      expect(engine.getEmbeddingSample().map((e) => e.index)).toEqual([1, 2, 3, 4, 5]);
+
+      feed(engine, 500);
+      const indices = engine.getEmbeddingSample().map((e) => e.index);
+      expect(indices).toHaveLength(10);
+      expect([...indices].sort((a, b) => a - b)).toEqual(indices);
+      expect(indices.some((i) => i > 5)).toBe(true);
+
+      const again = make();
+      feed(again, 5);
+      feed(again, 500);
+      expect(again.getEmbeddingSample()).toEqual(engine.getEmbeddingSample());
+    });
+
+    it("samples uniformly over the stream", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        embeddingSample: { size: 200, seed: 1 },
+      });
+      feed(engine, 2000);
+      const early = engine.getEmbeddingSample().filter((e) => e.index <= 1000);
+      expect(early.length).toBeGreaterThan(70);
+      expect(early.length).toBeLessThan(130);
+    });
+
+    it("is persisted and cleared on reset", () => {
+      const config = {
+        alpha: 0.5,
+        driftThreshold: 0.5,
+        embeddingSample: { size: 3 },
+      };
+      const engine = new SemanticStateEngine(config);
+      feed(engine, 5);
+      const copy = new SemanticStateEngine(config);
+      copy.importState(engine.exportState());
+      expect(copy.getEmbeddingSample()).toEqual(engine.getEmbeddingSample());
+      expect(copy.exportState().embeddingSample!.seen).toBe(5);
+
+      engine.reset();
+      expect(engine.getEmbeddingSample()).toEqual([]);
+    });
+  });
+
+  describe("timeToHealth", () => {
+    it("estimates the age-decay crossing without further updates", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1]), 0);
+      // health = 1 − 0.0001 · age
+      expect(engine.timeToHealth(0.5, 0)).toEqual({
+        ageDecayMs: 5000,
+        driftTrendMs: null,
+      });
+      expect(engine.timeToHealth(0.5, 2000).ageDecayMs).toBe(3000);
+      expect(engine.timeToHealth(0.5, 6000).ageDecayMs).toBe(0);
+    });
+
+    it("brings the crossing forward when drift is rising", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.updateEmbedding(vec([1]), 0);
+      engine.updateEmbedding(vec([1, 1]), 1000);
+      const drift = 1 - Math.SQRT1_2;
+      const health = 1 - 0.5 * drift;
+      const { ageDecayMs, driftTrendMs } = engine.timeToHealth(0.5, 1000);
+      expect(ageDecayMs).toBe(Math.ceil((health - 0.5) / 0.0001));
+      // drift keeps rising by `drift` per second
+      expect(driftTrendMs).toBe(
+        Math.ceil((health - 0.5) / (0.0001 + (0.5 * drift) / 1000)),
+      );
+    });
+
+    it("returns null when the floor keeps health above the target", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        health: { floor: 0.6 },
+      });
+      engine.updateEmbedding(vec([1]), 0);
+      engine.updateEmbedding(vec([0, 1]), 1000);
+      expect(engine.timeToHealth(0.5, 1000)).toEqual({
+        ageDecayMs: null,
+        driftTrendMs: null,
+      });
+    });
+
+    it("rejects targets outside [0, 1]", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      expect(() => engine.timeToHealth(1.5)).toThrow(
+        "target must be in the range [0, 1], got 1.5",
+      );
+    });
+  });
+
+  describe("divergence drift metrics", () => {
+    it("scores drift between distributions with jensen-shannon", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftMetric: "jensen-shannon",
+      });
+      engine.updateEmbedding([0.7, 0.2, 0.1], 0);
+      const close = engine.evaluate([0.6, 0.3, 0.1], 1000);
+      expect(close.driftScore).toBeLessThan(0.05);
+      expect(close.thresholdExceeded).toBe(false);
+
+      const result = engine.updateEmbedding([0, 0, 1], 1000);
+      expect(result.driftScore).toBeCloseTo(jsDivergence([0, 0, 1], [0.7, 0.2, 0.1]));
+      expect(result.driftDetected).toBe(true);
+    });
+
+    it("measures KL(embedding ‖ baseline) with kullback-leibler", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 1,
+        driftMetric: "kullback-leibler",
+      });
+      engine.updateEmbedding([0.9, 0.1], 0);
+      const result = engine.updateEmbedding([0.5, 0.5], 1000);
+      expect(result.driftScore).toBeCloseTo(klDivergence([0.5, 0.5], [0.9, 0.1]));
+    });
+
+    it("rejects embeddings that are not distributions", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        driftMetric: "jensen-shannon",
+      });
+      expect(() => engine.updateEmbedding([0.5, -0.1], 0)).toThrow(
+        "requires non-negative embeddings, got -0.1 at index 1",
+      );
+      expect(() => engine.updateEmbedding([0, 0], 0)).toThrow(
+        "requires embeddings with a non-zero component",
+      );
+      expect(engine.getUpdateCount()).toBe(0);
+    });
+  });
+
+  describe("logger", () => {
+    it("logs every update at debug level with its scores", () => {
+      const logger = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        metadata: { tenant: "a" },
+        logger,
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 2_000);
+
+      expect(logger).toHaveBeenCalledTimes(2);
+      const record = logger.mock.calls[1]![0];
+      expect(record).toMatchObject({
+        level: "debug",
+        event: "update",
+        timestamp: 2_000,
+        fields: { updateCount: 2, similarity: 1, driftScore: 0, alpha: 1 },
+        metadata: { tenant: "a" },
+      });
+      expect(record.fields.durationUs).toBeGreaterThanOrEqual(0);
+    });
+
+    it("logs reported drift at warn level", () => {
+      const logger = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        logger,
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 2_000);
+
+      const drift = logger.mock.calls
+        .map(([r]) => r)
+        .filter((r) => r.event === "drift_detected");
+      expect(drift).toHaveLength(1);
+      expect(drift[0]).toMatchObject({
+        level: "warn",
+        timestamp: 2_000,
+        fields: { driftScore: 1, similarity: 0, threshold: 0.5 },
+      });
+      expect(drift[0].fields).not.toHaveProperty("timestamp");
+    });
+
+    it("logs resets with their reason", () => {
+      const logger = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        dimensionChangePolicy: "reset",
+        logger,
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      engine.reset();
+      engine.resetBaseline(vec([0, 1, 0, 0]), 2_000);
+      engine.updateEmbedding([1, 0], 3_000);
+
+      const resets = logger.mock.calls
+        .map(([r]) => r)
+        .filter((r) => r.event === "reset");
+      expect(resets.map((r) => r.fields.reason)).toEqual([
+        "reset",
+        "resetBaseline",
+        "dimensionChange",
+      ]);
+      expect(resets.every((r) => r.level === "info")).toBe(true);
+      expect(resets[2]!.fields).toMatchObject({ from: 4, to: 2 });
+    });
+
+    it("is not part of the serializable config", () => {
+      const logger = vi.fn();
+      const engine = SemanticStateEngine.fromConfig(
+        { alpha: 1, driftThreshold: 0.5 },
+        { logger },
+      );
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      expect(logger).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe("output buffer", () => {
+    it("writes the fused state into the registered buffer", () => {
+      const engine = new SemanticStateEngine({ alpha: 0.5, driftThreshold: 0.5 });
+      const output = new Float32Array(DIM);
+      engine.setOutputBuffer(output);
+
+      engine.updateEmbeddingInto(vec([1, 0, 0, 0]), 1000);
+      const result = engine.updateEmbeddingInto(vec([0, 1, 0, 0]), 2000);
+
+      expect(result).not.toHaveProperty("vector");
+      expect(result.driftScore).toBeGreaterThan(0);
+      expect(Array.from(output)).toEqual(engine.getSnapshot().vector);
+    });
+
+    it("throws NOT_INITIALIZED without a registered buffer", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.setOutputBuffer(new Float32Array(DIM));
+      engine.setOutputBuffer(null);
+      expect(() => engine.updateEmbeddingInto(vec([1, 0, 0, 0]))).toThrow(
+        "requires a buffer from setOutputBuffer()",
+      );
+      expect(engine.getUpdateCount()).toBe(0);
+    });
+
+    it("throws DIMENSION_MISMATCH for a buffer of the wrong length", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      const listener = vi.fn();
+      engine.subscribe(listener);
+      engine.setOutputBuffer(new Float32Array(DIM + 1));
+      expect(() => engine.updateEmbeddingInto(vec([1, 0, 0, 0]))).toThrow(
+        "Output buffer dimension mismatch",
+      );
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      expect(() => engine.updateEmbeddingInto(vec([0, 1, 0, 0]), 2000)).toThrow(
+        "expected 4, got 5",
+      );
+      expect(engine.getUpdateCount()).toBe(1);
+      expect(engine.getHistory()).toHaveLength(1);
+      expect(engine.getSnapshot().vector).toEqual(vec([1, 0, 0, 0]));
+      expect(listener).toHaveBeenCalledTimes(1);
+    });
+
+    it("takes on the embedding's dimension under the reset policy", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        dimensionChangePolicy: "reset",
+      });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1000);
+      const output = new Float32Array(2);
+      engine.setOutputBuffer(output);
+      engine.updateEmbeddingInto([0, 1], 2000);
+      expect(Array.from(output)).toEqual([0, 1]);
+    });
+  });
+
+  describe("suppressUntil", () => {
+    it("records but does not report drift inside the window", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        onDriftDetected,
+      });
+      const listener = vi.fn();
+      engine.onDrift(listener);
+      engine.suppressUntil(3_000);
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      const result = engine.updateEmbedding(vec([0, 1, 0, 0]), 2_000);
+
+      expect(result.driftDetected).toBe(false);
+      expect(result.driftSuppressed).toBe(true);
+      expect(onDriftDetected).not.toHaveBeenCalled();
+      expect(listener).not.toHaveBeenCalled();
+      expect(engine.getSnapshot().vector).toEqual(vec([0, 1, 0, 0]));
+      expect(engine.getDriftEvents()).toMatchObject([
+        { timestamp: 2_000, suppressed: true },
+      ]);
+    });
+
+    it("keeps the drift type on the recorded event only", () => {
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        classifyDrift: true,
+      });
+      engine.suppressUntil(3_000);
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      const result = engine.updateEmbedding(vec([0, 1, 0, 0]), 2_000);
+
+      expect(result.driftSuppressed).toBe(true);
+      expect(result).not.toHaveProperty("driftType");
+      expect(engine.getDriftEvents()[0]!.driftType).toBeDefined();
+    });
+
+    it("reports drift again once the window has passed", () => {
+      const onDriftDetected = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        onDriftDetected,
+      });
+      engine.suppressUntil(2_000);
+
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      const result = engine.updateEmbedding(vec([0, 1, 0, 0]), 2_000);
+
+      expect(result.driftDetected).toBe(true);
+      expect(result).not.toHaveProperty("driftSuppressed");
+      expect(onDriftDetected).toHaveBeenCalledTimes(1);
+      expect(engine.getDriftEvents()[0]).not.toHaveProperty("suppressed");
+    });
+
+    it("logs suppressed drift at info level", () => {
+      const logger = vi.fn();
+      const engine = new SemanticStateEngine({
+        alpha: 1,
+        driftThreshold: 0.5,
+        logger,
+      });
+      engine.suppressUntil(3_000);
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 1_000);
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 2_000);
+
+      const drift = logger.mock.calls
+        .map(([r]) => r)
+        .filter((r) => r.event === "drift_detected");
+      expect(drift).toMatchObject([{ level: "info", fields: { suppressed: true } }]);
+    });
+
+    it("persists the window in the exported state", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      engine.suppressUntil(5_000);
+      const restored = new SemanticStateEngine({ alpha: 1, driftThreshold: 0.5 });
+      restored.importState(engine.exportState());
+      expect(restored.getSuppressUntil()).toBe(5_000);
+      expect(() => engine.suppressUntil(NaN)).toThrow("must be finite");
+    });
+  });
+
+  describe("state rotation", () => {
+    it("reports the angular velocity of the state vector", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      expect(engine.getSnapshot()).not.toHaveProperty("cumulativeRotation");
+
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 60_000);
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 120_000);
+      engine.updateEmbedding(vec([1, 1, 0, 0]), 150_000);
+
+      const snapshot = engine.getSnapshot();
+      expect(snapshot.angularVelocity).toBeCloseTo(Math.PI / 2);
+      expect(snapshot.cumulativeRotation).toBeCloseTo((3 * Math.PI) / 4);
+      expect(snapshot.meanAngularVelocity).toBeCloseTo((3 * Math.PI) / 4 / 2.5);
+    });
+
+    it("restarts rotation tracking on reset and resetBaseline", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 60_000);
+      engine.resetBaseline(vec([1, 0, 0, 0]), 120_000);
+      expect(engine.getSnapshot()).not.toHaveProperty("cumulativeRotation");
+      expect(engine.getSnapshot()).not.toHaveProperty("angularVelocity");
+
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 120_000);
+      expect(engine.getSnapshot().cumulativeRotation).toBeCloseTo(Math.PI / 2);
+      expect(engine.getSnapshot()).not.toHaveProperty("angularVelocity");
+    });
+
+    it("skips steps from or to a zero state", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      engine.updateEmbedding(vec([0, 0, 0, 0]), 60_000);
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 120_000);
+      expect(engine.getSnapshot()).not.toHaveProperty("cumulativeRotation");
+
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 180_000);
+      expect(engine.getSnapshot().cumulativeRotation).toBeCloseTo(Math.PI / 2);
+    });
+
+    it("survives an export/import round trip", () => {
+      const engine = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      engine.updateEmbedding(vec([1, 0, 0, 0]), 0);
+      engine.updateEmbedding(vec([0, 1, 0, 0]), 60_000);
+      const restored = new SemanticStateEngine({ alpha: 1, driftThreshold: 0 });
+      restored.importState(engine.exportState());
+      expect(restored.getSnapshot().angularVelocity).toBeCloseTo(Math.PI / 2);
+      expect(restored.getSnapshot().cumulativeRotation).toBeCloseTo(Math.PI / 2);
+    });
+  });
+});
@@ -1,4 +1,66 @@
-import { emaFusion, cosineSimilarity } from "../math/vector.js";
+import {
+  magnitude,
+  scale,
+  emaFusion,
+  cosineSimilarity,
+  euclideanDistance,
+  manhattanDistance,
+  angularDistance,
+  mahalanobisDistance,
+  jsDivergence,
+  klDivergence,
+  normalize,
+  standardize,
+  mean,
+  median,
+  weightedMean,
+} from "../math/vector.js";
+import { kalmanUpdate } from "../math/kalman.js";
+import { P2Quantile } from "../math/P2Quantile.js";
+import { mulberry32, RandomProjection } from "../math/projection.js";
+import { OnlineKMeans } from "../math/OnlineKMeans.js";
+import { OnlinePCA } from "../math/OnlinePCA.js";
+import { RunningStats } from "../math/RunningStats.js";
+import { PackedVector, type StoragePrecision } from "../math/quantize.js";
+import {
+  toDense,
+  topK,
+  validateSparse,
+  type SparseVector,
+} from "../math/sparse.js";
+import {
+  createChangeDetector,
+  type ChangeDetector,
+  type ChangeDetectorConfig,
+} from "../detectors/index.js";
+import { EngineError } from "./errors.js";
+import { attributeDrift, type DimensionContribution } from "./attribution.js";
+import { diffSnapshots, type SnapshotDiff, type SnapshotLike } from "./diff.js";
+import { parseEngineConfig, type EngineBindings } from "./config.js";
+import { decodeState, encodeState, type BinaryPrecision } from "./binary.js";
+import {
+  fitLine,
+  fitQuadratic,
+  forecastHistory,
+  timeUntilHealth,
+  type Forecast,
+  type HealthTimeEstimate,
+} from "./forecast.js";
+import { aggregateHistory, type HistoryBucket } from "./aggregate.js";
+import { migrateState, STATE_VERSION } from "./migrate.js";
+import { formatPrometheus } from "./prometheus.js";
+import { packUpdateResult } from "./packed.js";
+import { clonePlain, elapsedMs } from "./runtime.js";
+import {
+  driftEventToOtel,
+  snapshotToOtel,
+  type OtelLogRecord,
+} from "./otel.js";
+import {
+  classifyDrift,
+  type DriftObservation,
+  type DriftType,
+} from "./driftType.js";
 
 /**
  * A generic embedding provider contract.
@@ -7,20 +69,646 @@ import { emaFusion, cosineSimilarity } from "../math/vector.js";
  * or other remote-inference wrappers.
  */
 export interface EmbeddingProvider {
-  getEmbedding(text: string): Promise<Float32Array | number[]>;
+  getEmbedding(text: string): Promise<Float32Array | Float64Array | number[]>;
 }
 
 /**
- * Age-based health decay rate: health lost per millisecond of inactivity.
+ * Default age-based health decay rate: health lost per millisecond of inactivity.
  * At this rate, age alone reduces health to 0 after ~10 seconds of inactivity.
  */
-const AGE_DECAY_RATE = 0.0001;
+const DEFAULT_AGE_DECAY_RATE = 0.0001;
 
 /**
- * Weight applied to the most-recent drift value when computing healthScore.
+ * Default weight applied to the most-recent drift value when computing healthScore.
  * A drift of 1.0 (orthogonal vectors) reduces health by 0.5.
  */
-const DRIFT_WEIGHT = 0.5;
+const DEFAULT_DRIFT_WEIGHT = 0.5;
+
+/**
+ * A measurement the healthScore is penalized by:
+ *
+ * - `"age"`: time (ms) since the last update.
+ * - `"drift"`: drift score of the most recent update.
+ * - `"variance"`: population variance of the drift scores over the last 10
+ *   entries of the update history — high for erratic streams.
+ * - `"updateRate"`: mean interval (ms) between the last 10 history entries —
+ *   high for streams that have slowed down.
+ */
+export type HealthFactorName = "age" | "drift" | "variance" | "updateRate";
+
+/**
+ * How a factor's measurement x turns into a health penalty:
+ *
+ * - `"linear"`: weight · x.
+ * - `"exponential"`: 1 − e^(−weight · x), which saturates at 1 instead of
+ *   growing without bound.
+ */
+export type HealthCurve = "linear" | "exponential";
+
+/** Weight and curve of one {@link HealthFactorName | health factor}. */
+export interface HealthFactor {
+  /** Scale of the factor's measurement; 0 disables the factor. */
+  weight?: number;
+
+  /**
+   * Penalty curve.
+   * @default "linear"
+   */
+  curve?: HealthCurve;
+}
+
+/**
+ * Parameters of the healthScore, a composition of named factors:
+ *
+ *   healthScore = clamp(1 − Σ penalty(factor), floor, ceiling)
+ *
+ * With the defaults only the `age` and `drift` factors are active, both
+ * linear, giving 1 − ageDecayRate · Δt − driftWeight · lastDrift.
+ */
+export interface HealthConfig {
+  /**
+   * Health lost per millisecond since the last update — shorthand for
+   * `factors.age.weight`. Lower this for slow update cadences, e.g.
+   * `1 / 86_400_000` to reach 0 after a day.
+   * @default 0.0001
+   */
+  ageDecayRate?: number;
+
+  /**
+   * Health lost per unit of the most recent drift score — shorthand for
+   * `factors.drift.weight`.
+   * @default 0.5
+   */
+  driftWeight?: number;
+
+  /**
+   * Weight and curve of each factor. Omitted factors and fields keep their
+   * current values; `variance` and `updateRate` default to weight 0.
+   */
+  factors?: Partial<Record<HealthFactorName, HealthFactor>>;
+
+  /**
+   * Lower bound of the healthScore.
+   * @default 0
+   */
+  floor?: number;
+
+  /**
+   * Upper bound of the healthScore.
+   * @default 1
+   */
+  ceiling?: number;
+}
+
+/** A {@link HealthConfig} with every parameter and factor filled in. */
+export type ResolvedHealthConfig = Required<Omit<HealthConfig, "factors">> & {
+  factors: Record<HealthFactorName, Required<HealthFactor>>;
+};
+
+const HEALTH_FACTORS: readonly HealthFactorName[] = [
+  "age",
+  "drift",
+  "variance",
+  "updateRate",
+];
+
+/** Number of recent history entries the `variance` and `updateRate` factors look at. */
+const HEALTH_FACTOR_WINDOW = 10;
+
+/** Default number of recent embeddings retained for windowed fusion strategies. */
+const DEFAULT_FUSION_WINDOW = 10;
+
+/**
+ * How incoming embeddings are fused into the state vector.
+ *
+ * - `"ema"`: Exponential Moving Average, S_t = α · E_t + (1 − α) · S_{t-1}.
+ * - `"sma"`: Simple moving average over the last `fusionWindow` embeddings.
+ * - `"wma"`: Linearly weighted moving average over the last `fusionWindow`
+ *   embeddings; the most recent embedding has the highest weight.
+ * - `"cumulative"`: Running mean of every embedding seen so far.
+ * - `"median"`: Per-dimension median of the last `fusionWindow` embeddings,
+ *   robust to occasional extreme embeddings.
+ * - `"kalman"`: Diagonal Kalman filter that tracks a per-dimension variance
+ *   alongside the state; see {@link KalmanConfig}.
+ */
+export type FusionStrategy =
+  | "ema"
+  | "sma"
+  | "wma"
+  | "cumulative"
+  | "median"
+  | "kalman";
+
+/**
+ * How the `"ema"` strategy initializes the state from the first embedding.
+ *
+ * - `"zero"`: blend the first embedding with a zero-vector origin,
+ *   S_1 = α · E_1. The baseline magnitude starts shrunk by α and grows
+ *   toward the embeddings' magnitude over the first updates.
+ * - `"first"`: seed the state with the first embedding, S_1 = E_1.
+ *
+ * The other fusion strategies always seed from the first embedding.
+ */
+export type InitMode = "zero" | "first";
+
+/**
+ * What the engine does with an update timestamped before the previous one
+ * (e.g. from a client with a skewed clock):
+ *
+ * - `"accept"`: use it as given. Ages computed from it can be negative.
+ * - `"clamp"`: treat it as simultaneous with the previous update.
+ * - `"error"`: throw an {@link EngineError} with code `"OUT_OF_ORDER_TIMESTAMP"`.
+ */
+export type TimestampPolicy = "accept" | "clamp" | "error";
+
+/**
+ * Where update timestamps come from:
+ *
+ * - `"caller"`: the timestamp passed to each update (now by default).
+ * - `"monotonic"`: an internal clock that follows the system time but never
+ *   goes backwards; timestamps passed to updates are ignored.
+ */
+export type ClockMode = "caller" | "monotonic";
+
+/**
+ * What the engine does with an embedding containing `NaN` or `±Infinity`,
+ * which would otherwise poison the state vector for every later update:
+ *
+ * - `"error"`: throw an {@link EngineError} with code `"NON_FINITE_EMBEDDING"`
+ *   and leave the state untouched.
+ * - `"zero"`: replace every non-finite component with 0.
+ * - `"clamp"`: replace `±Infinity` with `±Number.MAX_VALUE` and `NaN` with 0.
+ */
+export type NonFinitePolicy = "error" | "zero" | "clamp";
+
+/**
+ * What an update does with an embedding whose dimension differs from the
+ * state's, e.g. after an embedding model upgrade:
+ *
+ * - `"error"`: throw an {@link EngineError} with code `"DIMENSION_MISMATCH"`.
+ * - `"reset"`: reset the engine as {@link SemanticStateEngine.reset} does,
+ *   drop the references and anchors of the old dimension, and fuse the
+ *   embedding as the first of the new dimension.
+ * - `"pad"`: zero-pad or truncate the embedding to the state's dimension.
+ *
+ * The update reports what happened in {@link UpdateResult.dimensionChange}.
+ */
+export type DimensionChangePolicy = "error" | "reset" | "pad";
+
+/** How an update's mismatched dimension was handled. See {@link DimensionChangePolicy}. */
+export interface DimensionChange {
+  /** The dimension the engine held before the update. */
+  from: number;
+  /** The dimension of the incoming embedding. */
+  to: number;
+  /** What was done: the engine was reset, or the embedding padded or truncated. */
+  action: "reset" | "pad" | "truncate";
+}
+
+/**
+ * How much each {@link UpdateResult} carries, for callers that post or
+ * serialize every result:
+ *
+ * - `"minimal"`: the scores and flags only — every required field except
+ *   `vector`, plus `smoothedDriftScore`, `horizonDriftScore`, `regimeShift`,
+ *   `dimensionChange` and `driftSuppressed`. History, reference, anchor and
+ *   cross-source similarities are not computed.
+ * - `"standard"`: everything except the vector-valued fields — `vector`,
+ *   `attribution` and `driftDirection` are omitted (and not computed).
+ * - `"full"`: everything.
+ */
+export type ResultDetail = "minimal" | "standard" | "full";
+
+/**
+ * How the engine normalizes every embedding (and reference) before fusion
+ * and drift measurement.
+ *
+ * - `"none"`: use embeddings as supplied.
+ * - `"l2"`: scale to unit length, so magnitude differences between sources
+ *   or models cannot skew the state.
+ * - `"zscore"`: standardize each embedding's components to zero mean and
+ *   unit standard deviation.
+ */
+export type NormalizationPolicy = "none" | "l2" | "zscore";
+
+/**
+ * Direction of travel of the health score, reported in
+ * {@link Snapshot.healthTrend}.
+ */
+export type HealthTrend = "improving" | "degrading" | "flat";
+
+/**
+ * Tiered severity of an update's threshold crossing:
+ *
+ * - `"none"`: the drift threshold was not crossed.
+ * - `"warning"`: `driftThreshold` was crossed, `criticalThreshold` was not.
+ * - `"critical"`: {@link SemanticStateEngineConfig.criticalThreshold} was
+ *   crossed too.
+ */
+export type DriftLevel = "none" | "warning" | "critical";
+
+/** Number of recent history entries the health trend is fitted over. */
+const HEALTH_TREND_WINDOW = 10;
+
+/** Health change per minute below which the trend counts as flat. */
+const HEALTH_TREND_FLAT_SLOPE = 0.01;
+
+/** Noise parameters for the `"kalman"` fusion strategy. */
+export interface KalmanConfig {
+  /**
+   * Variance added to every dimension per update — how fast the true
+   * semantic state is expected to move.
+   * @default 0.001
+   */
+  processNoise?: number;
+
+  /**
+   * Variance of each observed embedding dimension — how noisy individual
+   * embeddings are.
+   * @default 0.01
+   */
+  measurementNoise?: number;
+}
+
+const DEFAULT_KALMAN_PROCESS_NOISE = 0.001;
+const DEFAULT_KALMAN_MEASUREMENT_NOISE = 0.01;
+
+/**
+ * Auto-threshold mode: drift is reported when the drift score exceeds a
+ * running quantile of previously observed drift scores, estimated with the
+ * streaming P² algorithm. Replaces `driftThreshold` once enough drift scores
+ * have been seen.
+ */
+export interface AutoThresholdConfig {
+  /**
+   * Quantile of past drift scores used as the threshold, in (0, 1).
+   * @default 0.95
+   */
+  quantile?: number;
+
+  /**
+   * Number of drift scores observed before the quantile replaces the fixed
+   * `driftThreshold`.
+   * @default 20
+   */
+  minSamples?: number;
+}
+
+const DEFAULT_AUTO_THRESHOLD_QUANTILE = 0.95;
+const DEFAULT_AUTO_THRESHOLD_MIN_SAMPLES = 20;
+
+/**
+ * Outlier detection on individual embeddings: an update whose drift score
+ * lies more than `zScore` standard deviations above the mean of the recent
+ * drift scores is flagged as an outlier and, by default, not fused.
+ */
+export interface OutlierConfig {
+  /**
+   * Number of standard deviations above the rolling mean at which a drift
+   * score counts as an outlier.
+   * @default 3
+   */
+  zScore?: number;
+
+  /**
+   * Number of recent (non-outlier) drift scores the rolling mean and standard
+   * deviation are computed over.
+   * @default 50
+   */
+  window?: number;
+
+  /**
+   * Number of drift scores required before outliers are flagged.
+   * @default 10
+   */
+  minSamples?: number;
+
+  /**
+   * Whether outliers are rejected — left out of the state, the drift
+   * statistics and the update count — rather than only flagged.
+   * @default true
+   */
+  reject?: boolean;
+}
+
+/**
+ * Random projection of every incoming vector onto a fixed internal dimension,
+ * so embeddings of any size can be fused into the same state — e.g. across
+ * an embedding-model upgrade from 1536 to 3072 dimensions.
+ */
+export interface ProjectionConfig {
+  /** Internal dimension every embedding is projected onto. */
+  dimension: number;
+
+  /**
+   * Seed of the projection matrices. Engines that must produce comparable
+   * states (or restore each other's exports) need the same seed.
+   * @default 0
+   */
+  seed?: number;
+}
+
+/**
+ * Streaming clustering of incoming embeddings into recurring semantic
+ * "modes". See {@link SemanticStateEngineConfig.clustering}.
+ */
+export interface ClusteringConfig {
+  /**
+   * Maximum number of clusters (centroids) maintained.
+   * @default 8
+   */
+  k?: number;
+
+  /**
+   * Cosine similarity to the nearest centroid below which an embedding
+   * founds a new cluster, while fewer than `k` exist.
+   * @default 0.8
+   */
+  newClusterSimilarity?: number;
+}
+
+const DEFAULT_CLUSTER_K = 8;
+const DEFAULT_NEW_CLUSTER_SIMILARITY = 0.8;
+
+/** Keep the largest-magnitude components of the state vector. */
+export interface TopKCompressionConfig {
+  method: "topk";
+
+  /**
+   * Number of components kept.
+   * @default 16
+   */
+  k?: number;
+}
+
+/**
+ * Project the state vector onto the leading principal components of the
+ * incoming embeddings, learned online with {@link OnlinePCA}.
+ */
+export interface PcaCompressionConfig {
+  method: "pca";
+
+  /**
+   * Number of principal components (coordinates) kept.
+   * @default 2
+   */
+  components?: number;
+
+  /**
+   * Step size of the online PCA update, relative to the spread of the
+   * embeddings. Smaller values converge more slowly but more accurately.
+   * @default 0.02
+   */
+  learningRate?: number;
+}
+
+/**
+ * How {@link SemanticStateEngine.getSnapshotCompressed} compresses the state
+ * vector. See {@link SemanticStateEngineConfig.snapshotCompression}.
+ */
+export type SnapshotCompressionConfig =
+  | TopKCompressionConfig
+  | PcaCompressionConfig;
+
+/**
+ * Online 2D PCA of the incoming embeddings for plotting semantic
+ * trajectories. See {@link SemanticStateEngineConfig.pca2d}.
+ */
+export interface Pca2dConfig {
+  /**
+   * Step size of the online PCA update, relative to the spread of the
+   * embeddings. Smaller values converge more slowly but more accurately.
+   * @default 0.02
+   */
+  learningRate?: number;
+}
+
+const DEFAULT_COMPRESSION_K = 16;
+const DEFAULT_PCA_COMPONENTS = 2;
+const DEFAULT_PCA_LEARNING_RATE = 0.02;
+
+/**
+ * Periodic archiving of the state vector, searched by
+ * {@link SemanticStateEngine.findSimilarStates}. See
+ * {@link SemanticStateEngineConfig.stateArchive}.
+ */
+export interface StateArchiveConfig {
+  /**
+   * Archive the state after every `interval`-th update.
+   * @default 10
+   */
+  interval?: number;
+
+  /**
+   * Maximum number of archived states retained; the oldest is evicted first.
+   * @default 100
+   */
+  size?: number;
+}
+
+const DEFAULT_ARCHIVE_INTERVAL = 10;
+const DEFAULT_ARCHIVE_SIZE = 100;
+
+/**
+ * Retention of a bounded sample of the raw input embeddings, for offline
+ * analysis or recomputing a baseline. See
+ * {@link SemanticStateEngineConfig.embeddingSample}.
+ */
+export interface EmbeddingSampleConfig {
+  /**
+   * Maximum number of embeddings retained.
+   * @default 100
+   */
+  size?: number;
+
+  /**
+   * `"reservoir"` keeps a uniform random sample of every embedding fused
+   * since the last reset; `"stride"` keeps every `stride`-th one, evicting
+   * the oldest first.
+   * @default "reservoir"
+   */
+  method?: "reservoir" | "stride";
+
+  /**
+   * Keep every `stride`-th embedding under the `"stride"` method.
+   * @default 10
+   */
+  stride?: number;
+
+  /**
+   * Seed of the reservoir's random choices, so the sample is reproducible.
+   * @default 0
+   */
+  seed?: number;
+}
+
+const DEFAULT_SAMPLE_SIZE = 100;
+const DEFAULT_SAMPLE_STRIDE = 10;
+
+const DEFAULT_OUTLIER_Z_SCORE = 3;
+const DEFAULT_OUTLIER_WINDOW = 50;
+const DEFAULT_OUTLIER_MIN_SAMPLES = 10;
+
+/**
+ * Detection of abrupt changes in the magnitude of incoming embeddings — e.g.
+ * a silent upstream model swap that doubles every norm, which cosine drift
+ * cannot see. See {@link SemanticStateEngineConfig.inputShift}.
+ */
+export interface InputShiftConfig {
+  /**
+   * Factor by which an embedding's magnitude must exceed (or fall short of)
+   * the geometric mean of the recent magnitudes to count as a shift.
+   * @default 1.5
+   */
+  ratio?: number;
+
+  /**
+   * Number of recent magnitudes the baseline is computed over.
+   * @default 50
+   */
+  window?: number;
+
+  /**
+   * Number of magnitudes required before shifts are flagged.
+   * @default 10
+   */
+  minSamples?: number;
+}
+
+/**
+ * Comparison of each embedding with a time-decayed combination of the
+ * recent raw embeddings, alongside the EMA state. See
+ * {@link SemanticStateEngineConfig.historySimilarity}.
+ */
+export interface HistorySimilarityConfig {
+  /**
+   * Number of recent embeddings combined.
+   * @default 10
+   */
+  size?: number;
+
+  /**
+   * Age (ms) at which an embedding's weight in the combination halves.
+   * @default 60000
+   */
+  halfLifeMs?: number;
+}
+
+/**
+ * Several EMA states of the embeddings kept side by side at different α,
+ * with the fastest compared against the slowest. See
+ * {@link SemanticStateEngineConfig.horizons}.
+ */
+export interface HorizonConfig {
+  /**
+   * EMA α of each horizon, by name — at least two, not all equal. The
+   * horizon with the largest α is the fast one, the smallest the slow one.
+   * @default { fast: 0.5, medium: 0.1, slow: 0.02 }
+   */
+  alphas?: Record<string, number>;
+
+  /**
+   * Fast-vs-slow drift score at or above which
+   * {@link UpdateResult.regimeShift} is set. Defaults to the engine's live
+   * drift threshold as a drift score — 1 − `driftThreshold` for the fixed
+   * cosine threshold, or the current auto threshold.
+   */
+  threshold?: number;
+}
+
+const DEFAULT_HORIZON_ALPHAS: Record<string, number> = {
+  fast: 0.5,
+  medium: 0.1,
+  slow: 0.02,
+};
+
+const DEFAULT_HISTORY_SIMILARITY_SIZE = 10;
+const DEFAULT_HISTORY_SIMILARITY_HALF_LIFE_MS = 60_000;
+
+const DEFAULT_INPUT_SHIFT_RATIO = 1.5;
+const DEFAULT_INPUT_SHIFT_WINDOW = 50;
+const DEFAULT_INPUT_SHIFT_MIN_SAMPLES = 10;
+
+/** Number of preceding updates examined when classifying a drift. */
+const DRIFT_CLASSIFY_WINDOW = 10;
+
+/** Number of past concepts remembered for recurring-drift detection. */
+const MAX_PAST_CONCEPTS = 8;
+
+/**
+ * Cosine similarity to a past concept at or above which a drift counts as
+ * a return to that concept.
+ */
+const RECURRING_SIMILARITY = 0.9;
+
+/** Default number of entries retained in the update history. */
+const DEFAULT_HISTORY_SIZE = 100;
+
+/** Default number of recent embeddings averaged by the `"window"` drift baseline. */
+const DEFAULT_DRIFT_WINDOW = 10;
+
+/** Default number of checkpoints retained by {@link SemanticStateEngine.checkpoint}. */
+const DEFAULT_MAX_CHECKPOINTS = 10;
+
+/**
+ * What incoming embeddings are compared against when measuring drift.
+ *
+ * - `"state"`: the fused state vector.
+ * - `"window"`: the mean of the last `driftWindow` raw embeddings, which is
+ *   less sticky than the EMA state and unaffected by its zero-origin warm-up.
+ */
+export type DriftBaseline = "state" | "window";
+
+/**
+ * Metric used to compute the drift score between the drift baseline and an
+ * incoming embedding.
+ *
+ * - `"cosine"`: 1 − cosine similarity ∈ [0, 2]. Drift fires when the
+ *   similarity drops below `driftThreshold`.
+ * - `"euclidean"`: L2 distance. Sensitive to magnitude shifts.
+ * - `"manhattan"`: L1 distance.
+ * - `"angular"`: angle / π ∈ [0, 1].
+ * - `"mahalanobis"`: L2 distance with each dimension scaled by the running
+ *   variance of the incoming embeddings, so inherently noisy dimensions
+ *   count for less. Falls back to Euclidean until two embeddings are seen.
+ * - `"jensen-shannon"`: Jensen-Shannon divergence in bits ∈ [0, 1], for
+ *   embeddings that are probability distributions (softmax outputs, topic
+ *   mixtures).
+ * - `"kullback-leibler"`: KL(embedding ‖ baseline) in nats, likewise for
+ *   distributions. Unbounded and asymmetric.
+ *
+ * The two divergences scale both vectors to sum to 1 and reject embeddings
+ * with a negative component or no mass (`INVALID_DISTRIBUTION`), so leave
+ * {@link NormalizationPolicy} at `"none"` with them.
+ *
+ * For every metric other than `"cosine"`, `driftThreshold` is the maximum
+ * tolerated distance and drift fires when the score exceeds it.
+ */
+export type DriftMetric =
+  | "cosine"
+  | "euclidean"
+  | "manhattan"
+  | "angular"
+  | "mahalanobis"
+  | "jensen-shannon"
+  | "kullback-leibler";
+
+/** Floor applied to per-dimension variances by the `"mahalanobis"` metric. */
+const MIN_MAHALANOBIS_VARIANCE = 1e-6;
+
+/**
+ * Bounds for adaptive EMA. The effective α is interpolated linearly between
+ * `minAlpha` (no drift) and `maxAlpha` (drift score ≥ 1, i.e. orthogonal or
+ * worse), so the state reacts quickly to regime changes but stays smooth
+ * while the stream is stable.
+ */
+export interface AdaptiveAlphaConfig {
+  /** α applied when the incoming embedding matches the baseline. */
+  minAlpha: number;
+
+  /** α applied when the incoming embedding is orthogonal to the baseline. */
+  maxAlpha: number;
+}
 
 /**
  * Configuration for the SemanticStateEngine.
@@ -29,156 +717,4634 @@ export interface SemanticStateEngineConfig {
   /** EMA decay factor α ∈ (0, 1]. Higher values weight recent embeddings more. */
   alpha: number;
 
-  /** Minimum cosine similarity below which drift is detected and the callback fires. */
-  driftThreshold: number;
+  /**
+   * Minimum cosine similarity below which drift is detected and the callback
+   * fires. For non-cosine {@link driftMetric}s, the maximum tolerated drift
+   * score instead.
+   */
+  driftThreshold: number;
+
+  /**
+   * Embedding dimension the engine accepts. When set, the very first update
+   * (and every reference) is validated against it, instead of establishing
+   * whatever dimension arrives first — so a truncated first embedding is
+   * rejected rather than locking the engine to the wrong dimension. Cannot
+   * be combined with {@link projection}, which fixes the dimension itself.
+   */
+  dimension?: number;
+
+  /**
+   * Second, stricter threshold in the same units as `driftThreshold` (a lower
+   * similarity for `"cosine"`, a higher drift score otherwise). Updates that
+   * cross it report {@link UpdateResult.driftLevel} `"critical"` instead of
+   * `"warning"`. Disabled when unset.
+   */
+  criticalThreshold?: number;
+
+  /**
+   * Optional callback invoked when the incoming embedding drifts beyond the threshold.
+   * Fired *before* the EMA fusion is applied.
+   *
+   * @param vector    The incoming embedding that triggered the drift.
+   * @param driftScore Drift magnitude under the configured {@link driftMetric}
+   *                   (`1 − cosine_similarity` ∈ [0, 2] for cosine).
+   */
+  onDriftDetected?: (vector: number[], driftScore: number) => void;
+  /**
+   * Strategy used to fuse incoming embeddings into the state vector.
+   * Can be changed later with {@link SemanticStateEngine.setFusionStrategy}.
+   * @default "ema"
+   */
+  fusionStrategy?: FusionStrategy;
+
+  /**
+   * Number of recent embeddings fused by the `"sma"`, `"wma"` and `"median"`
+   * strategies.
+   * @default 10
+   */
+  fusionWindow?: number;
+
+  /**
+   * Reference that incoming embeddings are compared against for drift.
+   * @default "state"
+   */
+  driftBaseline?: DriftBaseline;
+
+  /**
+   * Number of recent embeddings averaged by the `"window"` drift baseline.
+   * @default 10
+   */
+  driftWindow?: number;
+
+  /**
+   * The embedding provider used to obtain embedding vectors asynchronously.
+   * Any object implementing `getEmbedding(text: string): Promise<Float32Array | Float64Array | number[]>`
+   * satisfies this interface — including `WorkerManager`, or a custom OpenAI / Ollama wrapper.
+   *
+   * Optional when embeddings are computed elsewhere and fed in through
+   * {@link SemanticStateEngine.updateEmbedding} (e.g. in a server-side
+   * ingestion pipeline); {@link SemanticStateEngine.update} then throws
+   * `NO_PROVIDER`.
+   */
+  provider?: EmbeddingProvider;
+
+  /**
+   * The name of the embedding model to use.
+   * Must match the modelName passed to the WorkerManager so the worker
+   * loads the correct model.
+   * @default "Xenova/all-MiniLM-L6-v2"
+   */
+  modelName?: string;
+
+  /**
+   * Enables adaptive EMA: α grows with drift magnitude within these bounds.
+   * Only affects the `"ema"` fusion strategy; the fixed `alpha` is still used
+   * for the baseline (first) update.
+   */
+  adaptiveAlpha?: AdaptiveAlphaConfig;
+
+  /**
+   * Metric used to compute drift scores.
+   * @default "cosine"
+   */
+  driftMetric?: DriftMetric;
+
+  /**
+   * Number of consecutive updates that must exceed the threshold before drift
+   * is reported. The streak restarts after each report.
+   * @default 1
+   */
+  driftConsecutive?: number;
+
+  /**
+   * Cool-down period (ms) after a drift report during which further drift is
+   * not reported. Measured against update timestamps.
+   * @default 0
+   */
+  driftCooldownMs?: number;
+
+  /**
+   * Smoothing factor β ∈ (0, 1] of an exponential moving average of the
+   * similarity and drift score. When set, the drift threshold (and
+   * `criticalThreshold`) is applied to the smoothed values instead of each
+   * update's own, so a single jittery update no longer trips detection:
+   * `smoothed = β · drift + (1 − β) · smoothed`, starting from the first
+   * measured drift. The smoothed score is reported in
+   * {@link UpdateResult.smoothedDriftScore}, and drift events record the
+   * smoothed values that crossed the threshold.
+   */
+  driftSmoothing?: number;
+
+  /**
+   * Maximum number of drift reports in any 60-second window (by update
+   * timestamp). Further drift in the window is suppressed — not reported,
+   * logged or passed to `onDriftDetected` — and counted by
+   * {@link SemanticStateEngine.getSuppressedDriftCount}. Unlimited when unset.
+   */
+  maxDriftEventsPerMinute?: number;
+
+  /**
+   * Maximum number of entries retained by {@link SemanticStateEngine.getHistory}.
+   * Set to 0 to disable history tracking.
+   * @default 100
+   */
+  historySize?: number;
+
+  /** Noise parameters for the `"kalman"` fusion strategy. */
+  kalman?: KalmanConfig;
+
+  /**
+   * Optional sequential change detector run over the stream of drift scores,
+   * reported separately from threshold drift as
+   * {@link UpdateResult.changePointDetected}.
+   */
+  changeDetector?: ChangeDetectorConfig;
+
+  /**
+   * Number of dimensions reported in {@link UpdateResult.attribution}, ranked
+   * by their contribution to the drift score. 0 disables attribution.
+   * @default 0
+   */
+  attributionTopK?: number;
+
+  /**
+   * Report the unit vector pointing from the drift baseline to each
+   * embedding as {@link UpdateResult.driftDirection}, e.g. to project it onto
+   * known concept axes. Off by default to spare the extra vector per update.
+   * @default false
+   */
+  driftDirection?: boolean;
+
+  /**
+   * Parameters of the healthScore formula. Can be changed later with
+   * {@link SemanticStateEngine.setHealthConfig}.
+   */
+  health?: HealthConfig;
+
+  /**
+   * Derive the drift threshold from the observed drift distribution instead
+   * of the fixed `driftThreshold`. See {@link AutoThresholdConfig}.
+   */
+  autoThreshold?: AutoThresholdConfig;
+
+  /**
+   * Half-life (ms) of the state vector between updates. When set, the state
+   * decays exponentially toward the zero vector while no updates arrive, so a
+   * stale engine gradually "forgets": snapshots report the decayed vector and
+   * the next update fuses into it. Unset disables decay.
+   */
+  stateHalfLifeMs?: number;
+
+  /**
+   * Number of initial updates during which drift detection is suppressed:
+   * embeddings are fused and drift is measured, but thresholds, change
+   * detectors and `onDriftDetected` are skipped. The default of 1 only
+   * exempts the update that establishes the baseline.
+   * @default 1
+   */
+  warmupUpdates?: number;
+
+  /**
+   * How the `"ema"` strategy initializes the state from the first embedding.
+   * @default "zero"
+   */
+  initMode?: InitMode;
+
+  /**
+   * How updates timestamped before the previous update are handled. See
+   * {@link TimestampPolicy}. Ignored with the `"monotonic"` clock.
+   * @default "accept"
+   */
+  timestampPolicy?: TimestampPolicy;
+
+  /**
+   * Source of update timestamps and of the current time used for health and
+   * snapshots. See {@link ClockMode}.
+   * @default "caller"
+   */
+  clock?: ClockMode;
+
+  /**
+   * How embeddings (and references) with `NaN` or infinite components are
+   * handled. See {@link NonFinitePolicy}.
+   * @default "error"
+   */
+  nonFinitePolicy?: NonFinitePolicy;
+
+  /**
+   * How much each {@link UpdateResult} carries. See {@link ResultDetail}.
+   * @default "full"
+   */
+  resultDetail?: ResultDetail;
+
+  /**
+   * What an update does with an embedding of a different dimension than the
+   * state's. See {@link DimensionChangePolicy}. `"reset"` cannot be combined
+   * with {@link dimension}.
+   * @default "error"
+   */
+  dimensionChangePolicy?: DimensionChangePolicy;
+
+  /**
+   * Label every reported drift as sudden, gradual, incremental or recurring
+   * in {@link UpdateResult.driftType}. See {@link DriftType}.
+   * @default false
+   */
+  classifyDrift?: boolean;
+
+  /**
+   * Flag (and by default reject) embeddings whose drift is anomalous
+   * relative to the recent drift scores. See {@link OutlierConfig}.
+   */
+  outliers?: OutlierConfig;
+
+  /**
+   * Number of recent updates summarized in {@link Snapshot.driftStats}.
+   * 0 disables the statistics.
+   * @default 0
+   */
+  statsWindow?: number;
+
+  /**
+   * Maximum number of entries retained by
+   * {@link SemanticStateEngine.getDriftEvents}. 0 disables the log.
+   * @default 100
+   */
+  driftEventLogSize?: number;
+
+  /**
+   * Project embeddings, references and queries of any dimension onto a fixed
+   * internal dimension before use, instead of rejecting a dimension change.
+   * See {@link ProjectionConfig}.
+   */
+  projection?: ProjectionConfig;
+
+  /**
+   * Cluster incoming embeddings with online k-means, reporting each update's
+   * cluster in {@link UpdateResult.cluster} and churn in
+   * {@link Snapshot.clusters}. See {@link ClusteringConfig}.
+   */
+  clustering?: ClusteringConfig;
+
+  /**
+   * Enables {@link SemanticStateEngine.getSnapshotCompressed}, which ships a
+   * compact representation of the state instead of the full vector. The
+   * `"pca"` method learns its components from every fused embedding. See
+   * {@link SnapshotCompressionConfig}.
+   */
+  snapshotCompression?: SnapshotCompressionConfig;
+
+  /**
+   * Learn the two leading principal components of the fused embeddings
+   * online, enabling {@link SemanticStateEngine.project2d} and
+   * {@link SemanticStateEngine.getState2d}. See {@link Pca2dConfig}.
+   */
+  pca2d?: Pca2dConfig;
+
+  /**
+   * Record every update into a trace that {@link SemanticStateEngine.replay}
+   * can reproduce exactly. See {@link SemanticStateEngine.getTrace}.
+   * @default false
+   */
+  recordTrace?: boolean;
+
+  /**
+   * Time (ms) without an update after which snapshots report the state as
+   * stale via {@link Snapshot.isStale}. Disabled when unset.
+   */
+  staleAfterMs?: number;
+
+  /**
+   * Normalize embeddings and references internally (after any projection).
+   * See {@link NormalizationPolicy}.
+   * @default "none"
+   */
+  normalizeInputs?: NormalizationPolicy;
+
+  /**
+   * Number of checkpoints retained by {@link SemanticStateEngine.checkpoint};
+   * taking one more discards the oldest.
+   * @default 10
+   */
+  maxCheckpoints?: number;
+
+  /**
+   * Archive the state periodically so
+   * {@link SemanticStateEngine.findSimilarStates} can answer "have we been in
+   * this semantic regime before?". Disabled when unset. See
+   * {@link StateArchiveConfig}.
+   */
+  stateArchive?: StateArchiveConfig;
+
+  /**
+   * Retain a sample of the raw input embeddings, as supplied (before
+   * projection, masking or normalization), returned by
+   * {@link SemanticStateEngine.getEmbeddingSample}. Disabled when unset. See
+   * {@link EmbeddingSampleConfig}.
+   */
+  embeddingSample?: EmbeddingSampleConfig;
+
+  /**
+   * Arbitrary string labels (e.g. tenant ID, model name) echoed into every
+   * {@link Snapshot} and {@link DriftEvent}, so output from many engines can
+   * be told apart without correlating it externally.
+   */
+  metadata?: Record<string, string>;
+
+  /**
+   * Structured logging hook, called with an {@link EngineLogRecord} after
+   * every update (`"update"`, level `"debug"`), whenever drift is reported
+   * (`"drift_detected"`, `"warn"`), and when accumulated state is discarded
+   * (`"reset"`, `"info"`). Forward records to pino, winston or a console
+   * sink; nothing is built when this is unset.
+   */
+  logger?: (record: EngineLogRecord) => void;
+
+  /**
+   * Flag abrupt changes in the magnitude of incoming embeddings via
+   * {@link UpdateResult.inputShift}. Disabled when unset. See
+   * {@link InputShiftConfig}.
+   */
+  inputShift?: InputShiftConfig;
+
+  /**
+   * Also compare every embedding with a time-decay-weighted combination of
+   * the last `size` embeddings, reported as
+   * {@link UpdateResult.historySimilarity}. Unlike the EMA, the combination
+   * forgets an old regime within `size` updates, so it is more faithful
+   * shortly after a transition. Disabled when unset.
+   */
+  historySimilarity?: HistorySimilarityConfig;
+
+  /**
+   * Also maintain EMA states at several time horizons and report the drift
+   * of the fastest against the slowest as
+   * {@link UpdateResult.horizonDriftScore}. Comparing two smoothed states
+   * rather than a raw embedding against one ignores single noisy inputs and
+   * picks up sustained regime shifts. Disabled when unset.
+   */
+  horizons?: HorizonConfig;
+
+  /**
+   * Number of updates required before health is reported. Until then,
+   * snapshots carry `insufficientData: true` and the summary
+   * `"insufficient data"`, so a brand-new engine does not look perfectly
+   * healthy on a dashboard.
+   * @default 0
+   */
+  minUpdatesForHealth?: number;
+
+  /**
+   * Precision at which the state vector, the fusion/drift window and the
+   * state archive are retained. `"f32"`, `"f16"` and `"int8"` cut their
+   * memory by 2×, 4× and 8× against the default double precision; vectors
+   * are dequantized on the fly, so all math still runs in double precision,
+   * but every stored value carries the rounding error of its format.
+   * @default "f64"
+   */
+  storagePrecision?: StoragePrecision;
+}
+
+/**
+ * A point-in-time snapshot of the current semantic state.
+ */
+export interface Snapshot {
+  /** The current EMA state vector. */
+  vector: number[];
+
+  /** Reliability indicator in [0, 1]. Degrades with age and high drift. */
+  healthScore: number;
+
+  /** Unix timestamp (ms) of the last state update. */
+  timestamp: number;
+
+  /** Drift score of the most recent update (0 before the first). */
+  lastDriftScore: number;
+
+  /** Similarity reported by the most recent update (1 before the first). */
+  lastSimilarity: number;
+
+  /** Number of updates fused into the state so far. */
+  updateCount: number;
+
+  /** Embedding dimension the engine expects, or `null` until established. */
+  dimension: number | null;
+
+  /**
+   * Human-readable description of the current state quality: `"stable"`,
+   * `"drifting"` or `"volatile"`, or `"insufficient data"` before
+   * {@link SemanticStateEngineConfig.minUpdatesForHealth} updates.
+   */
+  semanticSummary: string;
+
+  /**
+   * `true` while fewer than
+   * {@link SemanticStateEngineConfig.minUpdatesForHealth} updates have been
+   * fused; `healthScore` is not meaningful yet. Absent otherwise.
+   */
+  insufficientData?: boolean;
+
+  /**
+   * Mean per-dimension variance of the state estimate. Only present while
+   * the `"kalman"` fusion strategy is tracking variance; lower values mean
+   * the state is well observed.
+   */
+  uncertainty?: number;
+
+  /**
+   * Cosine similarity between the state vector and each reference registered
+   * with {@link SemanticStateEngine.addReference}, keyed by name.
+   */
+  referenceSimilarities?: Record<string, number>;
+
+  /**
+   * Rolling statistics over the last
+   * {@link SemanticStateEngineConfig.statsWindow} updates. Present when the
+   * window is enabled and at least one drift score has been measured.
+   */
+  driftStats?: DriftStats;
+
+  /**
+   * Cluster sizes and churn. Present when
+   * {@link SemanticStateEngineConfig.clustering} is configured and at least
+   * one embedding has been clustered.
+   */
+  clusters?: ClusterStats;
+
+  /**
+   * Whether health is improving, degrading or flat over the last updates.
+   * Present once the history holds at least two updates.
+   */
+  healthTrend?: HealthTrend;
+
+  /**
+   * Fitted change in post-update health per minute over the last updates
+   * (the age penalty is not included). Present alongside `healthTrend`.
+   */
+  healthSlope?: number;
+
+  /**
+   * Rate of change of the drift score per minute at the latest update,
+   * from a least-squares parabola over the last updates. Present alongside
+   * `healthTrend`.
+   */
+  driftVelocity?: number;
+
+  /**
+   * Second derivative of the drift score per minute², from the same fit.
+   * Positive when drift is accelerating, which can warrant an alert before
+   * a steady drift of the same magnitude would. Present once the history
+   * holds at least three updates.
+   */
+  driftAcceleration?: number;
+
+  /**
+   * Whether no update has arrived within
+   * {@link SemanticStateEngineConfig.staleAfterMs}. Present when that
+   * option is set.
+   */
+  isStale?: boolean;
+
+  /** How long (ms) the state has been stale. Present when `isStale` is true. */
+  staleForMs?: number;
+
+  /**
+   * Spread of the incoming embeddings around their running mean. Present once
+   * two embeddings have been fused. High variance with low drift means a
+   * noisy but centred stream rather than a calm one.
+   */
+  embeddingStats?: EmbeddingStats;
+
+  /**
+   * Angle (radians) the state vector turned through per minute between the
+   * last two updates — how fast the state itself is rotating, as opposed to
+   * how far inputs are from it. Present once two updates with distinct
+   * timestamps have been fused.
+   */
+  angularVelocity?: number;
+
+  /**
+   * Total angle (radians) the state vector has turned through, summed over
+   * every update since the baseline was established. Present once two
+   * updates have been fused.
+   */
+  cumulativeRotation?: number;
+
+  /**
+   * `cumulativeRotation` per minute since the baseline was established.
+   * Present alongside `cumulativeRotation` once time has passed.
+   */
+  meanAngularVelocity?: number;
+
+  /** The engine's {@link SemanticStateEngineConfig.metadata}, when configured. */
+  metadata?: Record<string, string>;
+}
+
+/** Running dispersion of the incoming embeddings, computed with Welford's algorithm. */
+export interface EmbeddingStats {
+  /** Number of embeddings seen. */
+  count: number;
+
+  /** Mean per-dimension sample variance. */
+  variance: number;
+
+  /** Square root of `variance`. */
+  stdDev: number;
+
+  /**
+   * Shannon entropy of how the variance is distributed across dimensions,
+   * normalized to [0, 1]: 1 when every dimension varies equally, 0 when a
+   * single dimension carries all of it (or nothing varies).
+   */
+  entropy: number;
+}
+
+/** Cluster sizes and churn, reported in {@link Snapshot.clusters}. */
+export interface ClusterStats {
+  /** Number of clusters founded so far. */
+  count: number;
+
+  /** Number of embeddings assigned to each cluster, in founding order. */
+  sizes: number[];
+
+  /** Cluster of the most recent embedding. */
+  current: number;
+
+  /** Number of times consecutive embeddings fell into different clusters. */
+  switches: number;
+
+  /** `switches` divided by the number of consecutive pairs; 0 before the second embedding. */
+  churnRate: number;
+}
+
+/** Rolling aggregates of recent drift scores, reported in {@link Snapshot.driftStats}. */
+export interface DriftStats {
+  /** Number of drift scores in the window. */
+  count: number;
+
+  /** Mean drift score. */
+  mean: number;
+
+  /** Population variance of the drift scores. */
+  variance: number;
+
+  /** Smallest drift score. */
+  min: number;
+
+  /** Largest drift score. */
+  max: number;
+
+  /**
+   * Update rate over the window, from the first to the last update's
+   * timestamp. 0 until the window spans a non-zero time.
+   */
+  updatesPerMinute: number;
+}
+
+/**
+ * A {@link Snapshot} whose state vector is a `Float32Array`, as returned by
+ * {@link SemanticStateEngine.getSnapshotRaw}. The typed array is a copy and
+ * its buffer can be transferred to another thread without cloning.
+ */
+export type RawSnapshot = Omit<Snapshot, "vector"> & { vector: Float32Array };
+
+/**
+ * A {@link Snapshot} whose state vector is a `Float64Array`, as returned by
+ * {@link SemanticStateEngine.getSnapshotRaw64}. Carries the state at the full
+ * double precision it is accumulated in.
+ */
+export type RawSnapshot64 = Omit<Snapshot, "vector"> & { vector: Float64Array };
+
+/**
+ * The parts of a {@link Snapshot} (plain or raw) that
+ * {@link SemanticStateEngine.fromSnapshot} restores.
+ */
+export interface RestorableSnapshot {
+  /** The state vector. */
+  vector: ArrayLike<number>;
+
+  /** Unix timestamp (ms) of the last state update. */
+  timestamp: number;
+
+  /** Number of updates fused into the state. */
+  updateCount: number;
+
+  /** Drift score of the most recent update. Defaults to 0. */
+  lastDriftScore?: number;
+
+  /** Similarity reported by the most recent update. Defaults to 1. */
+  lastSimilarity?: number;
+}
+
+/**
+ * A state vector compressed by {@link SemanticStateEngine.getSnapshotCompressed}.
+ * `dimension` is that of the full state.
+ *
+ * - `"topk"`: the largest-magnitude components, in index order; usable as a
+ *   {@link SparseVector}.
+ * - `"pca"`: coordinates along the learned principal components, strongest
+ *   first, relative to the mean of the embeddings seen.
+ */
+export type CompressedVector =
+  | { method: "topk"; indices: number[]; values: number[]; dimension: number }
+  | { method: "pca"; coordinates: number[]; dimension: number };
+
+/** A {@link Snapshot} carrying a {@link CompressedVector} instead of the state vector. */
+export type CompressedSnapshot = Omit<Snapshot, "vector"> & {
+  compressed: CompressedVector;
+};
+
+/**
+ * One entry of the engine's bounded update history.
+ */
+export interface HistoryEntry {
+  /** Unix timestamp (ms) of the update. */
+  timestamp: number;
+
+  /** Drift score of the update. */
+  driftScore: number;
+
+  /** healthScore immediately after the update (no age penalty). */
+  healthScore: number;
+}
+
+/**
+ * One entry of the engine's bounded drift event log, recorded whenever drift
+ * is reported.
+ */
+export interface DriftEvent {
+  /** Unix timestamp (ms) of the update that reported drift. */
+  timestamp: number;
+
+  /** Drift score of the update. */
+  driftScore: number;
+
+  /** Cosine similarity between the embedding and the drift baseline. */
+  similarity: number;
+
+  /** Metric the drift score was measured with. */
+  metric: DriftMetric;
+
+  /**
+   * Threshold that was crossed: a minimum similarity for the fixed `"cosine"`
+   * threshold, otherwise a maximum drift score.
+   */
+  threshold: number;
+
+  /** Human-readable explanation, e.g. `"similarity 0.42 < 0.75 (cosine)"`. */
+  reason: string;
+
+  /**
+   * Severity of the crossing (see {@link DriftLevel}). Absent from events
+   * recorded by releases before it was introduced.
+   */
+  driftLevel?: DriftLevel;
+
+  /** Kind of drift, when {@link SemanticStateEngineConfig.classifyDrift} is enabled. */
+  driftType?: DriftType;
+
+  /**
+   * `true` for drift detected inside a maintenance window set with
+   * {@link SemanticStateEngine.suppressUntil}: recorded, but not reported.
+   */
+  suppressed?: boolean;
+
+  /** The engine's {@link SemanticStateEngineConfig.metadata}, when configured. */
+  metadata?: Record<string, string>;
+}
+
+/** Severity of an {@link EngineLogRecord}. */
+export type EngineLogLevel = "debug" | "info" | "warn";
+
+/** One structured log record passed to {@link SemanticStateEngineConfig.logger}. */
+export interface EngineLogRecord {
+  /**
+   * `"debug"` for updates, `"warn"` for drift (`"info"` inside a
+   * {@link SemanticStateEngine.suppressUntil} window), `"info"` for resets.
+   */
+  level: EngineLogLevel;
+
+  /** What happened. */
+  event: "update" | "drift_detected" | "reset";
+
+  /** Unix timestamp (ms) of the update, or the wall-clock time of a reset. */
+  timestamp: number;
+
+  /**
+   * Event-specific values: the update's scores, alpha and latency for
+   * `"update"`; the {@link DriftEvent} fields for `"drift_detected"`; and
+   * the `reason` (`"reset"`, `"resetBaseline"` — also used by
+   * {@link SemanticStateEngine.seedState} — or `"dimensionChange"`) for
+   * `"reset"`.
+   */
+  fields: Record<string, unknown>;
+
+  /** The engine's {@link SemanticStateEngineConfig.metadata}, when configured. */
+  metadata?: Record<string, string>;
+}
+
+/**
+ * How an embedding compares with the current state, as returned by
+ * {@link SemanticStateEngine.evaluate} without fusing it.
+ */
+export interface DriftEvaluation {
+  /** Cosine similarity between the embedding and the drift baseline (1 before the first update). */
+  similarity: number;
+
+  /** Drift magnitude under the configured {@link DriftMetric}. */
+  driftScore: number;
+
+  /** Whether fusing the embedding now would cross the drift threshold. */
+  thresholdExceeded: boolean;
+
+  /** Severity of that crossing. See {@link DriftLevel}. */
+  driftLevel: DriftLevel;
+
+  /** Whether the engine is still warming up, so the threshold is not applied. */
+  warmingUp: boolean;
+}
+
+/**
+ * The outcome of fusing a single embedding into the state.
+ */
+export interface UpdateResult {
+  /**
+   * The incoming embedding that was fused. Omitted unless
+   * {@link SemanticStateEngineConfig.resultDetail} is `"full"`.
+   */
+  vector?: number[];
+
+  /** Cosine similarity between the embedding and the drift baseline (1 on the first update). */
+  similarity: number;
+
+  /**
+   * Drift magnitude under the configured {@link DriftMetric}
+   * (1 − similarity ∈ [0, 2] for the default cosine metric).
+   */
+  driftScore: number;
+
+  /**
+   * Relative change in L2 norm from the drift baseline to the embedding,
+   * ‖E‖ / ‖B‖ − 1: positive when the embedding is longer. Reported next to
+   * the directional `driftScore`, which ignores length. 0 on the first
+   * update and against a zero baseline; always near 0 with `"l2"`
+   * {@link SemanticStateEngineConfig.normalizeInputs}.
+   */
+  magnitudeDrift: number;
+
+  /**
+   * Whether drift was reported for this update (and `onDriftDetected` fired),
+   * after applying {@link SemanticStateEngineConfig.driftConsecutive} and
+   * {@link SemanticStateEngineConfig.driftCooldownMs}.
+   */
+  driftDetected: boolean;
+
+  /**
+   * Whether this update on its own crossed the drift threshold — or, with
+   * {@link SemanticStateEngineConfig.driftSmoothing}, whether the smoothed
+   * drift did.
+   */
+  thresholdExceeded: boolean;
+
+  /**
+   * Exponential moving average of the drift score, including this update.
+   * Present when {@link SemanticStateEngineConfig.driftSmoothing} is set and
+   * a baseline exists.
+   */
+  smoothedDriftScore?: number;
+
+  /**
+   * Whether the embedding's magnitude departed abruptly from the recent
+   * magnitudes (before any normalization). The magnitude baseline restarts
+   * from this embedding, so a sustained change is flagged once. Always
+   * `false` without {@link SemanticStateEngineConfig.inputShift}.
+   */
+  inputShift: boolean;
+
+  /**
+   * Cosine similarity between the embedding and the decay-weighted
+   * combination of the preceding embeddings, reported next to `similarity`.
+   * Present when {@link SemanticStateEngineConfig.historySimilarity} is
+   * configured and an earlier embedding exists.
+   */
+  historySimilarity?: number;
+
+  /**
+   * Cosine drift score (1 − similarity) between the fastest and the slowest
+   * {@link SemanticStateEngineConfig.horizons} state after this update.
+   * Present when horizons are configured and the update was fused.
+   */
+  horizonDriftScore?: number;
+
+  /**
+   * Whether `horizonDriftScore` reached the horizon threshold; never set
+   * during warm-up. Present alongside `horizonDriftScore`.
+   */
+  regimeShift?: boolean;
+
+  /**
+   * Present when the embedding's dimension differed from the state's and
+   * {@link SemanticStateEngineConfig.dimensionChangePolicy} handled it.
+   */
+  dimensionChange?: DimensionChange;
+
+  /**
+   * Severity of this update's threshold crossing; `"none"` whenever
+   * `thresholdExceeded` is false. See {@link DriftLevel}.
+   */
+  driftLevel: DriftLevel;
+
+  /**
+   * Whether the configured {@link SemanticStateEngineConfig.changeDetector}
+   * flagged a change point at this update. Always `false` without one.
+   */
+  changePointDetected: boolean;
+
+  /** EMA α applied to this update (varies when adaptive α is enabled). */
+  alpha: number;
+
+  /**
+   * Whether this update fell within the warm-up period, during which drift
+   * is measured but never reported. See
+   * {@link SemanticStateEngineConfig.warmupUpdates}.
+   */
+  warmingUp: boolean;
+
+  /**
+   * The dimensions contributing most to the drift score, largest first.
+   * Present when {@link SemanticStateEngineConfig.attributionTopK} is
+   * positive, a baseline exists and `resultDetail` is `"full"`.
+   */
+  attribution?: DimensionContribution[];
+
+  /**
+   * Unit vector from the drift baseline towards the embedding (all zeros if
+   * they coincide): the direction, rather than the amount, of the drift.
+   * Present when {@link SemanticStateEngineConfig.driftDirection} is enabled,
+   * a baseline exists and `resultDetail` is `"full"`.
+   */
+  driftDirection?: number[];
+
+  /**
+   * Cosine similarity between the updated state vector and each registered
+   * reference, keyed by name. Present when references are registered.
+   */
+  referenceSimilarities?: Record<string, number>;
+
+  /**
+   * The registered anchor most similar to the embedding (not the state).
+   * Present when anchors are registered with
+   * {@link SemanticStateEngine.addAnchor}.
+   */
+  anchor?: AnchorMatch;
+
+  /**
+   * What kind of drift was detected. Present when `driftDetected` is true
+   * and {@link SemanticStateEngineConfig.classifyDrift} is enabled.
+   */
+  driftType?: DriftType;
+
+  /**
+   * `true` when drift would have been reported but was suppressed by
+   * {@link SemanticStateEngineConfig.maxDriftEventsPerMinute} or a
+   * {@link SemanticStateEngine.suppressUntil} maintenance window.
+   */
+  driftSuppressed?: boolean;
+
+  /** Source the embedding was tagged with via {@link SemanticStateEngine.updateFrom}. */
+  source?: string;
+
+  /**
+   * Cosine similarity between this source's state and every other source's
+   * state, keyed by source. Present for {@link SemanticStateEngine.updateFrom}
+   * once another source has been seen.
+   */
+  sourceSimilarities?: Record<string, number>;
+
+  /**
+   * Index of the cluster the embedding was assigned to. Present when
+   * {@link SemanticStateEngineConfig.clustering} is configured and the
+   * embedding was not rejected.
+   */
+  cluster?: number;
+
+  /**
+   * Whether the embedding was flagged as an outlier. Always `false` unless
+   * {@link SemanticStateEngineConfig.outliers} is configured.
+   */
+  outlier: boolean;
+
+  /**
+   * Whether the embedding was rejected as an outlier and left out of the
+   * state. A rejected update never reports drift and has an `alpha` of 0.
+   */
+  rejected: boolean;
+
+  /** Unix timestamp (ms) recorded for this update. */
+  timestamp: number;
+}
+
+/** The best-matching concept anchor of an update. See {@link UpdateResult.anchor}. */
+export interface AnchorMatch {
+  /** Label the anchor was registered under. */
+  label: string;
+
+  /** Cosine similarity between the embedding and the anchor. */
+  similarity: number;
+}
+
+/** A state vector archived by {@link SemanticStateEngineConfig.stateArchive}. */
+export interface ArchivedState {
+  /** Unix timestamp (ms) of the update after which the state was archived. */
+  timestamp: number;
+
+  /** Number of updates fused into the state at that point. */
+  updateCount: number;
+
+  /** The state vector at that point. */
+  vector: number[];
+}
+
+/** An input embedding retained by {@link SemanticStateEngineConfig.embeddingSample}. */
+export interface SampledEmbedding {
+  /** Unix timestamp (ms) of the update. */
+  timestamp: number;
+
+  /** Position of the update among those sampled from, starting at 1. */
+  index: number;
+
+  /** The embedding as supplied. */
+  vector: number[];
+}
+
+/** An archived state matched by {@link SemanticStateEngine.findSimilarStates}. */
+export interface SimilarState extends ArchivedState {
+  /** Cosine similarity between the queried embedding and the archived state. */
+  similarity: number;
+}
+
+/**
+ * The full internal state of a {@link SemanticStateEngine}, as returned by
+ * {@link SemanticStateEngine.exportState}.
+ *
+ * Contains only plain numbers and arrays, so it survives `JSON.stringify` and
+ * structured cloning (e.g. storing in IndexedDB) unchanged.
+ */
+export interface EngineState {
+  /**
+   * Layout version, used by `importState` to migrate older states. Absent in
+   * states exported before versioning.
+   */
+  version?: number;
+
+  /** The current EMA state vector. */
+  stateVector: number[];
+
+  /** EMA decay factor α in effect when the state was exported. */
+  alpha: number;
+
+  /** Drift threshold in effect when the state was exported. */
+  driftThreshold: number;
+
+  /** Number of updates fused into the state so far. */
+  updateCount: number;
+
+  /** Drift magnitude of the most recent update. */
+  lastDrift: number;
+
+  /** Similarity reported by the most recent update. */
+  lastSimilarity?: number;
+
+  /** Unix timestamp (ms) of the last state update. */
+  lastUpdatedAt: number;
+
+  /** Fusion strategy in effect when the state was exported. */
+  fusionStrategy?: FusionStrategy;
+
+  /** Recent embeddings retained for windowed fusion, oldest first. */
+  recentEmbeddings?: number[][];
+
+  /** Consecutive threshold exceedances since the last drift report. */
+  driftStreak?: number;
+
+  /** Timestamp (ms) until which drift reports are in cool-down. */
+  driftCooldownUntil?: number;
+
+  /** Timestamp (ms) until which drift is suppressed by {@link SemanticStateEngine.suppressUntil}. */
+  suppressUntil?: number;
+
+  /** Update history, oldest first. */
+  history?: HistoryEntry[];
+
+  /** Per-dimension variance tracked by the `"kalman"` fusion strategy. */
+  kalmanVariance?: number[] | null;
+
+  /** Named reference vectors registered with `addReference`. */
+  references?: Record<string, number[]>;
+
+  /** Labeled concept anchors registered with `addAnchor`. */
+  anchors?: Record<string, number[]>;
+
+  /** Drift event log, oldest first. */
+  driftEvents?: DriftEvent[];
+
+  /** Per-source state vectors maintained by `updateFrom`. */
+  sources?: Record<string, number[]>;
+
+  /** EMA state of each configured time horizon. */
+  horizons?: Record<string, number[]>;
+
+  /** States archived by the `stateArchive` option, oldest first. */
+  archive?: ArchivedState[];
+
+  /**
+   * Embeddings retained by the `embeddingSample` option, oldest first, and
+   * the number of embeddings sampled from.
+   */
+  embeddingSample?: { seen: number; entries: SampledEmbedding[] };
+
+  /**
+   * Rotation of the state vector since the baseline: total angle (radians),
+   * timestamp (ms) of the baseline, the latest angular velocity (radians
+   * per minute, `null` until known) and the number of rotation steps.
+   */
+  rotation?: {
+    total: number;
+    since: number;
+    velocity: number | null;
+    steps: number;
+  };
+}
+
+/** One recorded update of an {@link UpdateTrace}. */
+export interface TraceEntry {
+  /** The embedding as supplied (before any projection). */
+  embedding: number[];
+
+  /** Unix timestamp (ms) the update was applied with. */
+  timestamp: number;
+
+  /** Weight passed to `updateWeighted`, if not 1. */
+  weight?: number;
+
+  /** Threshold passed to `updateWithThreshold`, if any. */
+  threshold?: number;
+
+  /** Source passed to `updateFrom`, if any. */
+  source?: string;
+}
+
+/**
+ * Every update fused since recording started, together with the state it
+ * started from, as returned by {@link SemanticStateEngine.getTrace}. Plain
+ * data, so it can be stored as JSON and replayed later in a test.
+ */
+export interface UpdateTrace {
+  /** The engine state when recording started. */
+  initialState: EngineState;
+
+  /** The recorded updates, oldest first. */
+  entries: TraceEntry[];
+}
+
+/**
+ * Performance and memory telemetry, as returned by
+ * {@link SemanticStateEngine.getMetrics}.
+ */
+export interface EngineMetrics {
+  /** Number of updates timed. */
+  updates: number;
+
+  /** Duration of the most recent update (µs). */
+  lastUpdateUs: number;
+
+  /** Mean update duration (µs). */
+  meanUpdateUs: number;
+
+  /** Slowest update (µs). */
+  maxUpdateUs: number;
+
+  /**
+   * Estimated bytes held by the state vector and every other retained
+   * vector (fusion window, references, sources, centroids, …).
+   */
+  stateBytes: number;
+
+  /** Largest `stateBytes` observed after an update. */
+  peakStateBytes: number;
+
+  /** Estimated bytes held by the history, drift event log and update trace. */
+  historyBytes: number;
+}
+
+/** Size assumed for every stored number in {@link EngineMetrics} estimates. */
+const BYTES_PER_NUMBER = 8;
+
+/** A retained vector: a plain array at `"f64"`, packed otherwise. */
+type StoredVector = number[] | PackedVector;
+
+/** Returns the values of a retained vector (the array itself at `"f64"`). */
+function unpack(v: StoredVector): number[] {
+  return v instanceof PackedVector ? v.toArray() : v;
+}
+
+/** Estimated bytes held by a retained vector. */
+function storedBytes(v: StoredVector): number {
+  return v instanceof PackedVector ? v.byteLength : v.length * BYTES_PER_NUMBER;
+}
+
+/** Zero-pads or truncates `v` to `dimension` components. */
+function fitDimension(v: number[], dimension: number): number[] {
+  return Array.from({ length: dimension }, (_, i) => v[i] ?? 0);
+}
+
+/**
+ * SemanticStateEngine tracks the implicit semantic intent of an event stream
+ * using Exponential Moving Average (EMA) vector fusion.
+ *
+ * It fires an optional drift callback when incoming embeddings diverge
+ * significantly from the current state, and exposes a healthScore that
+ * degrades with both age and volatility.
+ */
+export class SemanticStateEngine {
+  private alpha: number;
+  private driftThreshold: number;
+  private readonly criticalThreshold?: number;
+  private readonly dimension?: number;
+  private readonly metadata?: Record<string, string>;
+  private readonly logger?: (record: EngineLogRecord) => void;
+  private readonly inputShift?: Required<InputShiftConfig>;
+  private magnitudeLog: number[] = [];
+  private readonly historySimilarity?: Required<HistorySimilarityConfig>;
+  private decayWindow: { timestamp: number; vector: number[] }[] = [];
+  private readonly horizons?: {
+    alphas: Record<string, number>;
+    fast: string;
+    slow: string;
+    threshold?: number;
+  };
+  private horizonStates = new Map<string, number[]>();
+  private readonly minUpdatesForHealth: number;
+  private readonly onDriftDetected?: (
+    vector: number[],
+    driftScore: number,
+  ) => void;
+  private readonly provider?: EmbeddingProvider;
+  private readonly fusionWindow: number;
+  private readonly driftBaseline: DriftBaseline;
+  private readonly driftWindow: number;
+  private fusionStrategy: FusionStrategy;
+  private readonly adaptiveAlpha?: AdaptiveAlphaConfig;
+  private readonly driftMetric: DriftMetric;
+  private readonly driftConsecutive: number;
+  private readonly driftCooldownMs: number;
+  private readonly driftSmoothing?: number;
+  private readonly historySize: number;
+  private readonly processNoise: number;
+  private readonly measurementNoise: number;
+  private readonly changeDetector?: ChangeDetector;
+  private readonly attributionTopK: number;
+  private readonly driftDirection: boolean;
+  private health: ResolvedHealthConfig;
+  private readonly driftQuantile?: P2Quantile;
+  private readonly autoThresholdMinSamples: number;
+  private readonly stateHalfLifeMs?: number;
+  private readonly warmupUpdates: number;
+  private readonly initMode: InitMode;
+  private readonly classify: boolean;
+  private readonly outliers?: Required<OutlierConfig>;
+  private readonly statsWindow: number;
+  private readonly driftEventLogSize: number;
+  private readonly projection?: RandomProjection;
+  private dimensionMask: number[] | null = null;
+  private readonly clusterer?: OnlineKMeans;
+  private readonly compressionK?: number;
+  private readonly pca?: OnlinePCA;
+  private readonly pca2d?: OnlinePCA;
+  private readonly embeddingStats = new RunningStats();
+  private readonly staleAfterMs?: number;
+  private readonly normalizeInputs: NormalizationPolicy;
+  private readonly maxDriftEventsPerMinute?: number;
+  private driftReportTimes: number[] = [];
+  private suppressedDriftCount = 0;
+  private suppressedUntil = 0;
+  private rotation: NonNullable<EngineState["rotation"]> = {
+    total: 0,
+    since: 0,
+    velocity: null,
+    steps: 0,
+  };
+  private readonly maxCheckpoints: number;
+  private checkpoints = new Map<number, EngineState>();
+  private nextCheckpointId = 1;
+  private readonly archiveInterval: number;
+  private readonly archiveSize: number;
+  private readonly sampling?: Required<EmbeddingSampleConfig> & {
+    random: () => number;
+  };
+  private sampleSeen = 0;
+  private sample: (Omit<SampledEmbedding, "vector"> & { vector: StoredVector })[] =
+    [];
+  private archive: (Omit<ArchivedState, "vector"> & { vector: StoredVector })[] =
+    [];
+  private readonly storagePrecision: StoragePrecision;
+  readonly modelName: string;
+  private readonly config: SemanticStateEngineConfig;
+  private readonly timestampPolicy: TimestampPolicy;
+  private readonly clock: ClockMode;
+  private readonly nonFinitePolicy: NonFinitePolicy;
+  private readonly resultDetail: ResultDetail;
+  private readonly dimensionChangePolicy: DimensionChangePolicy;
+  private monotonicNow = 0;
+
+  private packedState: StoredVector = [];
+  private lastUpdatedAt: number;
+  private lastDrift: number;
+  private lastSimilarity = 1;
+  private updateCount: number;
+  private recentEmbeddings: StoredVector[];
+  private driftStreak: number;
+  private driftCooldownUntil: number;
+  private smoothedDrift: { similarity: number; drift: number } | null = null;
+  private history: HistoryEntry[];
+  private kalmanVariance: number[] | null;
+  private references = new Map<string, number[]>();
+  private anchors = new Map<string, number[]>();
+  private sources = new Map<string, number[]>();
+  private embeddingBuffer: Float32Array | null = null;
+  private outputBuffer: Float32Array | null = null;
+  private trace: UpdateTrace | null = null;
+  private metrics = {
+    updates: 0,
+    totalUpdateUs: 0,
+    lastUpdateUs: 0,
+    maxUpdateUs: 0,
+    peakStateBytes: 0,
+  };
+  private driftWindowLog: DriftObservation[];
+  private pastConcepts: number[][];
+  private outlierWindow: number[];
+  private statsLog: { timestamp: number; driftScore: number }[];
+  private driftEvents: DriftEvent[];
+  private currentCluster: number | null;
+  private clusterAssignments: number;
+  private clusterSwitches: number;
+  private readonly listeners = new Set<() => void>();
+  private readonly driftListeners = new Set<(event: DriftEvent) => void>();
+
+  constructor(config: SemanticStateEngineConfig) {
+    if (!(config.alpha > 0 && config.alpha <= 1)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `Alpha must be in the range (0, 1], got ${config.alpha}`,
+        { alpha: config.alpha },
+      );
+    }
+    this.alpha = config.alpha;
+    this.driftThreshold = config.driftThreshold;
+    this.storagePrecision = config.storagePrecision ?? "f64";
+    if (config.criticalThreshold !== undefined) {
+      const metric = config.driftMetric ?? "cosine";
+      const stricter =
+        metric === "cosine"
+          ? config.criticalThreshold <= config.driftThreshold
+          : config.criticalThreshold >= config.driftThreshold;
+      if (!Number.isFinite(config.criticalThreshold) || !stricter) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `criticalThreshold must be a finite number at least as strict as driftThreshold ${config.driftThreshold}, got ${config.criticalThreshold}`,
+          {
+            criticalThreshold: config.criticalThreshold,
+            driftThreshold: config.driftThreshold,
+          },
+        );
+      }
+      this.criticalThreshold = config.criticalThreshold;
+    }
+    this.onDriftDetected = config.onDriftDetected;
+    this.logger = config.logger;
+    this.provider = config.provider;
+    this.fusionStrategy = config.fusionStrategy ?? "ema";
+    this.fusionWindow = config.fusionWindow ?? DEFAULT_FUSION_WINDOW;
+    if (!Number.isInteger(this.fusionWindow) || this.fusionWindow < 1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `fusionWindow must be a positive integer, got ${this.fusionWindow}`,
+        { fusionWindow: this.fusionWindow },
+      );
+    }
+    this.driftBaseline = config.driftBaseline ?? "state";
+    this.driftWindow = config.driftWindow ?? DEFAULT_DRIFT_WINDOW;
+    if (!Number.isInteger(this.driftWindow) || this.driftWindow < 1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `driftWindow must be a positive integer, got ${this.driftWindow}`,
+        { driftWindow: this.driftWindow },
+      );
+    }
+    this.modelName = config.modelName ?? "Xenova/all-MiniLM-L6-v2";
+    this.config = { ...config };
+    if (config.metadata !== undefined) {
+      this.metadata = { ...config.metadata };
+    }
+    if (config.adaptiveAlpha !== undefined) {
+      const { minAlpha, maxAlpha } = config.adaptiveAlpha;
+      if (minAlpha <= 0 || maxAlpha > 1 || minAlpha > maxAlpha) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `adaptiveAlpha bounds must satisfy 0 < minAlpha <= maxAlpha <= 1, got [${minAlpha}, ${maxAlpha}]`,
+          { minAlpha, maxAlpha },
+        );
+      }
+      this.adaptiveAlpha = { minAlpha, maxAlpha };
+    }
+    this.driftMetric = config.driftMetric ?? "cosine";
+    this.driftConsecutive = config.driftConsecutive ?? 1;
+    if (!Number.isInteger(this.driftConsecutive) || this.driftConsecutive < 1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `driftConsecutive must be a positive integer, got ${this.driftConsecutive}`,
+        { driftConsecutive: this.driftConsecutive },
+      );
+    }
+    this.driftCooldownMs = config.driftCooldownMs ?? 0;
+    if (!(this.driftCooldownMs >= 0)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `driftCooldownMs must be non-negative, got ${this.driftCooldownMs}`,
+        { driftCooldownMs: this.driftCooldownMs },
+      );
+    }
+    if (config.driftSmoothing !== undefined) {
+      if (!(config.driftSmoothing > 0 && config.driftSmoothing <= 1)) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `driftSmoothing must be in (0, 1], got ${config.driftSmoothing}`,
+          { driftSmoothing: config.driftSmoothing },
+        );
+      }
+      this.driftSmoothing = config.driftSmoothing;
+    }
+    this.historySize = config.historySize ?? DEFAULT_HISTORY_SIZE;
+    if (!Number.isInteger(this.historySize) || this.historySize < 0) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `historySize must be a non-negative integer, got ${this.historySize}`,
+        { historySize: this.historySize },
+      );
+    }
+    this.processNoise =
+      config.kalman?.processNoise ?? DEFAULT_KALMAN_PROCESS_NOISE;
+    this.measurementNoise =
+      config.kalman?.measurementNoise ?? DEFAULT_KALMAN_MEASUREMENT_NOISE;
+    if (!(this.processNoise >= 0) || !(this.measurementNoise > 0)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `kalman noise must satisfy processNoise >= 0 and measurementNoise > 0, got [${this.processNoise}, ${this.measurementNoise}]`,
+        {
+          processNoise: this.processNoise,
+          measurementNoise: this.measurementNoise,
+        },
+      );
+    }
+    if (config.changeDetector !== undefined) {
+      try {
+        this.changeDetector = createChangeDetector(config.changeDetector);
+      } catch (err) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          err instanceof Error ? err.message : String(err),
+          { changeDetector: config.changeDetector.type },
+        );
+      }
+    }
+    this.attributionTopK = config.attributionTopK ?? 0;
+    this.driftDirection = config.driftDirection ?? false;
+    if (!Number.isInteger(this.attributionTopK) || this.attributionTopK < 0) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `attributionTopK must be a non-negative integer, got ${this.attributionTopK}`,
+        { attributionTopK: this.attributionTopK },
+      );
+    }
+    this.health = resolveHealthConfig(config.health ?? {}, {
+      ageDecayRate: DEFAULT_AGE_DECAY_RATE,
+      driftWeight: DEFAULT_DRIFT_WEIGHT,
+      floor: 0,
+      ceiling: 1,
+      factors: {
+        age: { weight: DEFAULT_AGE_DECAY_RATE, curve: "linear" },
+        drift: { weight: DEFAULT_DRIFT_WEIGHT, curve: "linear" },
+        variance: { weight: 0, curve: "linear" },
+        updateRate: { weight: 0, curve: "linear" },
+      },
+    });
+    this.autoThresholdMinSamples =
+      config.autoThreshold?.minSamples ?? DEFAULT_AUTO_THRESHOLD_MIN_SAMPLES;
+    if (config.autoThreshold !== undefined) {
+      const quantile =
+        config.autoThreshold.quantile ?? DEFAULT_AUTO_THRESHOLD_QUANTILE;
+      if (!(quantile > 0 && quantile < 1)) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `autoThreshold.quantile must be in the range (0, 1), got ${quantile}`,
+          { quantile },
+        );
+      }
+      if (
+        !Number.isInteger(this.autoThresholdMinSamples) ||
+        this.autoThresholdMinSamples < 1
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `autoThreshold.minSamples must be a positive integer, got ${this.autoThresholdMinSamples}`,
+          { minSamples: this.autoThresholdMinSamples },
+        );
+      }
+      this.driftQuantile = new P2Quantile(quantile);
+    }
+    if (config.stateHalfLifeMs !== undefined) {
+      if (!(config.stateHalfLifeMs > 0)) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `stateHalfLifeMs must be positive, got ${config.stateHalfLifeMs}`,
+          { stateHalfLifeMs: config.stateHalfLifeMs },
+        );
+      }
+      this.stateHalfLifeMs = config.stateHalfLifeMs;
+    }
+    this.initMode = config.initMode ?? "zero";
+    this.timestampPolicy = config.timestampPolicy ?? "accept";
+    this.clock = config.clock ?? "caller";
+    this.nonFinitePolicy = config.nonFinitePolicy ?? "error";
+    this.resultDetail = config.resultDetail ?? "full";
+    this.classify = config.classifyDrift ?? false;
+    if (config.inputShift !== undefined) {
+      const inputShift = {
+        ratio: config.inputShift.ratio ?? DEFAULT_INPUT_SHIFT_RATIO,
+        window: config.inputShift.window ?? DEFAULT_INPUT_SHIFT_WINDOW,
+        minSamples:
+          config.inputShift.minSamples ?? DEFAULT_INPUT_SHIFT_MIN_SAMPLES,
+      };
+      if (
+        !(inputShift.ratio > 1) ||
+        !Number.isInteger(inputShift.window) ||
+        !Number.isInteger(inputShift.minSamples) ||
+        inputShift.minSamples < 1 ||
+        inputShift.minSamples > inputShift.window
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `inputShift must satisfy ratio > 1 and integer 1 <= minSamples <= window, got ratio=${inputShift.ratio}, minSamples=${inputShift.minSamples}, window=${inputShift.window}`,
+          { ...inputShift },
+        );
+      }
+      this.inputShift = inputShift;
+    }
+    if (config.historySimilarity !== undefined) {
+      const historySimilarity = {
+        size: config.historySimilarity.size ?? DEFAULT_HISTORY_SIMILARITY_SIZE,
+        halfLifeMs:
+          config.historySimilarity.halfLifeMs ??
+          DEFAULT_HISTORY_SIMILARITY_HALF_LIFE_MS,
+      };
+      if (
+        !Number.isInteger(historySimilarity.size) ||
+        historySimilarity.size < 1 ||
+        !(historySimilarity.halfLifeMs > 0)
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `historySimilarity must have a positive integer size and a positive halfLifeMs, got size=${historySimilarity.size}, halfLifeMs=${historySimilarity.halfLifeMs}`,
+          { ...historySimilarity },
+        );
+      }
+      this.historySimilarity = historySimilarity;
+    }
+    if (config.horizons !== undefined) {
+      const alphas = { ...(config.horizons.alphas ?? DEFAULT_HORIZON_ALPHAS) };
+      const entries = Object.entries(alphas).sort(([, a], [, b]) => b - a);
+      const threshold = config.horizons.threshold;
+      if (
+        entries.length < 2 ||
+        entries.some(([, a]) => !(a > 0 && a <= 1)) ||
+        entries[0]![1] === entries[entries.length - 1]![1] ||
+        (threshold !== undefined && !Number.isFinite(threshold))
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `horizons must have at least two alphas in (0, 1], not all equal, and a finite threshold, got alphas=${JSON.stringify(alphas)}, threshold=${threshold}`,
+          { alphas, threshold },
+        );
+      }
+      this.horizons = {
+        alphas,
+        fast: entries[0]![0],
+        slow: entries[entries.length - 1]![0],
+        threshold,
+      };
+    }
+    if (config.outliers !== undefined) {
+      const outliers = {
+        zScore: config.outliers.zScore ?? DEFAULT_OUTLIER_Z_SCORE,
+        window: config.outliers.window ?? DEFAULT_OUTLIER_WINDOW,
+        minSamples: config.outliers.minSamples ?? DEFAULT_OUTLIER_MIN_SAMPLES,
+        reject: config.outliers.reject ?? true,
+      };
+      if (
+        !(outliers.zScore > 0) ||
+        !Number.isInteger(outliers.window) ||
+        !Number.isInteger(outliers.minSamples) ||
+        outliers.minSamples < 2 ||
+        outliers.minSamples > outliers.window
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `outliers must satisfy zScore > 0 and integer 2 <= minSamples <= window, got zScore=${outliers.zScore}, minSamples=${outliers.minSamples}, window=${outliers.window}`,
+          {
+            zScore: outliers.zScore,
+            minSamples: outliers.minSamples,
+            window: outliers.window,
+          },
+        );
+      }
+      this.outliers = outliers;
+    }
+    this.statsWindow = config.statsWindow ?? 0;
+    if (!Number.isInteger(this.statsWindow) || this.statsWindow < 0) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `statsWindow must be a non-negative integer, got ${this.statsWindow}`,
+        { statsWindow: this.statsWindow },
+      );
+    }
+    this.driftEventLogSize = config.driftEventLogSize ?? DEFAULT_HISTORY_SIZE;
+    if (
+      !Number.isInteger(this.driftEventLogSize) ||
+      this.driftEventLogSize < 0
+    ) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `driftEventLogSize must be a non-negative integer, got ${this.driftEventLogSize}`,
+        { driftEventLogSize: this.driftEventLogSize },
+      );
+    }
+    if (config.projection !== undefined) {
+      const { dimension, seed = 0 } = config.projection;
+      if (
+        !Number.isInteger(dimension) ||
+        dimension < 1 ||
+        !Number.isInteger(seed)
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `projection must have a positive integer dimension and an integer seed, got dimension=${dimension}, seed=${seed}`,
+          { dimension, seed },
+        );
+      }
+      this.projection = new RandomProjection(dimension, seed);
+    }
+    if (config.dimension !== undefined) {
+      if (!Number.isInteger(config.dimension) || config.dimension < 1) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `dimension must be a positive integer, got ${config.dimension}`,
+          { dimension: config.dimension },
+        );
+      }
+      if (config.projection !== undefined) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          "dimension cannot be combined with projection",
+          { dimension: config.dimension },
+        );
+      }
+      this.dimension = config.dimension;
+    }
+    this.dimensionChangePolicy = config.dimensionChangePolicy ?? "error";
+    if (this.dimensionChangePolicy === "reset" && this.dimension !== undefined) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        'dimensionChangePolicy "reset" cannot be combined with dimension',
+        { dimension: this.dimension },
+      );
+    }
+    this.normalizeInputs = config.normalizeInputs ?? "none";
+    this.minUpdatesForHealth = config.minUpdatesForHealth ?? 0;
+    if (
+      !Number.isInteger(this.minUpdatesForHealth) ||
+      this.minUpdatesForHealth < 0
+    ) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `minUpdatesForHealth must be a non-negative integer, got ${this.minUpdatesForHealth}`,
+        { minUpdatesForHealth: this.minUpdatesForHealth },
+      );
+    }
+    this.archiveInterval =
+      config.stateArchive?.interval ?? DEFAULT_ARCHIVE_INTERVAL;
+    this.archiveSize =
+      config.stateArchive === undefined
+        ? 0
+        : config.stateArchive.size ?? DEFAULT_ARCHIVE_SIZE;
+    if (
+      !Number.isInteger(this.archiveInterval) ||
+      this.archiveInterval < 1 ||
+      !Number.isInteger(this.archiveSize) ||
+      this.archiveSize < 0
+    ) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `stateArchive must have a positive integer interval and a non-negative integer size, got interval=${this.archiveInterval}, size=${this.archiveSize}`,
+        { interval: this.archiveInterval, size: this.archiveSize },
+      );
+    }
+    if (config.embeddingSample !== undefined) {
+      const sampling = {
+        size: config.embeddingSample.size ?? DEFAULT_SAMPLE_SIZE,
+        method: config.embeddingSample.method ?? "reservoir",
+        stride: config.embeddingSample.stride ?? DEFAULT_SAMPLE_STRIDE,
+        seed: config.embeddingSample.seed ?? 0,
+      };
+      if (
+        !Number.isInteger(sampling.size) ||
+        sampling.size < 1 ||
+        !Number.isInteger(sampling.stride) ||
+        sampling.stride < 1
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `embeddingSample must have a positive integer size and stride, got size=${sampling.size}, stride=${sampling.stride}`,
+          { size: sampling.size, stride: sampling.stride },
+        );
+      }
+      this.sampling = { ...sampling, random: mulberry32(sampling.seed) };
+    }
+    this.maxCheckpoints = config.maxCheckpoints ?? DEFAULT_MAX_CHECKPOINTS;
+    if (!Number.isInteger(this.maxCheckpoints) || this.maxCheckpoints < 1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `maxCheckpoints must be a positive integer, got ${this.maxCheckpoints}`,
+        { maxCheckpoints: this.maxCheckpoints },
+      );
+    }
+    if (config.maxDriftEventsPerMinute !== undefined) {
+      if (
+        !Number.isInteger(config.maxDriftEventsPerMinute) ||
+        config.maxDriftEventsPerMinute < 1
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `maxDriftEventsPerMinute must be a positive integer, got ${config.maxDriftEventsPerMinute}`,
+          { maxDriftEventsPerMinute: config.maxDriftEventsPerMinute },
+        );
+      }
+      this.maxDriftEventsPerMinute = config.maxDriftEventsPerMinute;
+    }
+    if (config.staleAfterMs !== undefined) {
+      if (!(config.staleAfterMs > 0)) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `staleAfterMs must be a positive number, got ${config.staleAfterMs}`,
+          { staleAfterMs: config.staleAfterMs },
+        );
+      }
+      this.staleAfterMs = config.staleAfterMs;
+    }
+    if (config.clustering !== undefined) {
+      const k = config.clustering.k ?? DEFAULT_CLUSTER_K;
+      const newClusterSimilarity =
+        config.clustering.newClusterSimilarity ??
+        DEFAULT_NEW_CLUSTER_SIMILARITY;
+      if (
+        !Number.isInteger(k) ||
+        k < 1 ||
+        !(newClusterSimilarity >= -1 && newClusterSimilarity <= 1)
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `clustering must have a positive integer k and newClusterSimilarity in [-1, 1], got k=${k}, newClusterSimilarity=${newClusterSimilarity}`,
+          { k, newClusterSimilarity },
+        );
+      }
+      this.clusterer = new OnlineKMeans(k, newClusterSimilarity);
+    }
+    const compression = config.snapshotCompression;
+    if (compression?.method === "topk") {
+      const k = compression.k ?? DEFAULT_COMPRESSION_K;
+      if (!Number.isInteger(k) || k < 1) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `snapshotCompression k must be a positive integer, got ${k}`,
+          { k },
+        );
+      }
+      this.compressionK = k;
+    } else if (compression?.method === "pca") {
+      const components = compression.components ?? DEFAULT_PCA_COMPONENTS;
+      const learningRate =
+        compression.learningRate ?? DEFAULT_PCA_LEARNING_RATE;
+      if (
+        !Number.isInteger(components) ||
+        components < 1 ||
+        !(learningRate > 0 && learningRate <= 1)
+      ) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `snapshotCompression must have a positive integer number of components and a learningRate in (0, 1], got components=${components}, learningRate=${learningRate}`,
+          { components, learningRate },
+        );
+      }
+      this.pca = new OnlinePCA(components, learningRate);
+    }
+    if (config.pca2d !== undefined) {
+      const learningRate =
+        config.pca2d.learningRate ?? DEFAULT_PCA_LEARNING_RATE;
+      if (!(learningRate > 0 && learningRate <= 1)) {
+        throw new EngineError(
+          "INVALID_CONFIG",
+          `pca2d learningRate must be in (0, 1], got ${learningRate}`,
+          { learningRate },
+        );
+      }
+      this.pca2d = new OnlinePCA(2, learningRate);
+    }
+    this.warmupUpdates = config.warmupUpdates ?? 1;
+    if (!Number.isInteger(this.warmupUpdates) || this.warmupUpdates < 1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `warmupUpdates must be a positive integer, got ${this.warmupUpdates}`,
+        { warmupUpdates: this.warmupUpdates },
+      );
+    }
+
+    this.stateVector = [];
+    this.lastUpdatedAt = Date.now();
+    this.lastDrift = 0;
+    this.updateCount = 0;
+    this.recentEmbeddings = [];
+    this.driftStreak = 0;
+    this.driftCooldownUntil = 0;
+    this.history = [];
+    this.kalmanVariance = null;
+    this.driftWindowLog = [];
+    this.pastConcepts = [];
+    this.outlierWindow = [];
+    this.statsLog = [];
+    this.driftEvents = [];
+    this.currentCluster = null;
+    this.clusterAssignments = 0;
+    this.clusterSwitches = 0;
+    if (config.recordTrace) this.restartTrace();
+  }
+
+  /**
+   * Creates an engine from a plain JSON configuration object or string —
+   * e.g. loaded from a file or a remote settings service. The shape is
+   * validated first, with errors naming the offending option; the provider
+   * and callbacks, which cannot be serialized, are passed separately.
+   *
+   * @throws {EngineError} `INVALID_CONFIG` if the configuration is malformed.
+   */
+  static fromConfig(
+    config: unknown,
+    bindings: EngineBindings = {},
+  ): SemanticStateEngine {
+    return new SemanticStateEngine({ ...parseEngineConfig(config), ...bindings });
+  }
+
+  /**
+   * Warm-restarts an engine from a snapshot returned by {@link getSnapshot}
+   * (or its raw variants) — lighter than {@link exportState} when only the
+   * snapshot was kept, e.g. by a dashboard. The state vector, timestamp,
+   * update count and last drift and similarity are restored; windows,
+   * history, drift events, references and sources are not part of a
+   * snapshot, so they start empty, and the fusion window starts from the
+   * state vector as after {@link resetBaseline}. A snapshot taken with
+   * `stateHalfLifeMs` holds the decayed state, which is restored as is.
+   *
+   * @param snapshot The snapshot to restore.
+   * @param config   Configuration of the new engine.
+   * @throws {EngineError} `INVALID_STATE` if the snapshot is malformed or
+   *         does not match the configured `dimension` or `projection`.
+   */
+  static fromSnapshot(
+    snapshot: RestorableSnapshot,
+    config: SemanticStateEngineConfig,
+  ): SemanticStateEngine {
+    const engine = new SemanticStateEngine(config);
+    const vector = Array.from(snapshot.vector);
+    engine.restoreState({
+      version: STATE_VERSION,
+      stateVector: vector,
+      alpha: engine.alpha,
+      driftThreshold: engine.driftThreshold,
+      updateCount: snapshot.updateCount,
+      lastDrift: snapshot.lastDriftScore ?? 0,
+      lastSimilarity: snapshot.lastSimilarity ?? 1,
+      lastUpdatedAt: snapshot.timestamp,
+      recentEmbeddings: snapshot.updateCount > 0 ? [vector] : [],
+    });
+    if (engine.trace !== null) engine.restartTrace();
+    return engine;
+  }
+
+  /**
+   * Clears all accumulated state — state vector, update count, windows,
+   * history, and debounce state — while keeping the configuration.
+   * The next update establishes a fresh baseline. Subscribers are notified.
+   */
+  reset(): void {
+    this.clearState();
+    this.log("info", "reset", Date.now(), { reason: "reset" });
+    this.listeners.forEach((l) => l());
+  }
+
+  /**
+   * Re-seeds the state vector from `embedding` (used as-is, not blended)
+   * while keeping the configuration and the update history. Useful after a
+   * known topic change. Subscribers are notified.
+   *
+   * @param embedding The new baseline vector.
+   * @param timestamp Unix timestamp (ms) of the new baseline. Defaults to now.
+   * @throws {EngineError} `EMPTY_EMBEDDING` for an empty vector, or
+   *   `DIMENSION_MISMATCH` if it does not match the state, the configured
+   *   `dimension` or the registered references.
+   */
+  resetBaseline(
+    embedding: ArrayLike<number>,
+    timestamp: number = Date.now(),
+  ): void {
+    this.installBaseline(this.prepareBaseline(embedding, true), timestamp);
+  }
+
+  /**
+   * Initializes the engine from a precomputed centroid — e.g. the mean of
+   * last week's embeddings — instead of a cold start, so the first live
+   * updates are compared against a meaningful baseline. All accumulated
+   * state is discarded, as by {@link reset}, before `vector` is installed as
+   * by {@link resetBaseline}. Subscribers are notified.
+   *
+   * @param vector    The centroid to start from.
+   * @param timestamp Unix timestamp (ms) the centroid is current as of. Defaults to now.
+   * @throws {EngineError} `EMPTY_EMBEDDING` for an empty vector, or
+   *   `DIMENSION_MISMATCH` if it does not match the configured `dimension`
+   *   or the registered references.
+   */
+  seedState(vector: ArrayLike<number>, timestamp: number = Date.now()): void {
+    const baseline = this.prepareBaseline(vector, false);
+    this.clearState();
+    this.installBaseline(baseline, timestamp);
+  }
+
+  /**
+   * Prepares a baseline vector and checks it against the dimension the
+   * engine expects — counting the current state's unless it is about to be
+   * discarded.
+   */
+  private prepareBaseline(
+    vector: ArrayLike<number>,
+    keepState: boolean,
+  ): number[] {
+    if (vector.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Embedding must not be empty");
+    }
+    const baseline = this.prepare(vector);
+    const [reference] = this.references.values();
+    const [anchor] = this.anchors.values();
+    const state =
+      keepState && this.updateCount > 0 ? this.stateVector.length : undefined;
+    const expected =
+      state ?? this.dimension ?? reference?.length ?? anchor?.length;
+    if (expected !== undefined && baseline.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Embedding dimension mismatch: expected ${expected}, got ${baseline.length}`,
+        { expected, actual: baseline.length },
+      );
+    }
+    return baseline;
+  }
+
+  /** Installs an already prepared baseline vector, as by {@link resetBaseline}. */
+  private installBaseline(baseline: number[], timestamp: number): void {
+    if (this.clock === "monotonic") timestamp = this.now();
+    this.stateVector = baseline;
+    this.rotation = { total: 0, since: timestamp, velocity: null, steps: 0 };
+    this.recentEmbeddings = [this.pack([...baseline])];
+    this.lastDrift = 0;
+    this.lastSimilarity = 1;
+    this.lastUpdatedAt = timestamp;
+    this.updateCount = 1;
+    this.driftStreak = 0;
+    this.driftCooldownUntil = 0;
+    this.smoothedDrift = null;
+    this.kalmanVariance = null;
+    this.changeDetector?.reset();
+    if (this.trace !== null) this.restartTrace();
+    this.log("info", "reset", timestamp, { reason: "resetBaseline" });
+    this.listeners.forEach((l) => l());
+  }
+
+  /** Resets every piece of accumulated state to its initial value. */
+  private clearState(): void {
+    this.stateVector = [];
+    this.lastUpdatedAt = Date.now();
+    this.lastDrift = 0;
+    this.lastSimilarity = 1;
+    this.updateCount = 0;
+    this.recentEmbeddings = [];
+    this.driftStreak = 0;
+    this.driftCooldownUntil = 0;
+    this.smoothedDrift = null;
+    this.history = [];
+    this.kalmanVariance = null;
+    this.driftWindowLog = [];
+    this.pastConcepts = [];
+    this.outlierWindow = [];
+    this.statsLog = [];
+    this.driftEvents = [];
+    this.currentCluster = null;
+    this.clusterAssignments = 0;
+    this.clusterSwitches = 0;
+    this.sources.clear();
+    this.archive = [];
+    this.sample = [];
+    this.sampleSeen = 0;
+    this.magnitudeLog = [];
+    this.decayWindow = [];
+    this.horizonStates.clear();
+    this.driftReportTimes = [];
+    this.suppressedDriftCount = 0;
+    this.rotation = { total: 0, since: 0, velocity: null, steps: 0 };
+    this.changeDetector?.reset();
+    this.driftQuantile?.reset();
+    this.clusterer?.reset();
+    this.pca?.reset();
+    this.pca2d?.reset();
+    this.embeddingStats.reset();
+    if (this.trace !== null) this.restartTrace();
+  }
+
+  /**
+   * Obtains an embedding for `text` from the WorkerManager and fuses it into
+   * the rolling semantic state using the configured {@link FusionStrategy}.
+   *
+   * On the first call the embedding establishes the baseline.
+   * On subsequent calls, if the cosine similarity between the current state
+   * and the new embedding falls below {@link SemanticStateEngineConfig.driftThreshold},
+   * the {@link SemanticStateEngineConfig.onDriftDetected} callback is fired
+   * *before* the EMA fusion is applied.
+   *
+   * @param text Raw text whose embedding will be fused into the state.
+   * @returns    The outcome of the update, or `null` if the provider dropped
+   *             the request (e.g. the worker is still loading).
+   */
+  async update(text: string): Promise<UpdateResult | null> {
+    if (this.provider === undefined) {
+      throw new EngineError(
+        "NO_PROVIDER",
+        "update(text) requires an embedding provider; use updateEmbedding() instead",
+      );
+    }
+    const raw = await this.provider.getEmbedding(text);
+    if (raw === null) {
+      return null;
+    }
+    return this.updateEmbedding(raw);
+  }
+
+  /**
+   * Fuses an already-computed embedding into the state, bypassing the
+   * provider. Behaves exactly like {@link update} otherwise.
+   *
+   * @param embedding The embedding vector to fuse.
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   */
+  updateEmbedding(
+    embedding: ArrayLike<number>,
+    timestamp: number = Date.now(),
+  ): UpdateResult {
+    const result = this.applyEmbedding(Array.from(embedding), timestamp);
+    this.listeners.forEach((l) => l());
+    return result;
+  }
+
+  /**
+   * Like {@link updateEmbedding}, but returns the result packed into a
+   * single `ArrayBuffer` that can be transferred (rather than cloned)
+   * between a Worker and the main thread. Decode it with
+   * {@link unpackUpdateResult}; see {@link packUpdateResult} for the layout.
+   */
+  updateEmbeddingPacked(
+    embedding: ArrayLike<number>,
+    timestamp: number = Date.now(),
+  ): ArrayBuffer {
+    return packUpdateResult(this.updateEmbedding(embedding, timestamp));
+  }
+
+  /**
+   * Measures how `embedding` compares with the current state — similarity,
+   * drift score and whether the threshold would be crossed — without fusing
+   * it or touching any other state. Useful to ask "would this candidate
+   * drift the state?" before committing it with {@link updateEmbedding}.
+   * Debouncing, cool-down and outlier checks are not applied.
+   *
+   * @param embedding The candidate embedding.
+   * @param timestamp Unix timestamp (ms) to evaluate at, which matters with
+   *                  {@link SemanticStateEngineConfig.stateHalfLifeMs}.
+   *                  Defaults to now.
+   * @throws {EngineError} `DIMENSION_MISMATCH` if `embedding` does not match
+   *         the state.
+   */
+  evaluate(
+    embedding: ArrayLike<number>,
+    timestamp: number = Date.now(),
+  ): DriftEvaluation {
+    if (embedding.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Embedding must not be empty");
+    }
+    const candidate = this.prepare(embedding);
+    const expected = this.expectedDimension();
+    if (expected !== null && candidate.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Embedding dimension mismatch: expected ${expected}, got ${candidate.length}`,
+        { expected, actual: candidate.length },
+      );
+    }
+    this.checkDistribution(candidate);
+    const warmingUp = this.updateCount < this.warmupUpdates;
+    if (this.updateCount === 0) {
+      return {
+        similarity: 1,
+        driftScore: 0,
+        thresholdExceeded: false,
+        driftLevel: "none",
+        warmingUp,
+      };
+    }
+    const reference = this.driftReference(this.decayedState(timestamp));
+    const similarity = cosineSimilarity(reference, candidate);
+    const driftScore = this.measureDrift(reference, candidate, similarity);
+    const smoothed = this.smoothDrift(similarity, driftScore);
+    const thresholdExceeded =
+      !warmingUp && this.exceedsThreshold(smoothed.similarity, smoothed.drift);
+    return {
+      similarity,
+      driftScore,
+      thresholdExceeded,
+      driftLevel: !thresholdExceeded
+        ? "none"
+        : this.exceedsCritical(smoothed.similarity, smoothed.drift)
+          ? "critical"
+          : "warning",
+      warmingUp,
+    };
+  }
+
+  /**
+   * Fuses an embedding whose influence on the state is scaled by `weight`,
+   * e.g. a confidence score from its source. Behaves like
+   * {@link updateEmbedding} otherwise.
+   *
+   * For the `"ema"` strategy the effective α is multiplied by `weight` and
+   * capped at 1; for `"kalman"` the measurement noise is divided by it. The
+   * windowed strategies (`"sma"`, `"wma"`, `"cumulative"`, `"median"`) ignore
+   * the weight.
+   *
+   * @param embedding The embedding vector to fuse.
+   * @param weight    Positive influence multiplier; 1 is a normal update.
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   * @throws {EngineError} `INVALID_CONFIG` unless `weight` is finite and
+   *   positive, before anything is applied.
+   */
+  updateWeighted(
+    embedding: ArrayLike<number>,
+    weight: number,
+    timestamp: number = Date.now(),
+  ): UpdateResult {
+    if (!(weight > 0) || weight === Infinity) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `weight must be a finite positive number, got ${weight}`,
+        { weight },
+      );
+    }
+    const result = this.applyEmbedding(Array.from(embedding), timestamp, weight);
+    this.listeners.forEach((l) => l());
+    return result;
+  }
+
+  /**
+   * Fuses an embedding, judging drift against `threshold` instead of the
+   * configured (or automatic) threshold for this update only — e.g. to
+   * tighten detection during a critical operation. Behaves like
+   * {@link updateEmbedding} otherwise.
+   *
+   * @param embedding The embedding vector to fuse.
+   * @param threshold Threshold in the units of
+   *                  {@link SemanticStateEngineConfig.driftThreshold}: a
+   *                  similarity for the cosine metric, a distance otherwise.
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   */
+  updateWithThreshold(
+    embedding: ArrayLike<number>,
+    threshold: number,
+    timestamp: number = Date.now(),
+  ): UpdateResult {
+    if (!Number.isFinite(threshold)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `threshold must be a finite number, got ${threshold}`,
+        { threshold },
+      );
+    }
+    const result = this.applyEmbedding(
+      Array.from(embedding),
+      timestamp,
+      1,
+      threshold,
+    );
+    this.listeners.forEach((l) => l());
+    return result;
+  }
+
+  /**
+   * Fuses a sparse embedding given as its non-zero entries — e.g. a
+   * SPLADE-style lexical vector with tens of thousands of dimensions. The
+   * state itself is dense, so the entries are scattered into a dense vector
+   * internally; callers never build one. Behaves like {@link updateEmbedding}
+   * otherwise.
+   *
+   * @param embedding The sparse embedding. Duplicate indices are summed.
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   * @throws {EngineError} `INVALID_CONFIG` if the sparse vector is malformed.
+   */
+  updateSparse(
+    embedding: SparseVector,
+    timestamp: number = Date.now(),
+  ): UpdateResult {
+    const problem = validateSparse(embedding);
+    if (problem !== null) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `Invalid sparse embedding: ${problem}`,
+        { dimension: embedding.dimension, nnz: embedding.indices.length },
+      );
+    }
+    const result = this.applyEmbedding(toDense(embedding), timestamp);
+    this.listeners.forEach((l) => l());
+    return result;
+  }
+
+  /**
+   * Allocates (or replaces) a reusable `Float32Array` of `dimension` floats
+   * owned by the engine. Fill it in place — e.g. straight from a model's
+   * output tensor — and call {@link updateFromBuffer}, instead of allocating
+   * a new array for every update of a high-frequency stream.
+   *
+   * @returns The buffer to write embeddings into.
+   */
+  allocEmbeddingBuffer(dimension: number): Float32Array {
+    if (!Number.isInteger(dimension) || dimension < 1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `dimension must be a positive integer, got ${dimension}`,
+        { dimension },
+      );
+    }
+    this.embeddingBuffer = new Float32Array(dimension);
+    return this.embeddingBuffer;
+  }
+
+  /**
+   * Fuses the current contents of the buffer returned by
+   * {@link allocEmbeddingBuffer}. Behaves like {@link updateEmbedding}
+   * otherwise; the buffer may be overwritten as soon as this returns.
+   *
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   * @throws {EngineError} `NOT_INITIALIZED` if no buffer has been allocated.
+   */
+  updateFromBuffer(timestamp: number = Date.now()): UpdateResult {
+    if (this.embeddingBuffer === null) {
+      throw new EngineError(
+        "NOT_INITIALIZED",
+        "updateFromBuffer() requires a buffer from allocEmbeddingBuffer()",
+      );
+    }
+    return this.updateEmbedding(this.embeddingBuffer, timestamp);
+  }
+
+  /**
+   * Registers a caller-owned `Float32Array` that {@link updateEmbeddingInto}
+   * writes the fused state into, so hot loops can read the state without a
+   * fresh copy from {@link getSnapshot} on every update. Pass `null` to
+   * unregister it.
+   */
+  setOutputBuffer(buffer: Float32Array | null): void {
+    this.outputBuffer = buffer;
+  }
+
+  /**
+   * Like {@link updateEmbedding}, but writes the fused state into the buffer
+   * registered with {@link setOutputBuffer} and returns only the result's
+   * scalar and summary fields — the echoed `vector` is left out.
+   *
+   * @param embedding The embedding vector to fuse.
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   * @throws {EngineError} `NOT_INITIALIZED` if no buffer is registered, or
+   *   `DIMENSION_MISMATCH` if its length differs from the dimension the
+   *   state will have. Either is thrown before the update is applied.
+   */
+  updateEmbeddingInto(
+    embedding: ArrayLike<number>,
+    timestamp: number = Date.now(),
+  ): Omit<UpdateResult, "vector"> {
+    const output = this.outputBuffer;
+    if (output === null) {
+      throw new EngineError(
+        "NOT_INITIALIZED",
+        "updateEmbeddingInto() requires a buffer from setOutputBuffer()",
+      );
+    }
+    // Under the "reset" policy the state takes on the embedding's dimension.
+    const expected =
+      this.projection?.outputDimension ??
+      (this.dimensionChangePolicy === "reset"
+        ? null
+        : this.expectedDimension()) ??
+      embedding.length;
+    if (output.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Output buffer dimension mismatch: expected ${expected}, got ${output.length}`,
+        { expected, actual: output.length },
+      );
+    }
+    const result = this.applyEmbedding(
+      Array.from(embedding),
+      timestamp,
+      1,
+      undefined,
+      undefined,
+      false,
+    );
+    output.set(this.stateVector);
+    this.listeners.forEach((l) => l());
+    return result;
+  }
+
+  /**
+   * Fuses an embedding tagged with the stream it came from — e.g. `"user"`
+   * queries and `"assistant"` responses. The embedding updates the global
+   * state exactly like {@link updateEmbedding}, and also an EMA state kept
+   * for that source alone (seeded with its first embedding). The result
+   * reports how similar this source's state is to every other source's.
+   *
+   * @param source    Name of the stream the embedding belongs to.
+   * @param embedding The embedding vector to fuse.
+   * @param timestamp Unix timestamp (ms) of the observation. Defaults to now.
+   */
+  updateFrom(
+    source: string,
+    embedding: ArrayLike<number>,
+    timestamp: number = Date.now(),
+  ): UpdateResult {
+    const result = this.applyEmbedding(
+      Array.from(embedding),
+      timestamp,
+      1,
+      undefined,
+      source,
+    );
+    this.listeners.forEach((l) => l());
+    return result;
+  }
+
+  /**
+   * Fuses an update into its source's state and, when `report` is set,
+   * reports the source and its cross-source similarity.
+   */
+  private applySource(
+    source: string,
+    embedding: number[],
+    result: UpdateResult,
+    report: boolean,
+  ): void {
+    if (report) result.source = source;
+    if (!result.rejected) {
+      const previous = this.sources.get(source);
+      this.sources.set(
+        source,
+        previous === undefined
+          ? [...embedding]
+          : emaFusion(embedding, previous, this.alpha),
+      );
+    }
+    const state = this.sources.get(source);
+    if (report && state !== undefined && this.sources.size > 1) {
+      result.sourceSimilarities = Object.fromEntries(
+        [...this.sources]
+          .filter(([name]) => name !== source)
+          .map(([name, v]) => [name, cosineSimilarity(state, v)]),
+      );
+    }
+  }
+
+  /**
+   * Returns a copy of each {@link SemanticStateEngineConfig.horizons} state
+   * by name — empty before the first update or without horizons.
+   */
+  getHorizonStates(): Record<string, number[]> {
+    return Object.fromEntries(
+      [...this.horizonStates].map(([name, v]) => [name, [...v]]),
+    );
+  }
+
+  /** Returns the names of the sources seen by {@link updateFrom}. */
+  getSources(): string[] {
+    return [...this.sources.keys()];
+  }
+
+  /**
+   * Returns a copy of a source's state vector, or `null` if no embedding
+   * from that source has been fused.
+   */
+  getSourceVector(source: string): number[] | null {
+    const state = this.sources.get(source);
+    return state === undefined ? null : [...state];
+  }
+
+  /**
+   * Fuses many embeddings in order and notifies subscribers once at the end,
+   * rather than once per embedding.
+   *
+   * If an embedding is rejected (e.g. dimension mismatch) the error is thrown
+   * after subscribers have been notified of the embeddings fused before it.
+   *
+   * @param embeddings Embedding vectors, oldest first.
+   * @param timestamps Optional Unix timestamps (ms), one per embedding.
+   *                   Defaults to now for every embedding.
+   * @returns          One {@link UpdateResult} per embedding.
+   */
+  updateBatch(
+    embeddings: ArrayLike<number>[],
+    timestamps?: number[],
+  ): UpdateResult[] {
+    if (timestamps !== undefined && timestamps.length !== embeddings.length) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `Timestamp count mismatch: embeddings=${embeddings.length}, timestamps=${timestamps.length}`,
+        { embeddings: embeddings.length, timestamps: timestamps.length },
+      );
+    }
+    const results: UpdateResult[] = [];
+    try {
+      embeddings.forEach((embedding, i) => {
+        results.push(
+          this.applyEmbedding(
+            Array.from(embedding),
+            timestamps?.[i] ?? Date.now(),
+          ),
+        );
+      });
+    } finally {
+      if (results.length > 0) {
+        this.listeners.forEach((l) => l());
+      }
+    }
+    return results;
+  }
+
+  /**
+   * Runs drift detection and fusion for a single embedding without notifying
+   * subscribers, recording its latency in the engine metrics.
+   */
+  private applyEmbedding(
+    embedding: number[],
+    timestamp: number,
+    weight = 1,
+    threshold?: number,
+    source?: string,
+    echo = true,
+  ): UpdateResult {
+    timestamp = this.resolveTimestamp(timestamp);
+    const start = elapsedMs();
+    const result = this.processEmbedding(
+      embedding,
+      timestamp,
+      weight,
+      threshold,
+      source,
+      echo,
+    );
+    const elapsedUs = (elapsedMs() - start) * 1000;
+    this.metrics.updates++;
+    this.metrics.totalUpdateUs += elapsedUs;
+    this.metrics.lastUpdateUs = elapsedUs;
+    this.metrics.maxUpdateUs = Math.max(this.metrics.maxUpdateUs, elapsedUs);
+    this.metrics.peakStateBytes = Math.max(
+      this.metrics.peakStateBytes,
+      this.stateBytes(),
+    );
+    if (this.logger !== undefined) {
+      this.log("debug", "update", timestamp, {
+        updateCount: this.updateCount,
+        similarity: result.similarity,
+        driftScore: result.driftScore,
+        driftLevel: result.driftLevel,
+        driftDetected: result.driftDetected,
+        alpha: result.alpha,
+        durationUs: elapsedUs,
+        ...(source !== undefined && { source }),
+      });
+    }
+    return result;
+  }
+
+  /** Logs a drift event under `"drift_detected"`, without its timestamp and metadata. */
+  private logDrift(level: EngineLogLevel, event: DriftEvent): void {
+    if (this.logger === undefined) return;
+    const fields: Record<string, unknown> = { ...event };
+    delete fields.timestamp;
+    delete fields.metadata;
+    this.log(level, "drift_detected", event.timestamp, fields);
+  }
+
+  /** Passes a structured record to the configured `logger`, if any. */
+  private log(
+    level: EngineLogLevel,
+    event: EngineLogRecord["event"],
+    timestamp: number,
+    fields: Record<string, unknown>,
+  ): void {
+    if (this.logger === undefined) return;
+    const record: EngineLogRecord = { level, event, timestamp, fields };
+    if (this.metadata !== undefined) record.metadata = { ...this.metadata };
+    this.logger(record);
+  }
+
+  private processEmbedding(
+    embedding: number[],
+    timestamp: number,
+    weight: number,
+    threshold: number | undefined,
+    source: string | undefined,
+    echo: boolean,
+  ): UpdateResult {
+    if (embedding.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Embedding must not be empty");
+    }
+    const input = embedding;
+    embedding = this.prepare(embedding);
+    let dimensionChange: DimensionChange | undefined;
+    const expected = this.expectedDimension();
+    if (expected !== null && embedding.length !== expected) {
+      dimensionChange = this.changeDimension(expected, embedding.length);
+      if (dimensionChange.action !== "reset") {
+        embedding = fitDimension(embedding, expected);
+      }
+    }
+    this.checkDistribution(embedding);
+
+    let similarity = 1;
+    let drift = 0;
+    let magnitudeDrift = 0;
+    let smoothedDriftScore: number | undefined;
+    let critical = false;
+    let driftDetected = false;
+    let thresholdExceeded = false;
+    let changePointDetected = false;
+    let attribution: DimensionContribution[] | undefined;
+    let driftDirection: number[] | undefined;
+    let driftType: DriftType | undefined;
+    let outlier = false;
+    let driftSuppressed = false;
+    let alpha = Math.min(1, this.alpha * weight);
+    const warmingUp = this.updateCount < this.warmupUpdates;
+
+    const inputShift = this.detectInputShift(input);
+    // "minimal" results skip the comparisons they would not report.
+    const detailed = this.resultDetail !== "minimal";
+    const historySimilarity = detailed
+      ? this.compareWithRecent(embedding)
+      : undefined;
+    const anchor = detailed ? this.matchAnchor(embedding) : undefined;
+    if (this.trace !== null) {
+      const entry: TraceEntry = { embedding: [...input], timestamp };
+      if (weight !== 1) entry.weight = weight;
+      if (threshold !== undefined) entry.threshold = threshold;
+      if (source !== undefined) entry.source = source;
+      this.trace.entries.push(entry);
+    }
+
+    if (this.updateCount === 0) {
+      // First call: establish baseline.
+      this.stateVector = this.fuse(embedding, alpha, weight);
+      this.rotation = { total: 0, since: timestamp, velocity: null, steps: 0 };
+    } else {
+      const undecayed = this.stateVector;
+      this.stateVector = this.decayedState(timestamp);
+      const reference = this.driftReference();
+      similarity = cosineSimilarity(reference, embedding);
+      drift = this.measureDrift(reference, embedding, similarity);
+      const baseMagnitude = magnitude(reference);
+      if (baseMagnitude > 0) {
+        magnitudeDrift = magnitude(embedding) / baseMagnitude - 1;
+      }
+      if (this.attributionTopK > 0 && this.resultDetail === "full") {
+        attribution = attributeDrift(
+          reference,
+          embedding,
+          this.driftMetric,
+          this.attributionTopK,
+          this.driftMetric === "mahalanobis"
+            ? this.mahalanobisVariance(embedding.length)
+            : undefined,
+        );
+      }
+
+      if (this.driftDirection && this.resultDetail === "full") {
+        driftDirection = normalize(embedding.map((v, i) => v - reference[i]!));
+      }
+
+      if (this.outliers !== undefined && !warmingUp) {
+        outlier = this.isOutlier(drift);
+        if (outlier && this.outliers.reject) {
+          this.stateVector = undecayed;
+          const rejected: UpdateResult = {
+            similarity,
+            driftScore: drift,
+            magnitudeDrift,
+            driftDetected: false,
+            thresholdExceeded: false,
+            inputShift,
+            driftLevel: "none",
+            changePointDetected: false,
+            alpha: 0,
+            timestamp,
+            warmingUp,
+            outlier,
+            rejected: true,
+          };
+          if (attribution !== undefined) rejected.attribution = attribution;
+          if (driftDirection !== undefined) {
+            rejected.driftDirection = driftDirection;
+          }
+          if (historySimilarity !== undefined) {
+            rejected.historySimilarity = historySimilarity;
+          }
+          if (anchor !== undefined) rejected.anchor = anchor;
+          if (dimensionChange !== undefined) {
+            rejected.dimensionChange = dimensionChange;
+          }
+          if (source !== undefined) {
+            this.applySource(source, embedding, rejected, detailed);
+          }
+          return this.withDetail(rejected, embedding, echo);
+        }
+        this.outlierWindow.push(drift);
+        if (this.outlierWindow.length > this.outliers.window) {
+          this.outlierWindow.shift();
+        }
+      }
+
+      const smoothed = this.smoothDrift(similarity, drift);
+      if (this.driftSmoothing !== undefined) {
+        this.smoothedDrift = smoothed;
+        smoothedDriftScore = smoothed.drift;
+      }
+      if (!warmingUp) {
+        thresholdExceeded = this.exceedsThreshold(
+          smoothed.similarity,
+          smoothed.drift,
+          threshold,
+        );
+        critical =
+          thresholdExceeded &&
+          this.exceedsCritical(smoothed.similarity, smoothed.drift);
+        this.driftQuantile?.update(smoothed.drift);
+        driftDetected = this.debounceDrift(thresholdExceeded, timestamp);
+        if (driftDetected && timestamp < this.suppressedUntil) {
+          // The type goes on the recorded event only: the result reports no drift.
+          const event = this.recordDriftEvent(
+            timestamp,
+            smoothed.drift,
+            smoothed.similarity,
+            this.classify ? this.classifyDetectedDrift(embedding) : undefined,
+            threshold,
+            true,
+          );
+          this.logDrift("info", event);
+          driftDetected = false;
+          driftSuppressed = true;
+        } else if (driftDetected && this.rateLimitDrift(timestamp)) {
+          driftDetected = false;
+          driftSuppressed = true;
+        }
+        if (driftDetected) {
+          if (this.classify) driftType = this.classifyDetectedDrift(embedding);
+          const event = this.recordDriftEvent(
+            timestamp,
+            smoothed.drift,
+            smoothed.similarity,
+            driftType,
+            threshold,
+          );
+          this.onDriftDetected?.([...embedding], drift);
+          this.driftListeners.forEach((l) => l(clonePlain(event)));
+          this.logDrift("warn", event);
+        }
+        changePointDetected = this.changeDetector?.update(drift) ?? false;
+      }
+      if (this.classify) {
+        this.driftWindowLog.push({ driftScore: drift, thresholdExceeded });
+        if (this.driftWindowLog.length > DRIFT_CLASSIFY_WINDOW) {
+          this.driftWindowLog.shift();
+        }
+      }
+      alpha = Math.min(1, this.effectiveAlpha(drift) * weight);
+      this.stateVector = this.fuse(embedding, alpha, weight);
+      this.trackRotation(undecayed, timestamp);
+
+      if (this.statsWindow > 0) {
+        this.statsLog.push({ timestamp, driftScore: drift });
+        if (this.statsLog.length > this.statsWindow) {
+          this.statsLog.shift();
+        }
+      }
+    }
+
+    const cluster = this.assignCluster(embedding);
+    this.embeddingStats.update(embedding);
+    this.pca?.observe(embedding);
+    this.pca2d?.observe(embedding);
+    this.recordRecent(embedding, timestamp);
+    const horizonDriftScore = this.updateHorizons(embedding);
+
+    this.lastDrift = drift;
+    this.lastSimilarity = similarity;
+    this.lastUpdatedAt = timestamp;
+    this.updateCount++;
+    this.recordHistory({
+      timestamp,
+      driftScore: drift,
+      healthScore: this.calculateHealth(timestamp),
+    });
+    this.archiveState(timestamp);
+    this.sampleEmbedding(input, timestamp);
+
+    const result: UpdateResult = {
+      similarity,
+      driftScore: drift,
+      magnitudeDrift,
+      driftDetected,
+      thresholdExceeded,
+      inputShift,
+      driftLevel: !thresholdExceeded
+        ? "none"
+        : critical
+          ? "critical"
+          : "warning",
+      changePointDetected,
+      alpha,
+      timestamp,
+      warmingUp,
+      outlier,
+      rejected: false,
+    };
+    if (attribution !== undefined) {
+      result.attribution = attribution;
+    }
+    if (driftDirection !== undefined) {
+      result.driftDirection = driftDirection;
+    }
+    if (historySimilarity !== undefined) {
+      result.historySimilarity = historySimilarity;
+    }
+    if (anchor !== undefined) {
+      result.anchor = anchor;
+    }
+    if (smoothedDriftScore !== undefined) {
+      result.smoothedDriftScore = smoothedDriftScore;
+    }
+    if (dimensionChange !== undefined) {
+      result.dimensionChange = dimensionChange;
+    }
+    if (horizonDriftScore !== undefined) {
+      result.horizonDriftScore = horizonDriftScore;
+      result.regimeShift =
+        !warmingUp &&
+        horizonDriftScore >=
+          (this.horizons!.threshold ?? this.driftScoreThreshold());
+    }
+    if (detailed && this.references.size > 0) {
+      result.referenceSimilarities = this.referenceSimilarities();
+    }
+    if (detailed && driftType !== undefined) {
+      result.driftType = driftType;
+    }
+    if (detailed && cluster !== undefined) {
+      result.cluster = cluster;
+    }
+    if (driftSuppressed) {
+      result.driftSuppressed = true;
+    }
+    if (source !== undefined) {
+      this.applySource(source, embedding, result, detailed);
+    }
+    return this.withDetail(result, embedding, echo);
+  }
+
+  /**
+   * Adds the echoed `vector` at `"full"` {@link SemanticStateEngineConfig.resultDetail}
+   * unless `echo` is false.
+   */
+  private withDetail(
+    result: UpdateResult,
+    embedding: number[],
+    echo: boolean,
+  ): UpdateResult {
+    if (echo && this.resultDetail === "full") {
+      // A copy: at "f64" precision `embedding` itself is retained in the
+      // fusion and history-similarity windows.
+      result.vector = [...embedding];
+    }
+    return result;
+  }
+
+  /**
+   * Returns a copy of the recorded update trace, or `null` unless
+   * {@link SemanticStateEngineConfig.recordTrace} is enabled. Recording
+   * restarts from the current state on `reset`, `resetBaseline`,
+   * `importState` and `replay`. Configuration changes made while recording
+   * (e.g. `setAlpha`, `addReference`) are not part of the trace.
+   */
+  getTrace(): UpdateTrace | null {
+    if (this.trace === null) return null;
+    return {
+      initialState: clonePlain(this.trace.initialState),
+      entries: this.trace.entries.map((entry) => ({
+        ...entry,
+        embedding: [...entry.embedding],
+      })),
+    };
+  }
+
+  /**
+   * Restores a trace's initial state and re-applies every recorded update,
+   * reproducing the exact final state of the recording engine — provided
+   * this engine has the same configuration. Subscribers are notified once.
+   *
+   * @returns One {@link UpdateResult} per recorded update.
+   * @throws {EngineError} `INVALID_STATE` if the initial state is malformed.
+   */
+  replay(trace: UpdateTrace): UpdateResult[] {
+    this.clearState();
+    this.restoreState(trace.initialState);
+    if (this.trace !== null) this.restartTrace();
+    const results = trace.entries.map((entry) =>
+      this.applyEmbedding(
+        [...entry.embedding],
+        entry.timestamp,
+        entry.weight ?? 1,
+        entry.threshold,
+        entry.source,
+      ),
+    );
+    this.listeners.forEach((l) => l());
+    return results;
+  }
+
+  /**
+   * Returns performance and memory telemetry accumulated since the engine
+   * was created or {@link resetMetrics} was last called. Memory figures are
+   * estimates at 8 bytes per stored number.
+   */
+  getMetrics(): EngineMetrics {
+    const {
+      updates,
+      totalUpdateUs,
+      lastUpdateUs,
+      maxUpdateUs,
+      peakStateBytes,
+    } = this.metrics;
+    return {
+      updates,
+      lastUpdateUs,
+      meanUpdateUs: updates > 0 ? totalUpdateUs / updates : 0,
+      maxUpdateUs,
+      stateBytes: this.stateBytes(),
+      peakStateBytes,
+      historyBytes: this.historyBytes(),
+    };
+  }
+
+  /** Clears the latency counters and the memory peak of {@link getMetrics}. */
+  resetMetrics(): void {
+    this.metrics = {
+      updates: 0,
+      totalUpdateUs: 0,
+      lastUpdateUs: 0,
+      maxUpdateUs: 0,
+      peakStateBytes: this.stateBytes(),
+    };
+  }
+
+  /** Estimated bytes held by the state and every other retained vector. */
+  private stateBytes(): number {
+    const vectors: StoredVector[] = [
+      this.packedState,
+      ...this.recentEmbeddings,
+      ...(this.kalmanVariance === null ? [] : [this.kalmanVariance]),
+      ...this.references.values(),
+      ...this.anchors.values(),
+      ...this.sources.values(),
+      ...this.horizonStates.values(),
+      ...this.pastConcepts,
+      ...this.archive.map((entry) => entry.vector),
+      ...this.sample.map((entry) => entry.vector),
+      this.embeddingStats.mean(),
+      this.embeddingStats.variance(),
+    ];
+    const d = this.packedState.length;
+    const centroids = (this.clusterer?.count ?? 0) * d;
+    const pcaBasis = [this.pca, this.pca2d].reduce(
+      (sum, pca) =>
+        pca === undefined || pca.count === 0
+          ? sum
+          : sum + (Math.min(pca.components, d) + 1) * d,
+      0,
+    );
+    return vectors.reduce(
+      (sum, v) => sum + storedBytes(v),
+      (centroids + pcaBasis) * BYTES_PER_NUMBER,
+    );
+  }
+
+  /** Estimated bytes held by the history, drift event log and trace. */
+  private historyBytes(): number {
+    const traced = (this.trace?.entries ?? []).reduce(
+      (sum, entry) => sum + entry.embedding.length + 1,
+      0,
+    );
+    const numbers =
+      this.history.length * 3 +
+      this.driftEvents.length * 4 +
+      this.statsLog.length * 2 +
+      traced;
+    return numbers * BYTES_PER_NUMBER;
+  }
+
+  /** Starts a new trace from the current state. */
+  private restartTrace(): void {
+    this.trace = { initialState: this.exportState(), entries: [] };
+  }
+
+  /** Assigns an embedding to a cluster and tracks churn, if clustering is enabled. */
+  private assignCluster(embedding: number[]): number | undefined {
+    if (this.clusterer === undefined) return undefined;
+    const { cluster } = this.clusterer.assign(embedding);
+    if (this.currentCluster !== null && cluster !== this.currentCluster) {
+      this.clusterSwitches++;
+    }
+    this.currentCluster = cluster;
+    this.clusterAssignments++;
+    return cluster;
+  }
+
+  /**
+   * Returns copies of the cluster centroids, in founding order. Empty unless
+   * {@link SemanticStateEngineConfig.clustering} is configured.
+   */
+  getCentroids(): number[][] {
+    return this.clusterer?.getCentroids() ?? [];
+  }
+
+  /** Appends an entry to the bounded history, evicting the oldest. */
+  private recordHistory(entry: HistoryEntry): void {
+    if (this.historySize === 0) return;
+    this.history.push(entry);
+    if (this.history.length > this.historySize) {
+      this.history.shift();
+    }
+  }
+
+  /** Archives the state if this update falls on the archive interval. */
+  private archiveState(timestamp: number): void {
+    if (this.archiveSize === 0) return;
+    if (this.updateCount % this.archiveInterval !== 0) return;
+    this.archive.push({
+      timestamp,
+      updateCount: this.updateCount,
+      vector: this.pack([...this.stateVector]),
+    });
+    if (this.archive.length > this.archiveSize) {
+      this.archive.shift();
+    }
+  }
+
+  /**
+   * Offers a fused input embedding to the
+   * {@link SemanticStateEngineConfig.embeddingSample} (Algorithm R for the
+   * reservoir).
+   */
+  private sampleEmbedding(input: ArrayLike<number>, timestamp: number): void {
+    if (this.sampling === undefined) return;
+    const index = ++this.sampleSeen;
+    const { size, method, stride, random } = this.sampling;
+    const entry = () => ({ timestamp, index, vector: this.pack(Array.from(input)) });
+    if (method === "stride") {
+      if (index % stride !== 0) return;
+      this.sample.push(entry());
+      if (this.sample.length > size) this.sample.shift();
+    } else if (this.sample.length < size) {
+      this.sample.push(entry());
+    } else {
+      const slot = Math.floor(random() * index);
+      if (slot < size) {
+        this.sample.splice(slot, 1);
+        this.sample.push(entry());
+      }
+    }
+  }
+
+  /**
+   * Returns a copy of the embeddings retained by
+   * {@link SemanticStateEngineConfig.embeddingSample}, oldest first — empty
+   * when it is not configured.
+   */
+  getEmbeddingSample(): SampledEmbedding[] {
+    return this.sample.map((entry) => ({
+      ...entry,
+      vector: [...unpack(entry.vector)],
+    }));
+  }
+
+  /**
+   * Returns the `n` archived states most similar to `embedding`, most similar
+   * first. Empty unless {@link SemanticStateEngineConfig.stateArchive} is
+   * configured. `embedding` is projected and normalized like an update.
+   *
+   * @throws {EngineError} `DIMENSION_MISMATCH` if `embedding` does not match
+   *         the state.
+   */
+  findSimilarStates(embedding: ArrayLike<number>, n: number): SimilarState[] {
+    if (!Number.isInteger(n) || n < 0) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `n must be a non-negative integer, got ${n}`,
+        { n },
+      );
+    }
+    if (embedding.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Embedding must not be empty");
+    }
+    const query = this.prepare(embedding);
+    const expected = this.expectedDimension();
+    if (expected !== null && query.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Embedding dimension mismatch: expected ${expected}, got ${query.length}`,
+        { expected, actual: query.length },
+      );
+    }
+    return this.archive
+      .map((entry) => {
+        const vector = [...unpack(entry.vector)];
+        return {
+          timestamp: entry.timestamp,
+          updateCount: entry.updateCount,
+          vector,
+          similarity: cosineSimilarity(vector, query),
+        };
+      })
+      .sort((a, b) => b.similarity - a.similarity)
+      .slice(0, n);
+  }
+
+  /**
+   * Builds the event for a reported drift and appends it to the bounded log,
+   * evicting the oldest.
+   */
+  private recordDriftEvent(
+    timestamp: number,
+    driftScore: number,
+    similarity: number,
+    driftType: DriftType | undefined,
+    override: number | undefined,
+    suppressed = false,
+  ): DriftEvent {
+    const auto = override === undefined ? this.getAutoThreshold() : null;
+    const bySimilarity = auto === null && this.driftMetric === "cosine";
+    const threshold = auto ?? override ?? this.driftThreshold;
+    const event: DriftEvent = {
+      timestamp,
+      driftScore,
+      similarity,
+      metric: this.driftMetric,
+      threshold,
+      reason: bySimilarity
+        ? `similarity ${similarity.toFixed(3)} < ${threshold} (cosine)`
+        : `drift ${driftScore.toFixed(3)} > ${auto === null ? threshold : `auto threshold ${threshold.toFixed(3)}`} (${this.driftMetric})`,
+      driftLevel: this.exceedsCritical(similarity, driftScore)
+        ? "critical"
+        : "warning",
+    };
+    if (driftType !== undefined) event.driftType = driftType;
+    if (suppressed) event.suppressed = true;
+    if (this.metadata !== undefined) event.metadata = { ...this.metadata };
+    if (this.driftEventLogSize > 0) {
+      this.driftEvents.push(event);
+      if (this.driftEvents.length > this.driftEventLogSize) {
+        this.driftEvents.shift();
+      }
+    }
+    return event;
+  }
+
+  /**
+   * Number of drift reports suppressed by
+   * {@link SemanticStateEngineConfig.maxDriftEventsPerMinute} since the
+   * engine was created or last reset.
+   */
+  getSuppressedDriftCount(): number {
+    return this.suppressedDriftCount;
+  }
+
+  /**
+   * Opens a maintenance window — e.g. around a planned prompt or model
+   * change — until `timestamp` (ms, compared with update timestamps).
+   * Updates are fused as usual, but drift detected before then is not
+   * reported: the result carries `driftSuppressed: true`, callbacks and
+   * `onDrift` listeners are skipped, and the event is still recorded in the
+   * drift event log with `suppressed: true`. A past timestamp closes the
+   * window.
+   */
+  suppressUntil(timestamp: number): void {
+    if (!Number.isFinite(timestamp)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `suppressUntil timestamp must be finite, got ${timestamp}`,
+        { timestamp },
+      );
+    }
+    this.suppressedUntil = timestamp;
+  }
+
+  /** Timestamp (ms) the current {@link suppressUntil} window ends at (0 when none was set). */
+  getSuppressUntil(): number {
+    return this.suppressedUntil;
+  }
+
+  /**
+   * Returns a copy of the drift event log — one entry per reported drift,
+   * oldest first. Capacity is controlled by
+   * {@link SemanticStateEngineConfig.driftEventLogSize}.
+   */
+  getDriftEvents(): DriftEvent[] {
+    return this.driftEvents.map((event) => clonePlain(event));
+  }
+
+  /** Empties the drift event log, keeping all other state. */
+  clearDriftEvents(): void {
+    this.driftEvents = [];
+  }
+
+  /**
+   * Returns a copy of the bounded update history, oldest first.
+   * Capacity is controlled by {@link SemanticStateEngineConfig.historySize}.
+   */
+  getHistory(): HistoryEntry[] {
+    return this.history.map((entry) => ({ ...entry }));
+  }
+
+  /**
+   * Aggregates the retained history into time buckets — e.g. hourly bars for
+   * a dashboard, without exporting the whole history on every render.
+   *
+   * @param bucketMs Bucket width (ms); buckets start at multiples of it.
+   * @param now      Unix timestamp (ms) of the newest update to include.
+   *                 Defaults to now.
+   * @returns        Per-bucket count, mean and max drift, and mean health,
+   *                 oldest first; buckets without updates are omitted.
+   */
+  getAggregates(bucketMs: number, now: number = this.now()): HistoryBucket[] {
+    if (!(bucketMs > 0) || bucketMs === Infinity) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `bucketMs must be a finite positive number, got ${bucketMs}`,
+        { bucketMs },
+      );
+    }
+    return aggregateHistory(this.history, bucketMs, now);
+  }
+
+  /**
+   * Extrapolates the drift and health trends of the update history
+   * `horizonMs` into the future, and estimates when drift will cross the
+   * threshold — so operators can be warned before it does.
+   *
+   * @param horizonMs How far ahead to forecast (ms).
+   * @param now       Unix timestamp (ms) to forecast from. Defaults to now.
+   * @returns         The forecast, or `null` until the history holds at
+   *                  least two updates.
+   */
+  forecast(horizonMs: number, now: number = this.now()): Forecast | null {
+    if (!(horizonMs >= 0) || horizonMs === Infinity) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `horizonMs must be a finite non-negative number, got ${horizonMs}`,
+        { horizonMs },
+      );
+    }
+    return forecastHistory(
+      this.history,
+      horizonMs,
+      this.driftScoreThreshold(),
+      now,
+    );
+  }
+
+  /**
+   * Estimates how long until health falls to `target` — e.g. to `0.5`, below
+   * which the summary turns `"volatile"` — both if no further update arrives
+   * and if the drift score keeps moving along the trend of the last updates
+   * (the range `healthSlope` is fitted over).
+   *
+   * @param target Health level in [0, 1].
+   * @param now    Unix timestamp (ms) to estimate from. Defaults to now.
+   * @throws {EngineError} `INVALID_CONFIG` if `target` is outside [0, 1].
+   */
+  timeToHealth(target: number, now: number = this.now()): HealthTimeEstimate {
+    if (!(target >= 0 && target <= 1)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `target must be in the range [0, 1], got ${target}`,
+        { target },
+      );
+    }
+    const ageDecayMs = timeUntilHealth(
+      (dt) => this.calculateHealth(now + dt),
+      target,
+    );
+    if (this.history.length < 2) return { ageDecayMs, driftTrendMs: null };
+    const { slope } = fitLine(
+      this.history.slice(-HEALTH_TREND_WINDOW),
+      (entry) => entry.driftScore,
+    );
+    const since = Math.max(0, now - this.lastUpdatedAt);
+    const driftTrendMs = timeUntilHealth(
+      (dt) =>
+        this.calculateHealth(
+          now + dt,
+          Math.max(0, this.lastDrift + slope * (since + dt)),
+        ),
+      target,
+    );
+    return { ageDecayMs, driftTrendMs };
+  }
+
+  /**
+   * Switches the fusion strategy used by subsequent updates. The current state
+   * vector and the window of recent embeddings are kept.
+   */
+  setFusionStrategy(strategy: FusionStrategy): void {
+    this.fusionStrategy = strategy;
+  }
+
+  /** Returns the base EMA α. */
+  getAlpha(): number {
+    return this.alpha;
+  }
+
+  /**
+   * Changes the base EMA α used by subsequent updates, keeping all state.
+   * Has no effect while {@link SemanticStateEngineConfig.adaptiveAlpha} is
+   * configured, which derives α from the drift instead.
+   */
+  setAlpha(alpha: number): void {
+    if (!(alpha > 0 && alpha <= 1)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `Alpha must be in the range (0, 1], got ${alpha}`,
+        { alpha },
+      );
+    }
+    this.alpha = alpha;
+  }
+
+  /** Returns the drift threshold. */
+  getDriftThreshold(): number {
+    return this.driftThreshold;
+  }
+
+  /**
+   * Changes the drift threshold used by subsequent updates, keeping all
+   * state — e.g. to tune it live from a dashboard.
+   */
+  setDriftThreshold(threshold: number): void {
+    if (!Number.isFinite(threshold)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `driftThreshold must be a finite number, got ${threshold}`,
+        { driftThreshold: threshold },
+      );
+    }
+    this.driftThreshold = threshold;
+  }
+
+  /** Returns the dimension mask as weights, or `null` when none is set. */
+  getDimensionMask(): number[] | null {
+    return this.dimensionMask === null ? null : [...this.dimensionMask];
+  }
+
+  /**
+   * Weights every incoming embedding component by `mask` — `false`/0 drops a
+   * dimension, `true`/1 keeps it, other non-negative weights scale it —
+   * before projection, drift measurement and fusion. Use it to exclude
+   * dimensions that should not count as drift, such as ones encoding the
+   * input language. `null` removes the mask.
+   *
+   * Applies to embeddings, references and anchors passed after the call: the
+   * current state keeps its masked components until they decay, so set the
+   * mask before the first update or follow it with {@link resetBaseline}.
+   *
+   * @throws {EngineError} `INVALID_CONFIG` if the mask is empty or holds a
+   *         negative or non-finite weight.
+   */
+  setDimensionMask(mask: ArrayLike<number | boolean> | null): void {
+    if (mask === null) {
+      this.dimensionMask = null;
+      return;
+    }
+    const weights = Array.from(mask, Number);
+    if (weights.length === 0) {
+      throw new EngineError("INVALID_CONFIG", "Dimension mask must not be empty");
+    }
+    const index = weights.findIndex((w) => !(w >= 0 && Number.isFinite(w)));
+    if (index !== -1) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `Dimension mask weights must be finite and non-negative, got ${weights[index]} at index ${index}`,
+        { index, weight: weights[index] },
+      );
+    }
+    this.dimensionMask = weights;
+  }
+
+  /** Returns a copy of the configured metadata (empty when none was given). */
+  getMetadata(): Record<string, string> {
+    return { ...this.metadata };
+  }
+
+  /** Returns the number of updates fused into the state so far. */
+  getUpdateCount(): number {
+    return this.updateCount;
+  }
+
+  /**
+   * Returns the embedding dimension the engine expects, or `null` if it is
+   * not yet established (no update, reference vector or configured
+   * {@link SemanticStateEngineConfig.dimension} yet). With a
+   * {@link SemanticStateEngineConfig.projection} this is the internal
+   * dimension embeddings are projected onto.
+   */
+  getDimension(): number | null {
+    return this.projection?.outputDimension ?? this.expectedDimension();
+  }
+
+  /**
+   * Registers (or replaces) a named reference vector — e.g. a known-good
+   * "golden" state. Updates and snapshots then report the state's cosine
+   * similarity to every reference. Subscribers are notified.
+   *
+   * With {@link SemanticStateEngineConfig.projection} configured, `vector`
+   * may have any dimension and is projected like an update.
+   *
+   * @throws {EngineError} `DIMENSION_MISMATCH` if `vector` does not match the
+   *         state, the configured dimension or the other references.
+   */
+  addReference(name: string, vector: ArrayLike<number>): void {
+    if (vector.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Reference vector must not be empty");
+    }
+    const reference = this.prepare(vector);
+    const others = [...this.references].filter(([key]) => key !== name);
+    const [anchor] = this.anchors.values();
+    const expected =
+      this.updateCount > 0
+        ? this.stateVector.length
+        : this.dimension ?? others[0]?.[1].length ?? anchor?.length;
+    if (expected !== undefined && reference.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Reference dimension mismatch: expected ${expected}, got ${reference.length}`,
+        { expected, actual: reference.length, name },
+      );
+    }
+    this.references.set(name, reference);
+    this.listeners.forEach((l) => l());
+  }
+
+  /**
+   * Unregisters a reference vector. Subscribers are notified if it existed.
+   *
+   * @returns `true` if a reference with that name was registered.
+   */
+  removeReference(name: string): boolean {
+    const removed = this.references.delete(name);
+    if (removed) this.listeners.forEach((l) => l());
+    return removed;
+  }
+
+  /** Returns the names of the registered reference vectors. */
+  getReferenceNames(): string[] {
+    return [...this.references.keys()];
+  }
+
+  /**
+   * Registers (or replaces) a labeled concept anchor — e.g. `"billing"`,
+   * `"support"` or `"spam"`. Every update then reports the anchor most
+   * similar to its embedding in {@link UpdateResult.anchor}, turning the
+   * engine into a lightweight semantic router. Anchors are included in
+   * {@link exportState} and survive {@link reset}. Subscribers are notified.
+   *
+   * @throws {EngineError} `DIMENSION_MISMATCH` if `vector` does not match the
+   *         state, the configured dimension, the references or the other
+   *         anchors.
+   */
+  addAnchor(label: string, vector: ArrayLike<number>): void {
+    if (vector.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Anchor vector must not be empty");
+    }
+    const anchor = this.prepare(vector);
+    const others = [...this.anchors].filter(([key]) => key !== label);
+    const [reference] = this.references.values();
+    const expected =
+      this.updateCount > 0
+        ? this.stateVector.length
+        : this.dimension ?? reference?.length ?? others[0]?.[1].length;
+    if (expected !== undefined && anchor.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Anchor dimension mismatch: expected ${expected}, got ${anchor.length}`,
+        { expected, actual: anchor.length, label },
+      );
+    }
+    this.anchors.set(label, anchor);
+    this.listeners.forEach((l) => l());
+  }
+
+  /**
+   * Unregisters a concept anchor. Subscribers are notified if it existed.
+   *
+   * @returns `true` if an anchor with that label was registered.
+   */
+  removeAnchor(label: string): boolean {
+    const removed = this.anchors.delete(label);
+    if (removed) this.listeners.forEach((l) => l());
+    return removed;
+  }
+
+  /** Returns the labels of the registered concept anchors. */
+  getAnchorLabels(): string[] {
+    return [...this.anchors.keys()];
+  }
+
+  /**
+   * Returns the drift-score threshold currently derived by
+   * {@link SemanticStateEngineConfig.autoThreshold}, or `null` when auto
+   * thresholding is disabled or still collecting its first `minSamples`
+   * drift scores (the fixed `driftThreshold` applies meanwhile).
+   */
+  getAutoThreshold(): number | null {
+    if (
+      this.driftQuantile === undefined ||
+      this.driftQuantile.count < this.autoThresholdMinSamples
+    ) {
+      return null;
+    }
+    return this.driftQuantile.value;
+  }
+
+  /**
+   * Updates the healthScore parameters. Omitted fields keep their current
+   * values. Subscribers are notified, since the reported health changes.
+   */
+  setHealthConfig(config: HealthConfig): void {
+    this.health = resolveHealthConfig(config, this.health);
+    this.listeners.forEach((l) => l());
+  }
+
+  /** Returns the healthScore parameters currently in effect. */
+  getHealthConfig(): ResolvedHealthConfig {
+    const { factors, ...rest } = this.health;
+    return {
+      ...rest,
+      factors: {
+        age: { ...factors.age },
+        drift: { ...factors.drift },
+        variance: { ...factors.variance },
+        updateRate: { ...factors.updateRate },
+      },
+    };
+  }
+
+  /**
+   * Fuses `embedding` into the current state vector using the active
+   * fusion strategy and returns the new state vector.
+   *
+   * For the EMA strategy the first embedding is blended with a zero-vector
+   * origin unless {@link InitMode} `"first"` is configured; the windowed
+   * strategies simply average what they have seen.
+   * `weight` scales the Kalman measurement confidence.
+   */
+  private fuse(embedding: number[], alpha: number, weight: number): number[] {
+    this.recentEmbeddings.push(this.pack(embedding));
+    if (this.recentEmbeddings.length > this.windowCapacity()) {
+      this.recentEmbeddings.shift();
+    }
+    const window = this.recentEmbeddings.slice(-this.fusionWindow).map(unpack);
+
+    switch (this.fusionStrategy) {
+      case "ema": {
+        if (this.updateCount === 0 && this.initMode === "first") {
+          return [...embedding];
+        }
+        const previous =
+          this.updateCount === 0
+            ? (new Array(embedding.length).fill(0) as number[])
+            : this.stateVector;
+        return emaFusion(embedding, previous, alpha);
+      }
+      case "sma":
+        return mean(window);
+      case "wma":
+        return weightedMean(
+          window,
+          window.map((_, i) => i + 1),
+        );
+      case "median":
+        return median(window);
+      case "cumulative": {
+        const n = this.updateCount + 1;
+        if (n === 1) return [...embedding];
+        return this.stateVector.map(
+          (val, i) => val + (embedding[i]! - val) / n,
+        );
+      }
+      case "kalman": {
+        if (this.updateCount === 0) {
+          // One measurement observed: the estimate is as noisy as it is.
+          this.kalmanVariance = embedding.map(() => this.measurementNoise);
+          return [...embedding];
+        }
+        const { estimate, variance } = kalmanUpdate(
+          this.stateVector,
+          this.kalmanVariance ?? embedding.map(() => this.measurementNoise),
+          embedding,
+          this.processNoise,
+          this.measurementNoise / weight,
+        );
+        this.kalmanVariance = variance;
+        return estimate;
+      }
+    }
+  }
+
+  /**
+   * The state vector, dequantized from the configured
+   * {@link SemanticStateEngineConfig.storagePrecision} on every read.
+   */
+  private get stateVector(): number[] {
+    return unpack(this.packedState);
+  }
+
+  private set stateVector(v: number[]) {
+    this.packedState = this.pack(v);
+  }
+
+  /** Stores `v` at the configured storage precision. */
+  private pack(v: number[]): StoredVector {
+    return this.storagePrecision === "f64"
+      ? v
+      : PackedVector.from(v, this.storagePrecision);
+  }
+
+  /**
+   * Maps a supplied vector into the engine's internal space: projected (if
+   * configured), then normalized per {@link NormalizationPolicy}.
+   */
+  private prepare(vector: ArrayLike<number>): number[] {
+    vector = this.applyMask(this.sanitize(vector));
+    const projected =
+      this.projection !== undefined
+        ? this.projection.project(vector)
+        : Array.from(vector);
+    switch (this.normalizeInputs) {
+      case "none":
+        return projected;
+      case "l2":
+        return normalize(projected);
+      case "zscore":
+        return standardize(projected);
+    }
+  }
+
+  /**
+   * Weights `vector` by the {@link setDimensionMask} mask, if any.
+   *
+   * @throws {EngineError} `DIMENSION_MISMATCH` if the lengths differ.
+   */
+  private applyMask(vector: ArrayLike<number>): ArrayLike<number> {
+    const mask = this.dimensionMask;
+    if (mask === null || vector.length === 0) return vector;
+    if (vector.length !== mask.length) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Embedding dimension mismatch: the dimension mask covers ${mask.length} dimensions, got ${vector.length}`,
+        { expected: mask.length, actual: vector.length },
+      );
+    }
+    return Array.from(vector, (x, i) => x * mask[i]!);
+  }
+
+  /**
+   * Applies the configured {@link NonFinitePolicy}. Returns `vector` itself
+   * when every component is finite.
+   */
+  private sanitize(vector: ArrayLike<number>): ArrayLike<number> {
+    let index = -1;
+    for (let i = 0; i < vector.length; i++) {
+      if (!Number.isFinite(vector[i]!)) {
+        index = i;
+        break;
+      }
+    }
+    if (index === -1) return vector;
+    switch (this.nonFinitePolicy) {
+      case "error":
+        throw new EngineError(
+          "NON_FINITE_EMBEDDING",
+          `Embedding component ${index} is ${vector[index]}`,
+          { index, value: vector[index] },
+        );
+      case "zero":
+        return Array.from(vector, (x) => (Number.isFinite(x) ? x : 0));
+      case "clamp":
+        return Array.from(vector, (x) =>
+          Number.isNaN(x)
+            ? 0
+            : Math.max(-Number.MAX_VALUE, Math.min(Number.MAX_VALUE, x)),
+        );
+    }
+  }
+
+  /**
+   * Applies the {@link DimensionChangePolicy} to an update of dimension `to`
+   * against a state of dimension `from`.
+   *
+   * @throws {EngineError} `DIMENSION_MISMATCH` under the `"error"` policy.
+   */
+  private changeDimension(from: number, to: number): DimensionChange {
+    switch (this.dimensionChangePolicy) {
+      case "error":
+        throw new EngineError(
+          "DIMENSION_MISMATCH",
+          `Embedding dimension mismatch: expected ${from}, got ${to}`,
+          { expected: from, actual: to },
+        );
+      case "reset":
+        this.clearState();
+        this.log("info", "reset", Date.now(), {
+          reason: "dimensionChange",
+          from,
+          to,
+        });
+        for (const [name, v] of this.references) {
+          if (v.length !== to) this.references.delete(name);
+        }
+        for (const [label, v] of this.anchors) {
+          if (v.length !== to) this.anchors.delete(label);
+        }
+        return { from, to, action: "reset" };
+      case "pad":
+        return { from, to, action: to < from ? "pad" : "truncate" };
+    }
+  }
+
+  /**
+   * Dimension every incoming embedding must have: that of the state once it
+   * is established, otherwise the configured `dimension` or that of the
+   * registered references, if any.
+   */
+  private expectedDimension(): number | null {
+    if (this.updateCount > 0) return this.stateVector.length;
+    if (this.dimension !== undefined) return this.dimension;
+    const [first] = this.references.values();
+    const [anchor] = this.anchors.values();
+    return first?.length ?? anchor?.length ?? null;
+  }
+
+  /**
+   * Returns a copy of the state vector decayed to `now` according to
+   * {@link SemanticStateEngineConfig.stateHalfLifeMs}.
+   */
+  private decayedState(now: number): number[] {
+    if (this.stateHalfLifeMs === undefined || this.updateCount === 0) {
+      return [...this.stateVector];
+    }
+    const elapsed = Math.max(0, now - this.lastUpdatedAt);
+    return scale(this.stateVector, 0.5 ** (elapsed / this.stateHalfLifeMs));
+  }
+
+  /** The anchor most similar to `embedding`, or `undefined` without anchors. */
+  private matchAnchor(embedding: number[]): AnchorMatch | undefined {
+    let best: AnchorMatch | undefined;
+    this.anchors.forEach((anchor, label) => {
+      const similarity = cosineSimilarity(embedding, anchor);
+      if (best === undefined || similarity > best.similarity) {
+        best = { label, similarity };
+      }
+    });
+    return best;
+  }
+
+  /** Cosine similarity between the state vector and each reference. */
+  private referenceSimilarities(): Record<string, number> {
+    const similarities: Record<string, number> = {};
+    this.references.forEach((reference, name) => {
+      similarities[name] = cosineSimilarity(this.stateVector, reference);
+    });
+    return similarities;
+  }
+
+  /**
+   * Whether `drift` lies more than the configured number of standard
+   * deviations above the mean of the recent drift scores. Never true before
+   * `minSamples` scores are available or while the scores have no spread.
+   */
+  private isOutlier(drift: number): boolean {
+    const { zScore, minSamples } = this.outliers!;
+    const n = this.outlierWindow.length;
+    if (n < minSamples) return false;
+    const mean = this.outlierWindow.reduce((sum, d) => sum + d, 0) / n;
+    const variance =
+      this.outlierWindow.reduce((sum, d) => sum + (d - mean) ** 2, 0) / (n - 1);
+    const std = Math.sqrt(variance);
+    return std > 0 && (drift - mean) / std > zScore;
+  }
+
+  /**
+   * Classifies a drift that is about to be reported, then remembers the
+   * concept being left so a later return to it can be recognized.
+   */
+  private classifyDetectedDrift(embedding: number[]): DriftType {
+    const recurring = this.pastConcepts.some(
+      (concept) => cosineSimilarity(concept, embedding) >= RECURRING_SIMILARITY,
+    );
+    const type = classifyDrift(this.driftWindowLog, recurring);
+
+    this.pastConcepts.push([...this.stateVector]);
+    if (this.pastConcepts.length > MAX_PAST_CONCEPTS) {
+      this.pastConcepts.shift();
+    }
+    return type;
+  }
+
+  /** Computes the drift score between `reference` and `embedding`. */
+  private measureDrift(
+    reference: number[],
+    embedding: number[],
+    similarity: number,
+  ): number {
+    switch (this.driftMetric) {
+      case "cosine":
+        return 1 - similarity;
+      case "euclidean":
+        return euclideanDistance(reference, embedding);
+      case "manhattan":
+        return manhattanDistance(reference, embedding);
+      case "angular":
+        return angularDistance(reference, embedding);
+      case "mahalanobis":
+        return mahalanobisDistance(
+          reference,
+          embedding,
+          this.mahalanobisVariance(embedding.length),
+        );
+      case "jensen-shannon":
+        return jsDivergence(embedding, reference);
+      case "kullback-leibler":
+        return klDivergence(embedding, reference);
+    }
+  }
+
+  /**
+   * Checks that `embedding` is a valid distribution under the divergence
+   * drift metrics.
+   *
+   * @throws {EngineError} `INVALID_DISTRIBUTION` if a component is negative
+   *         or every component is 0.
+   */
+  private checkDistribution(embedding: number[]): void {
+    if (
+      this.driftMetric !== "jensen-shannon" &&
+      this.driftMetric !== "kullback-leibler"
+    ) {
+      return;
+    }
+    const index = embedding.findIndex((x) => x < 0);
+    if (index !== -1) {
+      throw new EngineError(
+        "INVALID_DISTRIBUTION",
+        `The ${this.driftMetric} drift metric requires non-negative embeddings, got ${embedding[index]} at index ${index}`,
+        { index, value: embedding[index] },
+      );
+    }
+    if (embedding.every((x) => x === 0)) {
+      throw new EngineError(
+        "INVALID_DISTRIBUTION",
+        `The ${this.driftMetric} drift metric requires embeddings with a non-zero component`,
+      );
+    }
+  }
+
+  /**
+   * Per-dimension variance used by the `"mahalanobis"` metric: the running
+   * variance of the embeddings seen so far, floored, or all ones before two
+   * embeddings have been seen.
+   */
+  private mahalanobisVariance(dimension: number): number[] {
+    const stats = this.embeddingStats;
+    if (stats.count < 2) {
+      return new Array<number>(dimension).fill(1);
+    }
+    return stats.variance().map((v) => Math.max(v, MIN_MAHALANOBIS_VARIANCE));
+  }
+
+  /** The threshold in effect, expressed as a drift score. */
+  private driftScoreThreshold(): number {
+    const auto = this.getAutoThreshold();
+    if (auto !== null) return auto;
+    return this.driftMetric === "cosine"
+      ? 1 - this.driftThreshold
+      : this.driftThreshold;
+  }
+
+  /** Whether an update with this similarity / drift score counts as drift. */
+  private exceedsThreshold(
+    similarity: number,
+    drift: number,
+    override?: number,
+  ): boolean {
+    const auto = override === undefined ? this.getAutoThreshold() : null;
+    if (auto !== null) return drift > auto;
+    const threshold = override ?? this.driftThreshold;
+    return this.driftMetric === "cosine"
+      ? similarity < threshold
+      : drift > threshold;
+  }
+
+  /**
+   * Compares the magnitude of a raw embedding with the geometric mean of the
+   * recent magnitudes, restarting the baseline when it is flagged.
+   */
+  private detectInputShift(input: ArrayLike<number>): boolean {
+    if (this.inputShift === undefined) return false;
+    const logMagnitude = Math.log(magnitude(input));
+    if (!Number.isFinite(logMagnitude)) return false;
+    const { ratio, window, minSamples } = this.inputShift;
+    const log = this.magnitudeLog;
+    let shifted = false;
+    if (log.length >= minSamples) {
+      const baseline = log.reduce((sum, m) => sum + m, 0) / log.length;
+      shifted = Math.abs(logMagnitude - baseline) > Math.log(ratio);
+    }
+    if (shifted) {
+      this.magnitudeLog = [logMagnitude];
+    } else {
+      log.push(logMagnitude);
+      if (log.length > window) log.shift();
+    }
+    return shifted;
+  }
+
+  /**
+   * Cosine similarity between `embedding` and the recent embeddings, each
+   * weighted by 2^(−age / halfLifeMs); `undefined` without
+   * {@link SemanticStateEngineConfig.historySimilarity} or earlier embeddings.
+   * Ages are measured from the newest recent embedding rather than the
+   * update itself: cosine similarity ignores the overall scale, and a long
+   * pause then cannot underflow every weight to zero.
+   */
+  private compareWithRecent(embedding: number[]): number | undefined {
+    if (this.historySimilarity === undefined) return undefined;
+    if (this.decayWindow.length === 0) return undefined;
+    const { halfLifeMs } = this.historySimilarity;
+    const newest = Math.max(...this.decayWindow.map((e) => e.timestamp));
+    const weights = this.decayWindow.map(
+      (e) => 2 ** (-(newest - e.timestamp) / halfLifeMs),
+    );
+    const combined = weightedMean(
+      this.decayWindow.map((e) => e.vector),
+      weights,
+    );
+    return cosineSimilarity(combined, embedding);
+  }
+
+  /**
+   * Fuses `embedding` into every horizon state and returns the fast-vs-slow
+   * drift score; `undefined` without {@link SemanticStateEngineConfig.horizons}.
+   * Each horizon starts from the first embedding rather than from zero.
+   */
+  private updateHorizons(embedding: number[]): number | undefined {
+    if (this.horizons === undefined) return undefined;
+    for (const [name, alpha] of Object.entries(this.horizons.alphas)) {
+      const previous = this.horizonStates.get(name);
+      this.horizonStates.set(
+        name,
+        previous === undefined
+          ? [...embedding]
+          : emaFusion(embedding, previous, alpha),
+      );
+    }
+    return (
+      1 -
+      cosineSimilarity(
+        this.horizonStates.get(this.horizons.fast)!,
+        this.horizonStates.get(this.horizons.slow)!,
+      )
+    );
+  }
+
+  /** Appends a fused embedding to the window compared by {@link compareWithRecent}. */
+  private recordRecent(embedding: number[], timestamp: number): void {
+    if (this.historySimilarity === undefined) return;
+    this.decayWindow.push({ timestamp, vector: embedding });
+    if (this.decayWindow.length > this.historySimilarity.size) {
+      this.decayWindow.shift();
+    }
+  }
+
+  /**
+   * Adds the angle between the previous state and the freshly fused one to
+   * the rotation totals, and updates the angular velocity unless no time has
+   * passed since the previous update. A zero state has no direction, so
+   * steps from or to one are skipped.
+   */
+  private trackRotation(previous: number[], timestamp: number): void {
+    if (previous.length !== this.stateVector.length) return;
+    if (magnitude(previous) === 0 || magnitude(this.stateVector) === 0) return;
+    const angle = angularDistance(previous, this.stateVector) * Math.PI;
+    const elapsed = timestamp - this.lastUpdatedAt;
+    this.rotation.total += angle;
+    this.rotation.steps++;
+    if (elapsed > 0) this.rotation.velocity = (angle / elapsed) * 60_000;
+  }
 
   /**
-   * Optional callback invoked when the incoming embedding drifts beyond the threshold.
-   * Fired *before* the EMA fusion is applied.
-   *
-   * @param vector    The incoming embedding that triggered the drift.
-   * @param driftScore Drift magnitude: 1 − cosine_similarity ∈ [0, 2].
+   * The current time on the configured {@link ClockMode}. The monotonic
+   * clock never reads earlier than a previous reading or the last update.
    */
-  onDriftDetected?: (vector: number[], driftScore: number) => void;
+  private now(): number {
+    if (this.clock === "caller") return Date.now();
+    this.monotonicNow = Math.max(
+      this.monotonicNow,
+      this.lastUpdatedAt,
+      Date.now(),
+    );
+    return this.monotonicNow;
+  }
+
   /**
-   * The embedding provider used to obtain embedding vectors asynchronously.
-   * Any object implementing `getEmbedding(text: string): Promise<Float32Array | number[]>`
-   * satisfies this interface — including `WorkerManager`, or a custom OpenAI / Ollama wrapper.
+   * Applies the configured {@link ClockMode} and {@link TimestampPolicy} to
+   * an update's timestamp.
    */
-  provider: EmbeddingProvider;
+  private resolveTimestamp(timestamp: number): number {
+    if (this.clock === "monotonic") return this.now();
+    if (this.updateCount === 0 || timestamp >= this.lastUpdatedAt) {
+      return timestamp;
+    }
+    switch (this.timestampPolicy) {
+      case "accept":
+        return timestamp;
+      case "clamp":
+        return this.lastUpdatedAt;
+      case "error":
+        throw new EngineError(
+          "OUT_OF_ORDER_TIMESTAMP",
+          `Timestamp ${timestamp} precedes the previous update at ${this.lastUpdatedAt}`,
+          { timestamp, lastUpdatedAt: this.lastUpdatedAt },
+        );
+    }
+  }
 
   /**
-   * The name of the embedding model to use.
-   * Must match the modelName passed to the WorkerManager so the worker
-   * loads the correct model.
-   * @default "Xenova/all-MiniLM-L6-v2"
+   * Blends an update's similarity and drift score into the averages kept for
+   * {@link SemanticStateEngineConfig.driftSmoothing}, without storing them.
+   * Returns the inputs unchanged without smoothing or before the first
+   * measured drift.
    */
-  modelName?: string;
-}
+  private smoothDrift(
+    similarity: number,
+    drift: number,
+  ): { similarity: number; drift: number } {
+    const beta = this.driftSmoothing;
+    if (beta === undefined || this.smoothedDrift === null) {
+      return { similarity, drift };
+    }
+    return {
+      similarity: beta * similarity + (1 - beta) * this.smoothedDrift.similarity,
+      drift: beta * drift + (1 - beta) * this.smoothedDrift.drift,
+    };
+  }
 
-/**
- * A point-in-time snapshot of the current semantic state.
- */
-export interface Snapshot {
-  /** The current EMA state vector. */
-  vector: number[];
+  /** Whether an update also crossed {@link SemanticStateEngineConfig.criticalThreshold}. */
+  private exceedsCritical(similarity: number, drift: number): boolean {
+    if (this.criticalThreshold === undefined) return false;
+    return this.driftMetric === "cosine"
+      ? similarity < this.criticalThreshold
+      : drift > this.criticalThreshold;
+  }
 
-  /** Reliability indicator in [0, 1]. Degrades with age and high drift. */
-  healthScore: number;
+  /**
+   * Applies {@link SemanticStateEngineConfig.maxDriftEventsPerMinute} to a
+   * drift report at `timestamp`, and returns whether it must be suppressed.
+   */
+  private rateLimitDrift(timestamp: number): boolean {
+    if (this.maxDriftEventsPerMinute === undefined) return false;
+    this.driftReportTimes = this.driftReportTimes.filter(
+      (t) => timestamp - t < 60_000,
+    );
+    if (this.driftReportTimes.length >= this.maxDriftEventsPerMinute) {
+      this.suppressedDriftCount++;
+      return true;
+    }
+    this.driftReportTimes.push(timestamp);
+    return false;
+  }
 
-  /** Unix timestamp (ms) of the last state update. */
-  timestamp: number;
+  /**
+   * Applies hysteresis and cool-down to a raw threshold crossing and returns
+   * whether drift should be reported for this update.
+   */
+  private debounceDrift(thresholdExceeded: boolean, timestamp: number): boolean {
+    if (!thresholdExceeded) {
+      this.driftStreak = 0;
+      return false;
+    }
+    this.driftStreak++;
+    if (this.driftStreak < this.driftConsecutive) return false;
+    if (timestamp < this.driftCooldownUntil) return false;
 
-  /** Human-readable description of the current state quality. */
-  semanticSummary: string;
-}
+    this.driftStreak = 0;
+    this.driftCooldownUntil = timestamp + this.driftCooldownMs;
+    return true;
+  }
 
-/**
- * SemanticStateEngine tracks the implicit semantic intent of an event stream
- * using Exponential Moving Average (EMA) vector fusion.
- *
- * It fires an optional drift callback when incoming embeddings diverge
- * significantly from the current state, and exposes a healthScore that
- * degrades with both age and volatility.
- */
-export class SemanticStateEngine {
-  private readonly alpha: number;
-  private readonly driftThreshold: number;
-  private readonly onDriftDetected?: (
-    vector: number[],
-    driftScore: number,
-  ) => void;
-  private readonly provider: EmbeddingProvider;
-  readonly modelName: string;
+  /**
+   * Returns the EMA α for an update with the given drift score: the fixed
+   * α, or an interpolation between the adaptive bounds.
+   */
+  private effectiveAlpha(drift: number): number {
+    if (this.adaptiveAlpha === undefined) return this.alpha;
+    const { minAlpha, maxAlpha } = this.adaptiveAlpha;
+    const t = Math.max(0, Math.min(1, drift));
+    return minAlpha + (maxAlpha - minAlpha) * t;
+  }
 
-  private stateVector: number[];
-  private lastUpdatedAt: number;
-  private lastDrift: number;
-  private updateCount: number;
-  private readonly listeners = new Set<() => void>();
+  /**
+   * Returns the vector incoming embeddings are compared against for drift,
+   * according to the configured {@link DriftBaseline}.
+   */
+  private driftReference(state: number[] = this.stateVector): number[] {
+    if (this.driftBaseline === "window" && this.recentEmbeddings.length > 0) {
+      return mean(this.recentEmbeddings.slice(-this.driftWindow).map(unpack));
+    }
+    return state;
+  }
 
-  constructor(config: SemanticStateEngineConfig) {
-    this.alpha = config.alpha;
-    this.driftThreshold = config.driftThreshold;
-    this.onDriftDetected = config.onDriftDetected;
-    this.provider = config.provider;
-    this.modelName = config.modelName ?? "Xenova/all-MiniLM-L6-v2";
+  /** Number of recent embeddings that must be retained for fusion and drift. */
+  private windowCapacity(): number {
+    return this.driftBaseline === "window"
+      ? Math.max(this.fusionWindow, this.driftWindow)
+      : this.fusionWindow;
+  }
 
-    this.stateVector = [];
-    this.lastUpdatedAt = Date.now();
-    this.lastDrift = 0;
-    this.updateCount = 0;
+  /**
+   * Subscribes to state changes. Returns an unsubscribe function.
+   * The listener is called after every successful `update`, `updateEmbedding`,
+   * `updateBatch` (once per batch), and `importState`.
+   */
+  subscribe(listener: () => void): () => void {
+    this.listeners.add(listener);
+    return () => this.listeners.delete(listener);
   }
 
   /**
-   * Obtains an embedding for `text` from the WorkerManager and fuses it into
-   * the rolling semantic state using EMA.
+   * Subscribes to reported drift. The listener receives the
+   * {@link DriftEvent} — the same entry appended to the drift event log,
+   * even when `driftEventLogSize` is 0 — right after `onDriftDetected`,
+   * before the embedding is fused. Returns an unsubscribe function.
+   */
+  onDrift(listener: (event: DriftEvent) => void): () => void {
+    this.driftListeners.add(listener);
+    return () => this.driftListeners.delete(listener);
+  }
+
+  /**
+   * Returns a point-in-time snapshot of the current semantic state.
    *
-   * On the first call the embedding establishes the baseline.
-   * On subsequent calls, if the cosine similarity between the current state
-   * and the new embedding falls below {@link SemanticStateEngineConfig.driftThreshold},
-   * the {@link SemanticStateEngineConfig.onDriftDetected} callback is fired
-   * *before* the EMA fusion is applied.
+   * @param now Unix timestamp (ms) to evaluate state decay and health at.
+   *            Defaults to now.
+   */
+  getSnapshot(now: number = this.now()): Snapshot {
+    return { ...this.snapshotFields(now), vector: this.decayedState(now) };
+  }
+
+  /**
+   * Like {@link getSnapshot}, but returns the state vector as a `Float32Array`
+   * instead of a `number[]`. Cheaper for high-dimensional states and suitable
+   * for transferring to or from a Worker.
+   */
+  getSnapshotRaw(): RawSnapshot {
+    return {
+      ...this.snapshotFields(),
+      vector: Float32Array.from(this.decayedState(this.now())),
+    };
+  }
+
+  /**
+   * Like {@link getSnapshotRaw}, but returns the state vector as a
+   * `Float64Array`. The state is always accumulated in double precision, so
+   * this is lossless — use it when the `Float32Array` downcast of
+   * {@link getSnapshotRaw} would discard measurable precision.
+   */
+  getSnapshotRaw64(): RawSnapshot64 {
+    return {
+      ...this.snapshotFields(),
+      vector: Float64Array.from(this.decayedState(this.now())),
+    };
+  }
+
+  /**
+   * Like {@link getSnapshot}, but replaces the state vector with the compact
+   * representation configured by
+   * {@link SemanticStateEngineConfig.snapshotCompression} — e.g. for a
+   * dashboard that only renders a 2D projection of each engine.
    *
-   * @param text Raw text whose embedding will be fused into the state.
+   * @throws {EngineError} `NOT_INITIALIZED` if `snapshotCompression` is not
+   *         configured.
    */
-  async update(text: string): Promise<void> {
-    const raw = await this.provider.getEmbedding(text);
-    if (raw === null) {
-      return;
+  getSnapshotCompressed(): CompressedSnapshot {
+    const state = this.decayedState(this.now());
+    let compressed: CompressedVector;
+    if (this.compressionK !== undefined) {
+      compressed = { method: "topk", ...topK(state, this.compressionK) };
+    } else if (this.pca !== undefined) {
+      // A state restored with a dimension the components were not learned
+      // for has no meaningful projection yet.
+      compressed = {
+        method: "pca",
+        coordinates:
+          this.pca.dimension === state.length
+            ? this.pca.project(state)
+            : new Array<number>(this.pca.components).fill(0),
+        dimension: state.length,
+      };
+    } else {
+      throw new EngineError(
+        "NOT_INITIALIZED",
+        "getSnapshotCompressed() requires the snapshotCompression option",
+      );
     }
-    const embedding = Array.from(raw);
+    return { ...this.snapshotFields(), compressed };
+  }
 
-    if (this.updateCount === 0) {
-      // First call: establish baseline from a zero-vector origin.
-      const zero = new Array(embedding.length).fill(0) as number[];
-      this.stateVector = emaFusion(embedding, zero, this.alpha);
-      this.lastDrift = 0;
-    } else {
-      if (embedding.length !== this.stateVector.length) {
-        throw new Error(
-          `Embedding dimension mismatch: expected ${this.stateVector.length}, got ${embedding.length}`,
-        );
-      }
+  /**
+   * Projects `embedding` onto the two leading principal components of the
+   * embeddings fused so far, relative to their mean, for plotting it next
+   * to the trajectory of {@link getState2d}. Normalization and
+   * {@link SemanticStateEngineConfig.projection} apply first, as for an
+   * update. `[0, 0]` until an embedding has been fused.
+   *
+   * @throws {EngineError} `NOT_INITIALIZED` if `pca2d` is not configured, or
+   *         `DIMENSION_MISMATCH` if `embedding` does not match the state.
+   */
+  project2d(embedding: ArrayLike<number>): [number, number] {
+    if (embedding.length === 0) {
+      throw new EngineError("EMPTY_EMBEDDING", "Embedding must not be empty");
+    }
+    const candidate = this.prepare(embedding);
+    const expected = this.expectedDimension();
+    if (expected !== null && candidate.length !== expected) {
+      throw new EngineError(
+        "DIMENSION_MISMATCH",
+        `Embedding dimension mismatch: expected ${expected}, got ${candidate.length}`,
+        { expected, actual: candidate.length },
+      );
+    }
+    return this.to2d(candidate);
+  }
+
+  /**
+   * The current state vector in the 2D PCA space of {@link project2d}.
+   * Sampling it after every update traces the semantic trajectory.
+   *
+   * @throws {EngineError} `NOT_INITIALIZED` if `pca2d` is not configured.
+   */
+  getState2d(): [number, number] {
+    return this.to2d(this.decayedState(this.now()));
+  }
+
+  private to2d(v: number[]): [number, number] {
+    if (this.pca2d === undefined) {
+      throw new EngineError(
+        "NOT_INITIALIZED",
+        "2D projection requires the pca2d option",
+      );
+    }
+    if (this.pca2d.dimension !== v.length) return [0, 0];
+    const [x = 0, y = 0] = this.pca2d.project(v);
+    return [x, y];
+  }
+
+  /**
+   * Compares an earlier snapshot (of this or another engine) with the
+   * current state. Equivalent to `diffSnapshots(snapshot, this.getSnapshot())`.
+   */
+  compareTo(snapshot: SnapshotLike): SnapshotDiff {
+    return diffSnapshots(snapshot, this.getSnapshot());
+  }
+
+  /**
+   * Returns the current snapshot as an OpenTelemetry log record, with
+   * severity INFO, WARN or ERROR following the drift level of the most
+   * recent update. See {@link snapshotToOtel}.
+   */
+  getSnapshotOtel(): OtelLogRecord {
+    const level: DriftLevel = !this.exceedsThreshold(
+      this.lastSimilarity,
+      this.lastDrift,
+    )
+      ? "none"
+      : this.exceedsCritical(this.lastSimilarity, this.lastDrift)
+        ? "critical"
+        : "warning";
+    return snapshotToOtel(this.getSnapshot(), level, this.now());
+  }
+
+  /**
+   * Returns the recorded drift events as OpenTelemetry log records, oldest
+   * first. See {@link driftEventToOtel}.
+   */
+  getDriftEventsOtel(): OtelLogRecord[] {
+    return this.driftEvents.map(driftEventToOtel);
+  }
+
+  /**
+   * Renders the current health, drift score, similarity, update count and
+   * staleness as Prometheus gauges (text exposition format), labelled with
+   * the engine's {@link SemanticStateEngineConfig.metadata}. See
+   * {@link formatPrometheus}.
+   *
+   * @param prefix Metric name prefix.
+   */
+  exportPrometheus(prefix = "semantic_state"): string {
+    return formatPrometheus(
+      [{ labels: this.getMetadata(), snapshot: this.getSnapshot() }],
+      prefix,
+      this.now(),
+    );
+  }
 
-      const similarity = cosineSimilarity(this.stateVector, embedding);
-      const drift = 1 - similarity;
+  /** Builds every snapshot field except the state vector. */
+  private snapshotFields(now: number = this.now()): Omit<Snapshot, "vector"> {
+    const healthScore = this.calculateHealth(now);
+    const insufficientData = this.updateCount < this.minUpdatesForHealth;
+    const fields: Omit<Snapshot, "vector"> = {
+      healthScore,
+      timestamp: this.lastUpdatedAt,
+      lastDriftScore: this.lastDrift,
+      lastSimilarity: this.lastSimilarity,
+      updateCount: this.updateCount,
+      dimension: this.getDimension(),
+      semanticSummary: insufficientData
+        ? "insufficient data"
+        : this.buildSummary(healthScore),
+    };
+    if (insufficientData) fields.insufficientData = true;
+    if (this.kalmanVariance !== null && this.kalmanVariance.length > 0) {
+      fields.uncertainty =
+        this.kalmanVariance.reduce((sum, v) => sum + v, 0) /
+        this.kalmanVariance.length;
+    }
+    if (this.references.size > 0 && this.updateCount > 0) {
+      fields.referenceSimilarities = this.referenceSimilarities();
+    }
+    if (this.statsLog.length > 0) {
+      fields.driftStats = this.driftStats();
+    }
+    if (this.clusterer !== undefined && this.currentCluster !== null) {
+      fields.clusters = {
+        count: this.clusterer.count,
+        sizes: this.clusterer.getSizes(),
+        current: this.currentCluster,
+        switches: this.clusterSwitches,
+        churnRate:
+          this.clusterAssignments > 1
+            ? this.clusterSwitches / (this.clusterAssignments - 1)
+            : 0,
+      };
+    }
+    if (this.history.length >= 2) {
+      const recent = this.history.slice(-HEALTH_TREND_WINDOW);
+      const slope =
+        fitLine(recent, (entry) => entry.healthScore).slope * 60_000;
+      fields.healthSlope = slope;
+      fields.healthTrend =
+        slope > HEALTH_TREND_FLAT_SLOPE
+          ? "improving"
+          : slope < -HEALTH_TREND_FLAT_SLOPE
+            ? "degrading"
+            : "flat";
+      const drift = fitQuadratic(recent, (entry) => entry.driftScore);
+      const latest = recent[recent.length - 1]!.timestamp;
+      fields.driftVelocity = drift.slopeAt(latest) * 60_000;
+      if (recent.length >= 3) {
+        fields.driftAcceleration = drift.curvature * 60_000 ** 2;
+      }
+    }
+    if (this.embeddingStats.count >= 2) {
+      fields.embeddingStats = this.embeddingDispersion();
+    }
+    if (this.rotation.velocity !== null) {
+      fields.angularVelocity = this.rotation.velocity;
+    }
+    if (this.rotation.steps > 0) {
+      fields.cumulativeRotation = this.rotation.total;
+      const elapsed = this.lastUpdatedAt - this.rotation.since;
+      if (elapsed > 0) {
+        fields.meanAngularVelocity = (this.rotation.total / elapsed) * 60_000;
+      }
+    }
+    if (this.metadata !== undefined) {
+      fields.metadata = { ...this.metadata };
+    }
+    if (this.staleAfterMs !== undefined) {
+      const staleForMs = now - this.lastUpdatedAt - this.staleAfterMs;
+      fields.isStale = staleForMs > 0;
+      if (fields.isStale) fields.staleForMs = staleForMs;
+    }
+    return fields;
+  }
 
-      if (similarity < this.driftThreshold) {
-        this.onDriftDetected?.(embedding, drift);
+  /** Aggregates the running per-dimension variance of the embeddings. */
+  private embeddingDispersion(): EmbeddingStats {
+    const variances = this.embeddingStats.variance();
+    const total = variances.reduce((sum, v) => sum + v, 0);
+    const variance = total / variances.length;
+    let entropy = 0;
+    if (total > 0 && variances.length > 1) {
+      for (const v of variances) {
+        if (v > 0) entropy -= (v / total) * Math.log(v / total);
       }
+      entropy /= Math.log(variances.length);
+    }
+    return {
+      count: this.embeddingStats.count,
+      variance,
+      stdDev: Math.sqrt(variance),
+      entropy,
+    };
+  }
+
+  /** Aggregates the drift scores in the stats window. */
+  private driftStats(): DriftStats {
+    const scores = this.statsLog.map((e) => e.driftScore);
+    const count = scores.length;
+    const mean = scores.reduce((sum, d) => sum + d, 0) / count;
+    const variance = scores.reduce((sum, d) => sum + (d - mean) ** 2, 0) / count;
+    const span =
+      this.statsLog[count - 1]!.timestamp - this.statsLog[0]!.timestamp;
+    return {
+      count,
+      mean,
+      variance,
+      min: Math.min(...scores),
+      max: Math.max(...scores),
+      updatesPerMinute: span > 0 ? ((count - 1) * 60_000) / span : 0,
+    };
+  }
 
-      this.stateVector = emaFusion(embedding, this.stateVector, this.alpha);
-      this.lastDrift = drift;
+  /**
+   * Returns a new engine with this engine's configuration and a copy of its
+   * state, e.g. to run what-if simulations (say, with a different `alpha`)
+   * from the live state without disturbing it. Runtime changes made with
+   * {@link setAlpha}, {@link setDriftThreshold}, {@link setFusionStrategy}
+   * and {@link setHealthConfig} carry over unless overridden.
+   *
+   * Everything {@link exportState} covers is copied; subscribers,
+   * checkpoints and metrics are not, and internal estimator statistics
+   * (change detectors, auto threshold, clusters, online PCA) restart as after
+   * {@link importState}.
+   *
+   * @param overrides Configuration options replaced in the fork.
+   * @throws {EngineError} `INVALID_STATE` if the overrides are incompatible
+   *         with the current state (e.g. a different `dimension`).
+   */
+  fork(overrides: Partial<SemanticStateEngineConfig> = {}): SemanticStateEngine {
+    const fork = new SemanticStateEngine({
+      ...this.config,
+      health: this.getHealthConfig(),
+      ...overrides,
+    });
+    fork.restoreState(this.exportState());
+    fork.dimensionMask = this.dimensionMask;
+    if (overrides.alpha !== undefined) fork.alpha = overrides.alpha;
+    if (overrides.driftThreshold !== undefined) {
+      fork.driftThreshold = overrides.driftThreshold;
     }
+    if (overrides.fusionStrategy !== undefined) {
+      fork.fusionStrategy = overrides.fusionStrategy;
+    }
+    if (fork.trace !== null) fork.restartTrace();
+    return fork;
+  }
 
-    this.lastUpdatedAt = Date.now();
-    this.updateCount++;
+  /**
+   * Records the current state so {@link rollback} can return to it — e.g. to
+   * undo a batch of embeddings later found to be poisoned. Only the most
+   * recent {@link SemanticStateEngineConfig.maxCheckpoints} are retained.
+   *
+   * @returns The checkpoint's ID.
+   */
+  checkpoint(): number {
+    const id = this.nextCheckpointId++;
+    this.checkpoints.set(id, this.exportState());
+    if (this.checkpoints.size > this.maxCheckpoints) {
+      const oldest = this.checkpoints.keys().next().value as number;
+      this.checkpoints.delete(oldest);
+    }
+    return id;
+  }
+
+  /**
+   * Restores the state recorded by {@link checkpoint}, discarding every update
+   * fused since. The checkpoint is kept, so it can be rolled back to again.
+   * As with {@link importState}, internal estimator statistics (change
+   * detector, auto-threshold quantile, clusters, online PCA) restart.
+   * Subscribers are notified.
+   *
+   * @throws {EngineError} `CHECKPOINT_NOT_FOUND` if `id` was never issued or
+   *         has been evicted or released.
+   */
+  rollback(id: number): void {
+    const state = this.checkpoints.get(id);
+    if (state === undefined) {
+      throw new EngineError(
+        "CHECKPOINT_NOT_FOUND",
+        `No checkpoint with ID ${id}`,
+        { id },
+      );
+    }
+    this.clearState();
+    this.restoreState(state);
+    if (this.trace !== null) this.restartTrace();
     this.listeners.forEach((l) => l());
   }
 
+  /** Returns the IDs of the retained checkpoints, oldest first. */
+  getCheckpoints(): number[] {
+    return [...this.checkpoints.keys()];
+  }
+
   /**
-   * Subscribes to state changes. Returns an unsubscribe function.
-   * The listener is called after every successful `update`.
+   * Discards a checkpoint.
+   *
+   * @returns `true` if the checkpoint existed.
    */
-  subscribe(listener: () => void): () => void {
-    this.listeners.add(listener);
-    return () => this.listeners.delete(listener);
+  releaseCheckpoint(id: number): boolean {
+    return this.checkpoints.delete(id);
   }
 
   /**
-   * Returns a point-in-time snapshot of the current semantic state.
+   * Serializes the full internal state so it can be persisted (e.g. in
+   * IndexedDB) and later restored with {@link importState}.
    */
-  getSnapshot(): Snapshot {
-    const healthScore = this.calculateHealth();
+  exportState(): EngineState {
     return {
-      vector: [...this.stateVector],
-      healthScore,
-      timestamp: this.lastUpdatedAt,
-      semanticSummary: this.buildSummary(healthScore),
+      version: STATE_VERSION,
+      stateVector: [...this.stateVector],
+      alpha: this.alpha,
+      driftThreshold: this.driftThreshold,
+      updateCount: this.updateCount,
+      lastDrift: this.lastDrift,
+      lastSimilarity: this.lastSimilarity,
+      lastUpdatedAt: this.lastUpdatedAt,
+      fusionStrategy: this.fusionStrategy,
+      recentEmbeddings: this.recentEmbeddings.map((v) => [...unpack(v)]),
+      driftStreak: this.driftStreak,
+      driftCooldownUntil: this.driftCooldownUntil,
+      suppressUntil: this.suppressedUntil,
+      history: this.getHistory(),
+      kalmanVariance:
+        this.kalmanVariance === null ? null : [...this.kalmanVariance],
+      references: Object.fromEntries(
+        [...this.references].map(([name, v]) => [name, [...v]]),
+      ),
+      anchors: Object.fromEntries(
+        [...this.anchors].map(([label, v]) => [label, [...v]]),
+      ),
+      driftEvents: this.getDriftEvents(),
+      sources: Object.fromEntries(
+        [...this.sources].map(([name, v]) => [name, [...v]]),
+      ),
+      horizons: this.getHorizonStates(),
+      archive: this.archive.map((entry) => ({
+        ...entry,
+        vector: [...unpack(entry.vector)],
+      })),
+      embeddingSample: {
+        seen: this.sampleSeen,
+        entries: this.getEmbeddingSample(),
+      },
+      rotation: { ...this.rotation },
     };
   }
 
+  /**
+   * Restores a state previously produced by {@link exportState}, replacing the
+   * current state and configuration values it covers. Internal estimator
+   * statistics it does not cover restart. Subscribers are notified.
+   * States written by older releases are migrated to the current layout
+   * first (see {@link migrateState}).
+   *
+   * @throws {EngineError} `INVALID_STATE` if the supplied state is malformed
+   *         or was written by a newer release.
+   */
+  importState(state: EngineState): void {
+    this.restoreState(state);
+    if (this.trace !== null) this.restartTrace();
+    this.listeners.forEach((l) => l());
+  }
+
+  /**
+   * Validates an exported state, then discards all accumulated state and
+   * applies it, without notifying subscribers.
+   */
+  private restoreState(state: EngineState): void {
+    state = migrateState(state);
+    if (!Array.isArray(state.stateVector)) {
+      throw new EngineError(
+        "INVALID_STATE",
+        "Invalid engine state: stateVector must be an array",
+      );
+    }
+    if (state.alpha <= 0 || state.alpha > 1) {
+      throw new EngineError(
+        "INVALID_STATE",
+        `Alpha must be in the range (0, 1], got ${state.alpha}`,
+        { alpha: state.alpha },
+      );
+    }
+    if (!Number.isInteger(state.updateCount) || state.updateCount < 0) {
+      throw new EngineError(
+        "INVALID_STATE",
+        `Invalid engine state: updateCount must be a non-negative integer, got ${state.updateCount}`,
+        { updateCount: state.updateCount },
+      );
+    }
+    if (state.updateCount > 0 && state.stateVector.length === 0) {
+      throw new EngineError(
+        "INVALID_STATE",
+        "Invalid engine state: stateVector is empty but updateCount is non-zero",
+        { updateCount: state.updateCount },
+      );
+    }
+    if (
+      this.projection !== undefined &&
+      state.updateCount > 0 &&
+      state.stateVector.length !== this.projection.outputDimension
+    ) {
+      throw new EngineError(
+        "INVALID_STATE",
+        `Invalid engine state: stateVector has dimension ${state.stateVector.length} but the projection dimension is ${this.projection.outputDimension}`,
+        {
+          expected: this.projection.outputDimension,
+          actual: state.stateVector.length,
+        },
+      );
+    }
+
+    if (
+      this.dimension !== undefined &&
+      state.updateCount > 0 &&
+      state.stateVector.length !== this.dimension
+    ) {
+      throw new EngineError(
+        "INVALID_STATE",
+        `Invalid engine state: stateVector has dimension ${state.stateVector.length} but the engine is configured for dimension ${this.dimension}`,
+        { expected: this.dimension, actual: state.stateVector.length },
+      );
+    }
+
+    this.clearState();
+    this.stateVector = [...state.stateVector];
+    this.alpha = state.alpha;
+    this.driftThreshold = state.driftThreshold;
+    this.updateCount = state.updateCount;
+    this.lastDrift = state.lastDrift;
+    this.lastSimilarity = state.lastSimilarity ?? 1;
+    this.lastUpdatedAt = state.lastUpdatedAt;
+    this.fusionStrategy = state.fusionStrategy ?? this.fusionStrategy;
+    this.recentEmbeddings = (state.recentEmbeddings ?? [])
+      .slice(-this.windowCapacity())
+      .map((v) => this.pack([...v]));
+    this.driftStreak = state.driftStreak ?? 0;
+    this.driftCooldownUntil = state.driftCooldownUntil ?? 0;
+    this.suppressedUntil = state.suppressUntil ?? 0;
+    this.history =
+      this.historySize === 0
+        ? []
+        : (state.history ?? [])
+            .slice(-this.historySize)
+            .map((entry) => ({ ...entry }));
+    this.kalmanVariance = state.kalmanVariance
+      ? [...state.kalmanVariance]
+      : null;
+    this.driftEvents =
+      this.driftEventLogSize === 0
+        ? []
+        : (state.driftEvents ?? [])
+            .slice(-this.driftEventLogSize)
+            .map((event) => clonePlain(event));
+    if (state.references !== undefined) {
+      this.references = new Map(
+        Object.entries(state.references).map(([name, v]) => [name, [...v]]),
+      );
+    }
+    if (state.anchors !== undefined) {
+      this.anchors = new Map(
+        Object.entries(state.anchors).map(([label, v]) => [label, [...v]]),
+      );
+    }
+    this.sources = new Map(
+      Object.entries(state.sources ?? {}).map(([name, v]) => [name, [...v]]),
+    );
+    const horizons = this.horizons;
+    this.horizonStates = new Map(
+      horizons === undefined
+        ? []
+        : Object.entries(state.horizons ?? {})
+            .filter(([name]) => name in horizons.alphas)
+            .map(([name, v]) => [name, [...v]]),
+    );
+    this.archive =
+      this.archiveSize === 0
+        ? []
+        : (state.archive ?? [])
+            .slice(-this.archiveSize)
+            .map((entry) => ({ ...entry, vector: this.pack([...entry.vector]) }));
+    this.sampleSeen =
+      this.sampling === undefined ? 0 : (state.embeddingSample?.seen ?? 0);
+    this.sample =
+      this.sampling === undefined
+        ? []
+        : (state.embeddingSample?.entries ?? [])
+            .slice(-this.sampling.size)
+            .map((entry) => ({ ...entry, vector: this.pack([...entry.vector]) }));
+    this.rotation =
+      state.rotation === undefined
+        ? { total: 0, since: state.lastUpdatedAt, velocity: null, steps: 0 }
+        : { ...state.rotation };
+  }
+
+  /**
+   * Like {@link exportState}, but encodes the state in a compact binary
+   * format — far smaller and faster to parse than JSON for high-dimensional
+   * states. Restore it with {@link importStateBytes}.
+   *
+   * @param precision `"f64"` (default) is lossless; `"f32"` halves the size
+   *                  of the vectors at single precision.
+   */
+  exportStateBytes(precision: BinaryPrecision = "f64"): Uint8Array {
+    return encodeState(this.exportState(), precision);
+  }
+
+  /**
+   * Restores a state produced by {@link exportStateBytes}. Subscribers are
+   * notified.
+   *
+   * @throws {EngineError} `INVALID_STATE` if the bytes are malformed.
+   */
+  importStateBytes(bytes: Uint8Array): void {
+    this.importState(decodeState(bytes));
+  }
+
   /**
    * Computes the current healthScore.
    *
@@ -186,13 +5352,54 @@ export class SemanticStateEngine {
    * - An age penalty proportional to milliseconds elapsed since the last update.
    * - A drift penalty proportional to the most recent drift magnitude.
    *
-   * The result is clamped to [0, 1].
+   * The result is clamped to [floor, ceiling]; see {@link HealthConfig}.
+   *
+   * @param now Unix timestamp (ms) to evaluate health at. Defaults to now.
    */
-  private calculateHealth(): number {
-    const timeSinceUpdate = Date.now() - this.lastUpdatedAt;
-    const agePenalty = timeSinceUpdate * AGE_DECAY_RATE;
-    const driftPenalty = this.lastDrift * DRIFT_WEIGHT;
-    return Math.max(0, Math.min(1, 1.0 - agePenalty - driftPenalty));
+  private calculateHealth(
+    now: number = this.now(),
+    drift: number = this.lastDrift,
+  ): number {
+    const { factors, floor, ceiling } = this.health;
+    let penalty = 0;
+    for (const name of HEALTH_FACTORS) {
+      const { weight, curve } = factors[name];
+      if (weight === 0) continue;
+      const x = weight * this.healthMeasure(name, now, drift);
+      penalty += curve === "exponential" ? 1 - Math.exp(-x) : x;
+    }
+    return Math.max(floor, Math.min(ceiling, 1.0 - penalty));
+  }
+
+  /** The measurement a health factor is penalized by. */
+  private healthMeasure(
+    name: HealthFactorName,
+    now: number,
+    drift: number,
+  ): number {
+    switch (name) {
+      case "age":
+        return now - this.lastUpdatedAt;
+      case "drift":
+        return drift;
+      case "variance": {
+        const recent = this.history.slice(-HEALTH_FACTOR_WINDOW);
+        if (recent.length < 2) return 0;
+        const mean =
+          recent.reduce((sum, e) => sum + e.driftScore, 0) / recent.length;
+        return (
+          recent.reduce((sum, e) => sum + (e.driftScore - mean) ** 2, 0) /
+          recent.length
+        );
+      }
+      case "updateRate": {
+        const recent = this.history.slice(-HEALTH_FACTOR_WINDOW);
+        if (recent.length < 2) return 0;
+        const span =
+          recent[recent.length - 1]!.timestamp - recent[0]!.timestamp;
+        return span / (recent.length - 1);
+      }
+    }
   }
 
   private buildSummary(healthScore: number): string {
@@ -201,3 +5408,58 @@ export class SemanticStateEngine {
     return "volatile";
   }
 }
+
+/**
+ * Merges `config` over `base` and validates the result.
+ */
+function resolveHealthConfig(
+  config: HealthConfig,
+  base: ResolvedHealthConfig,
+): ResolvedHealthConfig {
+  const factor = (
+    name: HealthFactorName,
+    shorthand?: number,
+  ): Required<HealthFactor> => ({
+    weight:
+      config.factors?.[name]?.weight ?? shorthand ?? base.factors[name].weight,
+    curve: config.factors?.[name]?.curve ?? base.factors[name].curve,
+  });
+  const factors = {
+    age: factor("age", config.ageDecayRate),
+    drift: factor("drift", config.driftWeight),
+    variance: factor("variance"),
+    updateRate: factor("updateRate"),
+  };
+  const health = {
+    ageDecayRate: factors.age.weight,
+    driftWeight: factors.drift.weight,
+    floor: config.floor ?? base.floor,
+    ceiling: config.ceiling ?? base.ceiling,
+    factors,
+  };
+  for (const name of HEALTH_FACTORS) {
+    const { weight, curve } = factors[name];
+    if (!(weight >= 0)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `health factor weights must be non-negative, got ${name} weight ${weight}`,
+        { factor: name, weight },
+      );
+    }
+    if (curve !== "linear" && curve !== "exponential") {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `health factor curve must be "linear" or "exponential", got ${name} curve ${String(curve)}`,
+        { factor: name, curve },
+      );
+    }
+  }
+  if (!(0 <= health.floor && health.floor <= health.ceiling && health.ceiling <= 1)) {
+    throw new EngineError(
+      "INVALID_CONFIG",
+      `health bounds must satisfy 0 <= floor <= ceiling <= 1, got [${health.floor}, ${health.ceiling}]`,
+      { floor: health.floor, ceiling: health.ceiling },
+    );
+  }
+  return health;
+}
@@ -0,0 +1,34 @@
+import { describe, it, expect } from "vitest";
+import { aggregateHistory } from "./aggregate.js";
+
+const HOUR = 3_600_000;
+
+const history = [
+  { timestamp: 0.2 * HOUR, driftScore: 0.1, healthScore: 0.9 },
+  { timestamp: 0.7 * HOUR, driftScore: 0.3, healthScore: 0.7 },
+  { timestamp: 2.5 * HOUR, driftScore: 0.5, healthScore: 0.5 },
+  { timestamp: 3.1 * HOUR, driftScore: 0.2, healthScore: 0.8 },
+];
+
+describe("aggregateHistory", () => {
+  it("aggregates each epoch-aligned bucket", () => {
+    const buckets = aggregateHistory(history, HOUR, 4 * HOUR);
+    expect(buckets.map((b) => [b.start, b.count])).toEqual([
+      [0, 2],
+      [2 * HOUR, 1],
+      [3 * HOUR, 1],
+    ]);
+    expect(buckets[0]!.meanDrift).toBeCloseTo(0.2);
+    expect(buckets[0]!.maxDrift).toBe(0.3);
+    expect(buckets[0]!.meanHealth).toBeCloseTo(0.8);
+  });
+
+  it("ignores entries after now", () => {
+    const buckets = aggregateHistory(history, HOUR, 3 * HOUR);
+    expect(buckets.map((b) => b.start)).toEqual([0, 2 * HOUR]);
+  });
+
+  it("returns no buckets for an empty history", () => {
+    expect(aggregateHistory([], HOUR, 0)).toEqual([]);
+  });
+});
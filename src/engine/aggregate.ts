@@ -0,0 +1,67 @@
+import type { HistoryEntry } from "./SemanticStateEngine.js";
+
+/** Aggregates of the history entries in one time bucket, as returned by {@link aggregateHistory}. */
+export interface HistoryBucket {
+  /** Unix timestamp (ms) the bucket starts at, a multiple of the bucket size. */
+  start: number;
+
+  /** Number of updates in the bucket. */
+  count: number;
+
+  /** Mean drift score of the updates. */
+  meanDrift: number;
+
+  /** Largest drift score of the updates. */
+  maxDrift: number;
+
+  /** Mean post-update health of the updates. */
+  meanHealth: number;
+}
+
+/**
+ * Groups `history` into buckets of `bucketMs`, aligned to multiples of
+ * `bucketMs` since the Unix epoch (so hourly buckets start on the hour).
+ * Entries after `now` are ignored and buckets without updates are omitted.
+ *
+ * @param history  Update history, oldest first.
+ * @param bucketMs Bucket width (ms).
+ * @param now      Unix timestamp (ms) of the newest entry to include.
+ * @returns        The non-empty buckets, oldest first.
+ */
+export function aggregateHistory(
+  history: HistoryEntry[],
+  bucketMs: number,
+  now: number,
+): HistoryBucket[] {
+  const buckets = new Map<
+    number,
+    { count: number; driftSum: number; maxDrift: number; healthSum: number }
+  >();
+  for (const entry of history) {
+    if (entry.timestamp > now) continue;
+    const start = Math.floor(entry.timestamp / bucketMs) * bucketMs;
+    const bucket = buckets.get(start);
+    if (bucket === undefined) {
+      buckets.set(start, {
+        count: 1,
+        driftSum: entry.driftScore,
+        maxDrift: entry.driftScore,
+        healthSum: entry.healthScore,
+      });
+    } else {
+      bucket.count++;
+      bucket.driftSum += entry.driftScore;
+      bucket.maxDrift = Math.max(bucket.maxDrift, entry.driftScore);
+      bucket.healthSum += entry.healthScore;
+    }
+  }
+  return [...buckets]
+    .sort(([a], [b]) => a - b)
+    .map(([start, { count, driftSum, maxDrift, healthSum }]) => ({
+      start,
+      count,
+      meanDrift: driftSum / count,
+      maxDrift,
+      meanHealth: healthSum / count,
+    }));
+}
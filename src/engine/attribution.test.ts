@@ -0,0 +1,47 @@
+import { describe, it, expect } from "vitest";
+import { attributeDrift } from "./attribution.js";
+import { jsDivergence, klDivergence } from "../math/vector.js";
+
+describe("attributeDrift", () => {
+  it("ranks the dimensions that moved the most first", () => {
+    const top = attributeDrift([1, 0, 0, 0], [1, 0, 3, 1], "manhattan", 2);
+    expect(top).toEqual([
+      { index: 2, contribution: 3 },
+      { index: 3, contribution: 1 },
+    ]);
+  });
+
+  it("cosine contributions sum to 1 − cosine similarity", () => {
+    const all = attributeDrift([1, 0, 0], [0, 1, 0], "cosine", 3);
+    const total = all.reduce((sum, c) => sum + c.contribution, 0);
+    expect(total).toBeCloseTo(1);
+    expect(all.map((c) => c.index).sort()).toEqual([0, 1, 2]);
+  });
+
+  it("cosine contributions ignore pure magnitude changes", () => {
+    const all = attributeDrift([1, 2], [2, 4], "cosine", 2);
+    expect(all.every((c) => c.contribution < 1e-12)).toBe(true);
+  });
+
+  it("euclidean contributions sum to the squared distance", () => {
+    const all = attributeDrift([0, 0], [3, 4], "euclidean", 2);
+    expect(all[0]).toEqual({ index: 1, contribution: 16 });
+    expect(all.reduce((sum, c) => sum + c.contribution, 0)).toBeCloseTo(25);
+  });
+
+  it("divergence contributions sum to the divergence", () => {
+    const reference = [0.5, 0.3, 0.2, 0];
+    const embedding = [0.1, 0.3, 0.2, 0.4];
+    for (const [metric, divergence] of [
+      ["jensen-shannon", jsDivergence],
+      ["kullback-leibler", klDivergence],
+    ] as const) {
+      const all = attributeDrift(reference, embedding, metric, 4);
+      expect(all.every((c) => c.contribution >= 0)).toBe(true);
+      expect(all.reduce((sum, c) => sum + c.contribution, 0)).toBeCloseTo(
+        divergence(embedding, reference),
+      );
+      expect(all[0]!.index).toBe(3);
+    }
+  });
+});
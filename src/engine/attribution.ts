@@ -0,0 +1,75 @@
+import { jsTerm, normalize, toDistribution } from "../math/vector.js";
+import type { DriftMetric } from "./SemanticStateEngine.js";
+
+/** How much a single embedding dimension contributed to a drift score. */
+export interface DimensionContribution {
+  /** Index of the dimension within the embedding. */
+  index: number;
+
+  /** Non-negative share of the drift attributed to this dimension. */
+  contribution: number;
+}
+
+/**
+ * Decomposes the drift between `reference` and `embedding` into
+ * per-dimension contributions and returns the `k` largest, largest first.
+ *
+ * Contributions are chosen so they add up to the metric's drift score:
+ * - `"cosine"` / `"angular"`: (ê_i − r̂_i)² / 2 over the unit vectors, which
+ *   sums to 1 − cosine similarity.
+ * - `"euclidean"`: (e_i − r_i)², which sums to the squared L2 distance.
+ * - `"manhattan"`: |e_i − r_i|, which sums to the L1 distance.
+ * - `"mahalanobis"`: (e_i − r_i)² / σ²_i, which sums to the squared
+ *   Mahalanobis distance. `variance` supplies σ²; it defaults to all ones.
+ * - `"jensen-shannon"`: each component's term of the divergence between the
+ *   two distributions, which sums to it.
+ * - `"kullback-leibler"`: p_i ln(p_i / q_i) − p_i + q_i with p the
+ *   embedding and q the reference distribution, which is never negative and
+ *   sums to KL(p ‖ q) (up to the floor applied where q has no mass).
+ */
+export function attributeDrift(
+  reference: number[],
+  embedding: number[],
+  metric: DriftMetric,
+  k: number,
+  variance?: number[],
+): DimensionContribution[] {
+  let contributions: number[];
+  switch (metric) {
+    case "cosine":
+    case "angular": {
+      const r = normalize(reference);
+      const e = normalize(embedding);
+      contributions = e.map((val, i) => (val - r[i]!) ** 2 / 2);
+      break;
+    }
+    case "euclidean":
+      contributions = embedding.map((val, i) => (val - reference[i]!) ** 2);
+      break;
+    case "manhattan":
+      contributions = embedding.map((val, i) => Math.abs(val - reference[i]!));
+      break;
+    case "mahalanobis":
+      contributions = embedding.map(
+        (val, i) => (val - reference[i]!) ** 2 / (variance?.[i] ?? 1),
+      );
+      break;
+    case "jensen-shannon": {
+      const q = toDistribution(reference);
+      contributions = toDistribution(embedding).map((p, i) => jsTerm(p, q[i]!));
+      break;
+    }
+    case "kullback-leibler": {
+      const q = toDistribution(reference).map((x) => Math.max(x, 1e-10));
+      contributions = toDistribution(embedding).map((p, i) =>
+        Math.max(0, (p > 0 ? p * Math.log(p / q[i]!) : 0) - p + q[i]!),
+      );
+      break;
+    }
+  }
+
+  return contributions
+    .map((contribution, index) => ({ index, contribution }))
+    .sort((a, b) => b.contribution - a.contribution)
+    .slice(0, k);
+}
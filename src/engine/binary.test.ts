@@ -0,0 +1,58 @@
+import { describe, it, expect } from "vitest";
+import { decodeState, encodeState } from "./binary.js";
+import { EngineError } from "./errors.js";
+import type { EngineState } from "./SemanticStateEngine.js";
+
+const state: EngineState = {
+  stateVector: [0.1, -0.2, 0.3, 1 / 3],
+  alpha: 0.5,
+  driftThreshold: 0.75,
+  updateCount: 3,
+  lastDrift: 0.05,
+  lastUpdatedAt: 1_700_000_000_000,
+  fusionStrategy: "sma",
+  recentEmbeddings: [
+    [1, 0, 0, 0],
+    [0, 1, 0, 0],
+  ],
+  history: [{ timestamp: 1, driftScore: 0, healthScore: 1 }],
+  kalmanVariance: null,
+  references: { golden: [1, 0, 0, 0] },
+};
+
+describe("encodeState / decodeState", () => {
+  it("round-trips a state losslessly at f64 precision", () => {
+    expect(decodeState(encodeState(state))).toEqual(state);
+  });
+
+  it("rounds vectors to single precision at f32, keeping scalars exact", () => {
+    const decoded = decodeState(encodeState(state, "f32"));
+    expect(decoded.stateVector[3]).toBe(Math.fround(1 / 3));
+    expect(decoded.lastUpdatedAt).toBe(state.lastUpdatedAt);
+    expect(decoded.history).toEqual(state.history);
+  });
+
+  it("is much smaller than JSON for high-dimensional states", () => {
+    const big: EngineState = {
+      ...state,
+      stateVector: Array.from({ length: 1536 }, (_, i) => Math.sin(i) / 7),
+      recentEmbeddings: [],
+    };
+    const json = JSON.stringify(big).length;
+    expect(encodeState(big, "f32").length * 4).toBeLessThan(json);
+  });
+
+  it("rejects data without the expected header", () => {
+    expect(() => decodeState(new Uint8Array([1, 2, 3]))).toThrow(EngineError);
+    const bytes = encodeState(state);
+    bytes[4] = 99;
+    expect(() => decodeState(bytes)).toThrow(/unsupported format version 99/);
+  });
+
+  it("rejects truncated data", () => {
+    const bytes = encodeState(state);
+    expect(() => decodeState(bytes.subarray(0, bytes.length - 3))).toThrow(
+      /truncated/,
+    );
+  });
+});
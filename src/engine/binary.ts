@@ -0,0 +1,129 @@
+import { EngineError } from "./errors.js";
+import type { EngineState } from "./SemanticStateEngine.js";
+
+/**
+ * Compact binary encoding of {@link EngineState}.
+ *
+ * Layout (all integers little-endian):
+ *
+ * | bytes  | content                                              |
+ * |--------|------------------------------------------------------|
+ * | 0–3    | magic `"SSEB"`                                       |
+ * | 4      | format version (1)                                   |
+ * | 5      | bytes per float: 4 (`"f32"`) or 8 (`"f64"`)          |
+ * | 6–7    | reserved (0)                                         |
+ * | 8–11   | byte length H of the JSON header                     |
+ * | 12…    | UTF-8 JSON header, then the float block              |
+ *
+ * Every array of numbers in the state (state vector, recent embeddings,
+ * Kalman variance, references, …) is moved into the float block and replaced
+ * in the header by `{ "$f": [offset, length] }`; everything else stays JSON.
+ */
+
+/** Float width used for the vectors in a binary state. */
+export type BinaryPrecision = "f32" | "f64";
+
+const MAGIC = [0x53, 0x53, 0x45, 0x42]; // "SSEB"
+const FORMAT_VERSION = 1;
+const PREAMBLE_BYTES = 12;
+
+/** Encodes `state` into the binary format described above. */
+export function encodeState(
+  state: EngineState,
+  precision: BinaryPrecision = "f64",
+): Uint8Array {
+  const floats: number[] = [];
+  const pack = (value: unknown): unknown => {
+    if (Array.isArray(value)) {
+      if (value.length > 0 && value.every((v) => typeof v === "number")) {
+        const ref = { $f: [floats.length, value.length] };
+        floats.push(...(value as number[]));
+        return ref;
+      }
+      return value.map(pack);
+    }
+    if (typeof value === "object" && value !== null) {
+      return Object.fromEntries(
+        Object.entries(value).map(([key, v]) => [key, pack(v)]),
+      );
+    }
+    return value;
+  };
+  const header = new TextEncoder().encode(JSON.stringify(pack(state)));
+
+  const width = precision === "f32" ? 4 : 8;
+  const bytes = new Uint8Array(PREAMBLE_BYTES + header.length + floats.length * width);
+  const view = new DataView(bytes.buffer);
+  bytes.set(MAGIC, 0);
+  view.setUint8(4, FORMAT_VERSION);
+  view.setUint8(5, width);
+  view.setUint32(8, header.length, true);
+  bytes.set(header, PREAMBLE_BYTES);
+
+  let offset = PREAMBLE_BYTES + header.length;
+  for (const f of floats) {
+    if (width === 4) view.setFloat32(offset, f, true);
+    else view.setFloat64(offset, f, true);
+    offset += width;
+  }
+  return bytes;
+}
+
+/**
+ * Decodes a state produced by {@link encodeState}.
+ *
+ * @throws {EngineError} `INVALID_STATE` if the bytes are not a valid binary state.
+ */
+export function decodeState(bytes: Uint8Array): EngineState {
+  const invalid = (reason: string): never => {
+    throw new EngineError("INVALID_STATE", `Invalid binary engine state: ${reason}`);
+  };
+  if (bytes.length < PREAMBLE_BYTES || MAGIC.some((b, i) => bytes[i] !== b)) {
+    invalid("missing SSEB header");
+  }
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+  const version = view.getUint8(4);
+  if (version !== FORMAT_VERSION) {
+    invalid(`unsupported format version ${version}`);
+  }
+  const width = view.getUint8(5);
+  if (width !== 4 && width !== 8) {
+    invalid(`unsupported float width ${width}`);
+  }
+  const headerLength = view.getUint32(8, true);
+  const floatStart = PREAMBLE_BYTES + headerLength;
+  if (floatStart > bytes.length || (bytes.length - floatStart) % width !== 0) {
+    invalid("truncated data");
+  }
+
+  let header: unknown;
+  try {
+    header = JSON.parse(
+      new TextDecoder().decode(bytes.subarray(PREAMBLE_BYTES, floatStart)),
+    );
+  } catch {
+    invalid("malformed header");
+  }
+
+  const floatCount = (bytes.length - floatStart) / width;
+  const readFloat = (i: number) =>
+    width === 4
+      ? view.getFloat32(floatStart + i * 4, true)
+      : view.getFloat64(floatStart + i * 8, true);
+  const unpack = (value: unknown): unknown => {
+    if (Array.isArray(value)) return value.map(unpack);
+    if (typeof value === "object" && value !== null) {
+      const ref = (value as { $f?: unknown }).$f;
+      if (Array.isArray(ref)) {
+        const [start, length] = ref as [number, number];
+        if (start + length > floatCount) invalid("vector out of bounds");
+        return Array.from({ length }, (_, i) => readFloat(start + i));
+      }
+      return Object.fromEntries(
+        Object.entries(value).map(([key, v]) => [key, unpack(v)]),
+      );
+    }
+    return value;
+  };
+  return unpack(header) as EngineState;
+}
@@ -0,0 +1,114 @@
+import { describe, it, expect } from "vitest";
+import { parseEngineConfig } from "./config.js";
+import { EngineError } from "./errors.js";
+
+const messageOf = (fn: () => unknown): string => {
+  try {
+    fn();
+  } catch (err) {
+    expect(err).toBeInstanceOf(EngineError);
+    expect((err as EngineError).code).toBe("INVALID_CONFIG");
+    return (err as EngineError).message;
+  }
+  throw new Error("expected an error");
+};
+
+describe("parseEngineConfig", () => {
+  it("accepts a complete configuration object", () => {
+    const config = {
+      alpha: 0.3,
+      driftThreshold: 0.8,
+      fusionStrategy: "kalman",
+      driftMetric: "angular",
+      historySize: 50,
+      kalman: { processNoise: 0.002 },
+      changeDetector: { type: "cusum", threshold: 0.4 },
+      health: { ageDecayRate: 0 },
+    };
+    expect(parseEngineConfig(config)).toEqual(config);
+  });
+
+  it("parses JSON strings", () => {
+    expect(parseEngineConfig('{"alpha":0.5,"driftThreshold":0.7}')).toEqual({
+      alpha: 0.5,
+      driftThreshold: 0.7,
+    });
+    expect(messageOf(() => parseEngineConfig("{alpha"))).toMatch(
+      /not valid JSON/,
+    );
+  });
+
+  it("names missing required options", () => {
+    expect(messageOf(() => parseEngineConfig({ alpha: 0.5 }))).toBe(
+      "config.driftThreshold is required",
+    );
+  });
+
+  it("names mistyped options, including nested ones", () => {
+    expect(
+      messageOf(() =>
+        parseEngineConfig({
+          alpha: 0.5,
+          driftThreshold: 0.7,
+          kalman: { processNoise: "0.1" },
+        }),
+      ),
+    ).toBe('config.kalman.processNoise must be a number, got "0.1"');
+  });
+
+  it("checks every value of a record option", () => {
+    expect(
+      messageOf(() =>
+        parseEngineConfig({
+          alpha: 0.5,
+          driftThreshold: 0.7,
+          metadata: { tenant: "acme", shard: 3 },
+        }),
+      ),
+    ).toBe("config.metadata.shard must be a string, got 3");
+  });
+
+  it("rejects unknown options and enum values", () => {
+    expect(
+      messageOf(() =>
+        parseEngineConfig({ alpha: 0.5, driftThreshold: 0.7, alhpa: 0.5 }),
+      ),
+    ).toMatch(/^config\.alhpa is not a recognized option/);
+    expect(
+      messageOf(() =>
+        parseEngineConfig({
+          alpha: 0.5,
+          driftThreshold: 0.7,
+          driftMetric: "hamming",
+        }),
+      ),
+    ).toMatch(/^config\.driftMetric must be one of "cosine"/);
+  });
+
+  it("validates change detector options against their type", () => {
+    expect(
+      messageOf(() =>
+        parseEngineConfig({
+          alpha: 0.5,
+          driftThreshold: 0.7,
+          changeDetector: { type: "cusum", lambda: 1 },
+        }),
+      ),
+    ).toMatch(/^config\.changeDetector\.lambda is not a recognized option/);
+    expect(
+      messageOf(() =>
+        parseEngineConfig({
+          alpha: 0.5,
+          driftThreshold: 0.7,
+          changeDetector: { type: "bocpd" },
+        }),
+      ),
+    ).toMatch(/^config\.changeDetector\.type must be one of/);
+  });
+
+  it("rejects non-object configurations", () => {
+    expect(messageOf(() => parseEngineConfig([]))).toBe(
+      "config must be an object, got []",
+    );
+  });
+});
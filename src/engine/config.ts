@@ -0,0 +1,301 @@
+import { EngineError } from "./errors.js";
+import type { SemanticStateEngineConfig } from "./SemanticStateEngine.js";
+
+/**
+ * The JSON-serializable part of {@link SemanticStateEngineConfig}: every
+ * option except the provider and callbacks, which are supplied separately to
+ * {@link SemanticStateEngine.fromConfig}.
+ */
+export type SerializableEngineConfig = Omit<
+  SemanticStateEngineConfig,
+  "provider" | "onDriftDetected" | "logger"
+>;
+
+/** The non-serializable options accepted alongside a {@link SerializableEngineConfig}. */
+export type EngineBindings = Pick<
+  SemanticStateEngineConfig,
+  "provider" | "onDriftDetected" | "logger"
+>;
+
+type FieldSpec =
+  | { kind: "number"; required?: boolean }
+  | { kind: "string" }
+  | { kind: "boolean" }
+  | { kind: "enum"; values: readonly string[] }
+  | { kind: "object"; fields: Record<string, FieldSpec> }
+  | { kind: "record"; values: FieldSpec }
+  | { kind: "tagged"; tag: string; variants: Record<string, Record<string, FieldSpec>> };
+
+const num: FieldSpec = { kind: "number" };
+
+const healthFactor: FieldSpec = {
+  kind: "object",
+  fields: { weight: num, curve: { kind: "enum", values: ["linear", "exponential"] } },
+};
+
+/**
+ * Schema of every serializable option. Typed against the config interface so
+ * that adding an option without describing it here fails to compile.
+ */
+const FIELDS: Record<keyof SerializableEngineConfig, FieldSpec> = {
+  alpha: { kind: "number", required: true },
+  driftThreshold: { kind: "number", required: true },
+  criticalThreshold: num,
+  dimension: num,
+  fusionStrategy: {
+    kind: "enum",
+    values: ["ema", "sma", "wma", "cumulative", "median", "kalman"],
+  },
+  fusionWindow: num,
+  driftBaseline: { kind: "enum", values: ["state", "window"] },
+  driftWindow: num,
+  modelName: { kind: "string" },
+  adaptiveAlpha: {
+    kind: "object",
+    fields: {
+      minAlpha: { kind: "number", required: true },
+      maxAlpha: { kind: "number", required: true },
+    },
+  },
+  driftMetric: {
+    kind: "enum",
+    values: [
+      "cosine",
+      "euclidean",
+      "manhattan",
+      "angular",
+      "mahalanobis",
+      "jensen-shannon",
+      "kullback-leibler",
+    ],
+  },
+  driftConsecutive: num,
+  driftCooldownMs: num,
+  driftSmoothing: num,
+  historySize: num,
+  kalman: {
+    kind: "object",
+    fields: { processNoise: num, measurementNoise: num },
+  },
+  changeDetector: {
+    kind: "tagged",
+    tag: "type",
+    variants: {
+      "page-hinkley": { delta: num, lambda: num },
+      cusum: { slack: num, threshold: num },
+      adwin: { delta: num, maxWindow: num, minSubWindow: num },
+    },
+  },
+  attributionTopK: num,
+  driftDirection: { kind: "boolean" },
+  health: {
+    kind: "object",
+    fields: {
+      ageDecayRate: num,
+      driftWeight: num,
+      floor: num,
+      ceiling: num,
+      factors: {
+        kind: "object",
+        fields: {
+          age: healthFactor,
+          drift: healthFactor,
+          variance: healthFactor,
+          updateRate: healthFactor,
+        },
+      },
+    },
+  },
+  autoThreshold: {
+    kind: "object",
+    fields: { quantile: num, minSamples: num },
+  },
+  stateHalfLifeMs: num,
+  warmupUpdates: num,
+  initMode: { kind: "enum", values: ["zero", "first"] },
+  timestampPolicy: { kind: "enum", values: ["accept", "clamp", "error"] },
+  clock: { kind: "enum", values: ["caller", "monotonic"] },
+  nonFinitePolicy: { kind: "enum", values: ["error", "zero", "clamp"] },
+  resultDetail: { kind: "enum", values: ["minimal", "standard", "full"] },
+  dimensionChangePolicy: { kind: "enum", values: ["error", "reset", "pad"] },
+  classifyDrift: { kind: "boolean" },
+  outliers: {
+    kind: "object",
+    fields: {
+      zScore: num,
+      window: num,
+      minSamples: num,
+      reject: { kind: "boolean" },
+    },
+  },
+  statsWindow: num,
+  driftEventLogSize: num,
+  projection: {
+    kind: "object",
+    fields: { dimension: { kind: "number", required: true }, seed: num },
+  },
+  clustering: {
+    kind: "object",
+    fields: { k: num, newClusterSimilarity: num },
+  },
+  snapshotCompression: {
+    kind: "tagged",
+    tag: "method",
+    variants: {
+      topk: { k: num },
+      pca: { components: num, learningRate: num },
+    },
+  },
+  pca2d: { kind: "object", fields: { learningRate: num } },
+  recordTrace: { kind: "boolean" },
+  staleAfterMs: num,
+  normalizeInputs: { kind: "enum", values: ["none", "l2", "zscore"] },
+  maxDriftEventsPerMinute: num,
+  maxCheckpoints: num,
+  stateArchive: {
+    kind: "object",
+    fields: { interval: num, size: num },
+  },
+  embeddingSample: {
+    kind: "object",
+    fields: {
+      size: num,
+      method: { kind: "enum", values: ["reservoir", "stride"] },
+      stride: num,
+      seed: num,
+    },
+  },
+  metadata: { kind: "record", values: { kind: "string" } },
+  inputShift: {
+    kind: "object",
+    fields: { ratio: num, window: num, minSamples: num },
+  },
+  minUpdatesForHealth: num,
+  historySimilarity: {
+    kind: "object",
+    fields: { size: num, halfLifeMs: num },
+  },
+  horizons: {
+    kind: "object",
+    fields: { alphas: { kind: "record", values: num }, threshold: num },
+  },
+  storagePrecision: { kind: "enum", values: ["f64", "f32", "f16", "int8"] },
+};
+
+/**
+ * Validates a plain JSON value (or JSON string) against the engine's
+ * configuration schema and returns it typed.
+ *
+ * Only shapes and types are checked here — unknown keys, missing required
+ * options, wrong types and unknown enum values. Range checks are left to the
+ * engine constructor.
+ *
+ * @throws {EngineError} `INVALID_CONFIG` naming the offending path, e.g.
+ *         `config.kalman.processNoise must be a number, got "0.1"`.
+ */
+export function parseEngineConfig(input: unknown): SerializableEngineConfig {
+  let value = input;
+  if (typeof input === "string") {
+    try {
+      value = JSON.parse(input);
+    } catch (err) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `config is not valid JSON: ${err instanceof Error ? err.message : String(err)}`,
+      );
+    }
+  }
+  checkObject(value, FIELDS, "config");
+  return value as SerializableEngineConfig;
+}
+
+function checkObject(
+  value: unknown,
+  fields: Record<string, FieldSpec>,
+  path: string,
+): void {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    fail(path, "an object", value);
+  }
+  const record = value as Record<string, unknown>;
+  for (const key of Object.keys(record)) {
+    if (!(key in fields)) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `${path}.${key} is not a recognized option (expected one of: ${Object.keys(fields).join(", ")})`,
+        { path: `${path}.${key}` },
+      );
+    }
+  }
+  for (const [key, spec] of Object.entries(fields)) {
+    const field = record[key];
+    const fieldPath = `${path}.${key}`;
+    if (field === undefined) {
+      if (spec.kind === "number" && spec.required) {
+        throw new EngineError("INVALID_CONFIG", `${fieldPath} is required`, {
+          path: fieldPath,
+        });
+      }
+      continue;
+    }
+    checkField(field, spec, fieldPath);
+  }
+}
+
+function checkField(value: unknown, spec: FieldSpec, path: string): void {
+  switch (spec.kind) {
+    case "number":
+      if (typeof value !== "number") fail(path, "a number", value);
+      return;
+    case "string":
+      if (typeof value !== "string") fail(path, "a string", value);
+      return;
+    case "boolean":
+      if (typeof value !== "boolean") fail(path, "a boolean", value);
+      return;
+    case "enum":
+      if (typeof value !== "string" || !spec.values.includes(value)) {
+        fail(path, `one of ${spec.values.map((v) => `"${v}"`).join(", ")}`, value);
+      }
+      return;
+    case "object":
+      checkObject(value, spec.fields, path);
+      return;
+    case "record":
+      if (typeof value !== "object" || value === null || Array.isArray(value)) {
+        fail(path, "an object", value);
+      }
+      for (const [key, v] of Object.entries(value as Record<string, unknown>)) {
+        checkField(v, spec.values, `${path}.${key}`);
+      }
+      return;
+    case "tagged": {
+      const tag =
+        typeof value === "object" && value !== null
+          ? (value as Record<string, unknown>)[spec.tag]
+          : undefined;
+      const variants = Object.keys(spec.variants);
+      if (typeof tag !== "string" || !variants.includes(tag)) {
+        fail(
+          `${path}.${spec.tag}`,
+          `one of ${variants.map((v) => `"${v}"`).join(", ")}`,
+          tag,
+        );
+      }
+      checkObject(
+        value,
+        { [spec.tag]: { kind: "string" }, ...spec.variants[tag]! },
+        path,
+      );
+      return;
+    }
+  }
+}
+
+function fail(path: string, expected: string, actual: unknown): never {
+  throw new EngineError(
+    "INVALID_CONFIG",
+    `${path} must be ${expected}, got ${JSON.stringify(actual) ?? String(actual)}`,
+    { path },
+  );
+}
@@ -0,0 +1,62 @@
+import { describe, it, expect } from "vitest";
+import { compareEngines, diffSnapshots, type SnapshotLike } from "./diff.js";
+import { EngineError } from "./errors.js";
+
+const snap = (
+  vector: number[],
+  healthScore = 1,
+  timestamp = 0,
+): SnapshotLike => ({
+  vector,
+  healthScore,
+  timestamp,
+  lastDriftScore: 0,
+  lastSimilarity: 1,
+  updateCount: 1,
+  dimension: vector.length,
+  semanticSummary: "stable",
+});
+
+describe("diffSnapshots", () => {
+  it("reports similarity, distance and per-dimension delta statistics", () => {
+    const diff = diffSnapshots(snap([1, 0, 0, 0]), snap([1, 0, 3, -1]));
+    expect(diff.similarity).toBeCloseTo(1 / Math.sqrt(11));
+    expect(diff.euclideanDistance).toBeCloseTo(Math.sqrt(10));
+    expect(diff.delta.mean).toBeCloseTo(0.5);
+    expect(diff.delta.meanAbs).toBeCloseTo(1);
+    expect(diff.delta.std).toBeCloseTo(Math.sqrt(10 / 4 - 0.25));
+    expect(diff.delta.maxAbs).toBe(3);
+    expect(diff.delta.maxAbsIndex).toBe(2);
+  });
+
+  it("reports health and time deltas as b − a", () => {
+    const diff = diffSnapshots(snap([1, 0], 0.9, 1000), snap([1, 0], 0.6, 4000));
+    expect(diff.healthDelta).toBeCloseTo(-0.3);
+    expect(diff.timeDelta).toBe(3000);
+    expect(diff.similarity).toBeCloseTo(1);
+  });
+
+  it("accepts typed-array vectors", () => {
+    const diff = diffSnapshots(
+      { ...snap([]), vector: new Float32Array([0, 1]) },
+      snap([0, 1]),
+    );
+    expect(diff.euclideanDistance).toBe(0);
+  });
+
+  it("rejects snapshots of different dimensions", () => {
+    expect(() => diffSnapshots(snap([1, 0]), snap([1, 0, 0]))).toThrow(
+      EngineError,
+    );
+  });
+});
+
+describe("compareEngines", () => {
+  it("diffs the engines' current snapshots", () => {
+    const staging = { getSnapshot: () => snap([1, 0, 0, 0], 0.9) };
+    const production = { getSnapshot: () => snap([0, 1, 0, 0], 0.6) };
+    const diff = compareEngines(staging, production);
+    expect(diff.similarity).toBe(0);
+    expect(diff.healthDelta).toBeCloseTo(-0.3);
+  });
+});
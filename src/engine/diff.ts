@@ -0,0 +1,107 @@
+import { cosineSimilarity, euclideanDistance } from "../math/vector.js";
+import { EngineError } from "./errors.js";
+import type { Snapshot } from "./SemanticStateEngine.js";
+
+/** Any snapshot shape — plain or raw — that can be diffed. */
+export type SnapshotLike = Omit<Snapshot, "vector"> & {
+  vector: ArrayLike<number>;
+};
+
+/** Summary statistics of the per-dimension deltas b_i − a_i. */
+export interface DeltaStats {
+  /** Mean signed delta. */
+  mean: number;
+
+  /** Mean absolute delta. */
+  meanAbs: number;
+
+  /** Standard deviation of the signed deltas. */
+  std: number;
+
+  /** Largest absolute delta. */
+  maxAbs: number;
+
+  /** Index of the dimension with the largest absolute delta. */
+  maxAbsIndex: number;
+}
+
+/** The difference between two snapshots, as returned by {@link diffSnapshots}. */
+export interface SnapshotDiff {
+  /** Cosine similarity between the two state vectors. */
+  similarity: number;
+
+  /** L2 distance between the two state vectors. */
+  euclideanDistance: number;
+
+  /** Statistics of the per-dimension deltas b − a. */
+  delta: DeltaStats;
+
+  /** `b.healthScore − a.healthScore`. */
+  healthDelta: number;
+
+  /** `b.timestamp − a.timestamp` (ms). */
+  timeDelta: number;
+}
+
+/**
+ * Compares two snapshots of the same dimension. Deltas are taken as `b − a`,
+ * so pass the earlier snapshot first.
+ */
+export function diffSnapshots(a: SnapshotLike, b: SnapshotLike): SnapshotDiff {
+  const va = Array.from(a.vector);
+  const vb = Array.from(b.vector);
+  if (va.length !== vb.length) {
+    throw new EngineError(
+      "DIMENSION_MISMATCH",
+      `Snapshot dimension mismatch: expected ${va.length}, got ${vb.length}`,
+      { expected: va.length, actual: vb.length },
+    );
+  }
+  if (va.length === 0) {
+    throw new EngineError("EMPTY_EMBEDDING", "Cannot diff empty snapshots");
+  }
+
+  let sum = 0;
+  let sumAbs = 0;
+  let sumSq = 0;
+  let maxAbs = 0;
+  let maxAbsIndex = 0;
+  vb.forEach((val, i) => {
+    const d = val - va[i]!;
+    sum += d;
+    sumAbs += Math.abs(d);
+    sumSq += d * d;
+    if (Math.abs(d) > maxAbs) {
+      maxAbs = Math.abs(d);
+      maxAbsIndex = i;
+    }
+  });
+  const n = va.length;
+  const mean = sum / n;
+
+  return {
+    similarity: cosineSimilarity(va, vb),
+    euclideanDistance: euclideanDistance(va, vb),
+    delta: {
+      mean,
+      meanAbs: sumAbs / n,
+      std: Math.sqrt(Math.max(0, sumSq / n - mean * mean)),
+      maxAbs,
+      maxAbsIndex,
+    },
+    healthDelta: b.healthScore - a.healthScore,
+    timeDelta: b.timestamp - a.timestamp,
+  };
+}
+
+/**
+ * Compares the current states of two engines — e.g. staging against
+ * production. Deltas are taken as `b − a`. Equivalent to
+ * `diffSnapshots(a.getSnapshot(), b.getSnapshot())`.
+ */
+export function compareEngines(
+  a: { getSnapshot(): SnapshotLike },
+  b: { getSnapshot(): SnapshotLike },
+): SnapshotDiff {
+  return diffSnapshots(a.getSnapshot(), b.getSnapshot());
+}
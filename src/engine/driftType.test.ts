@@ -0,0 +1,31 @@
+import { describe, it, expect } from "vitest";
+import { classifyDrift, type DriftObservation } from "./driftType.js";
+
+const calm = (scores: number[]): DriftObservation[] =>
+  scores.map((driftScore) => ({ driftScore, thresholdExceeded: false }));
+
+describe("classifyDrift", () => {
+  it("labels a jump out of a flat window as sudden", () => {
+    expect(classifyDrift(calm([0.01, 0.02, 0.01, 0.02]), false)).toBe("sudden");
+    expect(classifyDrift([], false)).toBe("sudden");
+  });
+
+  it("labels a crossing preceded by rising drift as incremental", () => {
+    expect(classifyDrift(calm([0.01, 0.03, 0.05, 0.04, 0.08, 0.12]), false)).toBe(
+      "incremental",
+    );
+  });
+
+  it("labels clustered threshold crossings as gradual", () => {
+    const window = [
+      ...calm([0.01, 0.02]),
+      { driftScore: 0.6, thresholdExceeded: true },
+      ...calm([0.05]),
+    ];
+    expect(classifyDrift(window, true)).toBe("gradual");
+  });
+
+  it("labels a return to an earlier concept as recurring", () => {
+    expect(classifyDrift(calm([0.01, 0.03, 0.05, 0.08]), true)).toBe("recurring");
+  });
+});
@@ -0,0 +1,49 @@
+/**
+ * Kinds of concept drift, following the usual taxonomy:
+ *
+ * - `"sudden"`: an abrupt switch from a stable concept to a new one.
+ * - `"gradual"`: old and new concepts alternate before the new one takes over,
+ *   so threshold crossings cluster together.
+ * - `"incremental"`: the stream moves steadily away, with drift scores rising
+ *   over several updates before crossing the threshold.
+ * - `"recurring"`: the stream returns to a concept it had left earlier.
+ */
+export type DriftType = "sudden" | "gradual" | "incremental" | "recurring";
+
+/** One past update as seen by {@link classifyDrift}. */
+export interface DriftObservation {
+  driftScore: number;
+  thresholdExceeded: boolean;
+}
+
+/**
+ * Fraction of consecutive pairs in the window whose drift score must increase
+ * for the drift to count as incremental.
+ */
+const INCREMENTAL_RISE_FRACTION = 0.7;
+
+/**
+ * Labels a detected drift from the updates that preceded it.
+ *
+ * @param window    Recent updates before the drift, oldest first.
+ * @param recurring Whether the incoming embedding matches a concept the
+ *                  stream drifted away from earlier.
+ */
+export function classifyDrift(
+  window: DriftObservation[],
+  recurring: boolean,
+): DriftType {
+  if (window.some((o) => o.thresholdExceeded)) return "gradual";
+  if (recurring) return "recurring";
+
+  if (window.length >= 3) {
+    let rises = 0;
+    for (let i = 1; i < window.length; i++) {
+      if (window[i]!.driftScore > window[i - 1]!.driftScore) rises++;
+    }
+    if (rises / (window.length - 1) >= INCREMENTAL_RISE_FRACTION) {
+      return "incremental";
+    }
+  }
+  return "sudden";
+}
@@ -0,0 +1,25 @@
+import { describe, it, expect } from "vitest";
+import { EngineError } from "./errors.js";
+
+describe("EngineError", () => {
+  it("is an Error carrying a code and details", () => {
+    const err = new EngineError("DIMENSION_MISMATCH", "bad dim", {
+      expected: 4,
+      actual: 2,
+    });
+    expect(err).toBeInstanceOf(Error);
+    expect(err).toBeInstanceOf(EngineError);
+    expect(err.name).toBe("EngineError");
+    expect(err.code).toBe("DIMENSION_MISMATCH");
+    expect(err.message).toBe("bad dim");
+    expect(err.details).toEqual({ expected: 4, actual: 2 });
+  });
+
+  it("serializes to { code, message, details }", () => {
+    const err = new EngineError("EMPTY_EMBEDDING", "empty");
+    expect(JSON.parse(JSON.stringify(err))).toEqual({
+      code: "EMPTY_EMBEDDING",
+      message: "empty",
+    });
+  });
+});
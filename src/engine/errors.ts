@@ -0,0 +1,73 @@
+/**
+ * Structured errors thrown by the SemanticStateEngine.
+ *
+ * Every error carries a stable machine-readable `code` so callers can branch
+ * on the failure kind instead of parsing the human-readable message.
+ */
+
+/** Stable identifiers for every failure the engine can report. */
+export type EngineErrorCode =
+  /** An embedding with zero dimensions was supplied. */
+  | "EMPTY_EMBEDDING"
+  /** An embedding's dimension does not match the established state. */
+  | "DIMENSION_MISMATCH"
+  /**
+   * The operation requires something not yet set up — a baseline (no update
+   * fused yet), an embedding buffer (`allocEmbeddingBuffer` not called) or
+   * the option it relies on (e.g. `snapshotCompression`, `pca2d`).
+   */
+  | "NOT_INITIALIZED"
+  /** A configuration value or method argument is out of range. */
+  | "INVALID_CONFIG"
+  /** A persisted state passed to `importState` is malformed. */
+  | "INVALID_STATE"
+  /** An {@link EngineRegistry} key does not name a registered engine. */
+  | "ENGINE_NOT_FOUND"
+  /** An {@link EngineRegistry} key is already taken. */
+  | "ENGINE_EXISTS"
+  /** `update(text)` was called on an engine constructed without a provider. */
+  | "NO_PROVIDER"
+  /** A checkpoint ID passed to `rollback` was never issued or is no longer retained. */
+  | "CHECKPOINT_NOT_FOUND"
+  /** An update was timestamped before the previous one under the `"error"` timestamp policy. */
+  | "OUT_OF_ORDER_TIMESTAMP"
+  /** An embedding contained `NaN` or `±Infinity` under the `"error"` non-finite policy. */
+  | "NON_FINITE_EMBEDDING"
+  /**
+   * An embedding had a negative component or no mass under a divergence
+   * drift metric (`"jensen-shannon"`, `"kullback-leibler"`).
+   */
+  | "INVALID_DISTRIBUTION";
+
+/** Plain-object form of an {@link EngineError}, as produced by `toJSON`. */
+export interface EngineErrorJSON {
+  code: EngineErrorCode;
+  message: string;
+  details?: Record<string, unknown>;
+}
+
+/**
+ * Error thrown by the SemanticStateEngine.
+ *
+ * Serializes to `{ code, message, details }` via `JSON.stringify`, so it can
+ * be posted across a Worker boundary or logged without losing its code.
+ */
+export class EngineError extends Error {
+  readonly code: EngineErrorCode;
+  readonly details?: Record<string, unknown>;
+
+  constructor(
+    code: EngineErrorCode,
+    message: string,
+    details?: Record<string, unknown>,
+  ) {
+    super(message);
+    this.name = "EngineError";
+    this.code = code;
+    this.details = details;
+  }
+
+  toJSON(): EngineErrorJSON {
+    return { code: this.code, message: this.message, details: this.details };
+  }
+}
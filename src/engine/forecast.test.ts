@@ -0,0 +1,84 @@
+import { describe, it, expect } from "vitest";
+import { fitQuadratic, forecastHistory, timeUntilHealth } from "./forecast.js";
+
+const MINUTE = 60_000;
+
+const rising = [
+  { timestamp: 0, driftScore: 0.1, healthScore: 0.9 },
+  { timestamp: MINUTE, driftScore: 0.2, healthScore: 0.8 },
+  { timestamp: 2 * MINUTE, driftScore: 0.3, healthScore: 0.7 },
+];
+
+describe("forecastHistory", () => {
+  it("extrapolates linear drift and health trends", () => {
+    const forecast = forecastHistory(rising, MINUTE, 0.5, 2 * MINUTE)!;
+    expect(forecast.driftScore).toBeCloseTo(0.4);
+    expect(forecast.healthScore).toBeCloseTo(0.6);
+    expect(forecast.driftTrend).toBeCloseTo(0.1);
+    expect(forecast.healthTrend).toBeCloseTo(-0.1);
+    expect(forecast.timeToThresholdMs).toBeCloseTo(2 * MINUTE);
+  });
+
+  it("reports 0 once the trend is past the threshold", () => {
+    expect(forecastHistory(rising, 0, 0.25, 2 * MINUTE)!.timeToThresholdMs).toBe(0);
+  });
+
+  it("reports no crossing when drift is flat or falling", () => {
+    const falling = rising.map((e, i) => ({ ...e, driftScore: 0.3 - 0.1 * i }));
+    const forecast = forecastHistory(falling, 10 * MINUTE, 0.5, 2 * MINUTE)!;
+    expect(forecast.timeToThresholdMs).toBeNull();
+    expect(forecast.driftScore).toBe(0);
+  });
+
+  it("clamps the predicted health to [0, 1]", () => {
+    expect(forecastHistory(rising, 60 * MINUTE, 0.5, 2 * MINUTE)!.healthScore).toBe(0);
+  });
+
+  it("needs at least two entries", () => {
+    expect(forecastHistory(rising.slice(0, 1), MINUTE, 0.5, 0)).toBeNull();
+  });
+});
+
+describe("fitQuadratic", () => {
+  const at = (i: number, driftScore: number) => ({
+    timestamp: 1_700_000_000_000 + i * MINUTE,
+    driftScore,
+    healthScore: 1,
+  });
+
+  it("recovers the derivatives of a parabola", () => {
+    const history = [0, 1, 2, 3, 4].map((i) => at(i, 0.1 + 0.02 * i + 0.01 * i * i));
+    const fit = fitQuadratic(history, (e) => e.driftScore);
+    expect(fit.slopeAt(history[4]!.timestamp) * MINUTE).toBeCloseTo(0.1);
+    expect(fit.slopeAt(history[0]!.timestamp) * MINUTE).toBeCloseTo(0.02);
+    expect(fit.curvature * MINUTE ** 2).toBeCloseTo(0.02);
+  });
+
+  it("falls back to a line without three distinct timestamps", () => {
+    const two = fitQuadratic([at(0, 0.1), at(1, 0.3)], (e) => e.driftScore);
+    expect(two.slopeAt(0) * MINUTE).toBeCloseTo(0.2);
+    expect(two.curvature).toBe(0);
+
+    const same = fitQuadratic([at(0, 0.1), at(0, 0.2), at(0, 0.3)], (e) => e.driftScore);
+    expect(same.slopeAt(0)).toBe(0);
+    expect(same.curvature).toBe(0);
+  });
+});
+
+describe("timeUntilHealth", () => {
+  it("finds the first millisecond at or below the target", () => {
+    expect(timeUntilHealth((dt) => 1 - dt / 1000, 0.5)).toBe(500);
+    expect(timeUntilHealth((dt) => 0.9 - dt / 1000, 0.5)).toBe(400);
+  });
+
+  it("returns 0 when already at the target and null when never reached", () => {
+    expect(timeUntilHealth(() => 0.5, 0.5)).toBe(0);
+    expect(timeUntilHealth(() => 0.8, 0.5)).toBeNull();
+    expect(timeUntilHealth((dt) => 1 - dt / 1000, 0.5, 100)).toBeNull();
+  });
+
+  it("skips over an initial rise", () => {
+    const health = (dt: number) => 0.6 + 0.2 * Math.sin(dt / 1000);
+    expect(timeUntilHealth(health, 0.5)).toBe(Math.ceil(1000 * (Math.PI + Math.asin(0.5))));
+  });
+});
@@ -0,0 +1,184 @@
+import type { HistoryEntry } from "./SemanticStateEngine.js";
+
+/** A linear extrapolation of the update history, as returned by {@link forecastHistory}. */
+export interface Forecast {
+  /** Predicted drift score at the forecast time (never negative). */
+  driftScore: number;
+
+  /**
+   * Predicted health at the forecast time, clamped to [0, 1]. Extrapolates
+   * the recorded post-update health, so the age penalty is not included.
+   */
+  healthScore: number;
+
+  /** Fitted change in drift score per minute. */
+  driftTrend: number;
+
+  /** Fitted change in health per minute. */
+  healthTrend: number;
+
+  /**
+   * Milliseconds from `now` until the fitted drift trend crosses the
+   * threshold: 0 if it already has, `null` if drift is not rising.
+   */
+  timeToThresholdMs: number | null;
+}
+
+/** When health will reach a level, as returned by `timeToHealth`. */
+export interface HealthTimeEstimate {
+  /**
+   * Milliseconds from `now` until health reaches the target if no further
+   * update arrives (pure age decay): 0 if it already has, `null` if it will
+   * not within {@link MAX_HEALTH_FORECAST_MS}.
+   */
+  ageDecayMs: number | null;
+
+  /**
+   * As `ageDecayMs`, with the drift score also continuing along its recent
+   * trend. `null` as well until the history holds two updates.
+   */
+  driftTrendMs: number | null;
+}
+
+const MS_PER_MINUTE = 60_000;
+
+/** How far ahead health crossings are searched for: one year. */
+export const MAX_HEALTH_FORECAST_MS = 365 * 24 * 60 * MS_PER_MINUTE;
+
+/**
+ * Fits least-squares lines of drift score and health against time over
+ * `history` and extrapolates them to `now + horizonMs`.
+ *
+ * @param history        Update history, oldest first.
+ * @param horizonMs      How far past `now` to forecast (ms).
+ * @param driftThreshold Drift score above which drift counts as exceeded.
+ * @param now            Unix timestamp (ms) the horizon is measured from.
+ * @returns              The forecast, or `null` with fewer than two entries.
+ */
+export function forecastHistory(
+  history: HistoryEntry[],
+  horizonMs: number,
+  driftThreshold: number,
+  now: number,
+): Forecast | null {
+  if (history.length < 2) return null;
+
+  const drift = fitLine(history, (entry) => entry.driftScore);
+  const health = fitLine(history, (entry) => entry.healthScore);
+  const target = now + horizonMs;
+
+  const driftNow = drift.at(now);
+  let timeToThresholdMs: number | null = null;
+  if (driftNow > driftThreshold) {
+    timeToThresholdMs = 0;
+  } else if (drift.slope > 0) {
+    timeToThresholdMs = (driftThreshold - driftNow) / drift.slope;
+  }
+
+  return {
+    driftScore: Math.max(0, drift.at(target)),
+    healthScore: Math.max(0, Math.min(1, health.at(target))),
+    driftTrend: drift.slope * MS_PER_MINUTE,
+    healthTrend: health.slope * MS_PER_MINUTE,
+    timeToThresholdMs,
+  };
+}
+
+/**
+ * Milliseconds until `health(dt)` first drops to `target` or below, to the
+ * nearest millisecond, for a `health` that rises at most once and then
+ * falls: 0 if `health(0)` already has, `null` if it does not within
+ * `maxMs`. Doubles the step until the crossing is bracketed, then bisects.
+ */
+export function timeUntilHealth(
+  health: (dtMs: number) => number,
+  target: number,
+  maxMs: number = MAX_HEALTH_FORECAST_MS,
+): number | null {
+  if (health(0) <= target) return 0;
+  let lo = 0;
+  let hi = 1;
+  while (health(hi) > target) {
+    if (hi >= maxMs) return null;
+    lo = hi;
+    hi = Math.min(hi * 2, maxMs);
+  }
+  while (hi - lo > 1) {
+    const mid = Math.floor((lo + hi) / 2);
+    if (health(mid) > target) lo = mid;
+    else hi = mid;
+  }
+  return hi;
+}
+
+/** Ordinary least squares of `value(entry)` against `entry.timestamp`. */
+export function fitLine(
+  history: HistoryEntry[],
+  value: (entry: HistoryEntry) => number,
+): { slope: number; at: (t: number) => number } {
+  const n = history.length;
+  const meanT = history.reduce((sum, e) => sum + e.timestamp, 0) / n;
+  const meanY = history.reduce((sum, e) => sum + value(e), 0) / n;
+  let covariance = 0;
+  let variance = 0;
+  for (const entry of history) {
+    const dt = entry.timestamp - meanT;
+    covariance += dt * (value(entry) - meanY);
+    variance += dt * dt;
+  }
+  const slope = variance === 0 ? 0 : covariance / variance;
+  return { slope, at: (t) => meanY + slope * (t - meanT) };
+}
+
+/**
+ * Least-squares parabola of `value(entry)` against `entry.timestamp`, given
+ * as its first derivative at time `t` and its constant second derivative
+ * (per ms and per ms²). Degenerates to {@link fitLine}, with a second
+ * derivative of 0, for fewer than three distinct timestamps.
+ */
+export function fitQuadratic(
+  history: HistoryEntry[],
+  value: (entry: HistoryEntry) => number,
+): { slopeAt: (t: number) => number; curvature: number } {
+  const n = history.length;
+  const meanT = history.reduce((sum, e) => sum + e.timestamp, 0) / n;
+  // Fit against u = (t − meanT) / spread so the normal equations stay well
+  // conditioned at millisecond timestamps.
+  const spread = history.reduce(
+    (max, e) => Math.max(max, Math.abs(e.timestamp - meanT)),
+    0,
+  );
+  const u = history.map((e) =>
+    spread > 0 ? (e.timestamp - meanT) / spread : 0,
+  );
+  const y = history.map(value);
+  const s1 = sum(u);
+  const s2 = sum(u.map((x) => x ** 2));
+  const s3 = sum(u.map((x) => x ** 3));
+  const s4 = sum(u.map((x) => x ** 4));
+  const y0 = sum(y);
+  const y1 = sum(y.map((v, i) => u[i]! * v));
+  const y2 = sum(y.map((v, i) => u[i]! ** 2 * v));
+
+  const det = det3([n, s1, s2], [s1, s2, s3], [s2, s3, s4]);
+  if (spread === 0 || Math.abs(det) < 1e-9 * n ** 3) {
+    const line = fitLine(history, value);
+    return { slopeAt: () => line.slope, curvature: 0 };
+  }
+  const b = det3([n, y0, s2], [s1, y1, s3], [s2, y2, s4]) / det / spread;
+  const c = det3([n, s1, y0], [s1, s2, y1], [s2, s3, y2]) / det / spread ** 2;
+  return { slopeAt: (t) => b + 2 * c * (t - meanT), curvature: 2 * c };
+}
+
+function sum(values: number[]): number {
+  return values.reduce((total, v) => total + v, 0);
+}
+
+/** Determinant of the 3×3 matrix with rows `r0`, `r1` and `r2`. */
+function det3(r0: number[], r1: number[], r2: number[]): number {
+  return (
+    r0[0]! * (r1[1]! * r2[2]! - r1[2]! * r2[1]!) -
+    r0[1]! * (r1[0]! * r2[2]! - r1[2]! * r2[0]!) +
+    r0[2]! * (r1[0]! * r2[1]! - r1[1]! * r2[0]!)
+  );
+}
@@ -0,0 +1,32 @@
+import { describe, it, expect } from "vitest";
+import { migrateState, STATE_VERSION } from "./migrate.js";
+import { EngineError } from "./errors.js";
+
+const legacy = {
+  stateVector: [1, 0],
+  alpha: 0.5,
+  driftThreshold: 0.75,
+  updateCount: 1,
+  lastDrift: 0,
+  lastUpdatedAt: 1000,
+};
+
+describe("migrateState", () => {
+  it("upgrades an unversioned state to the current version", () => {
+    expect(migrateState(legacy)).toEqual({ ...legacy, version: STATE_VERSION });
+  });
+
+  it("leaves a current state unchanged", () => {
+    const current = { ...legacy, version: STATE_VERSION };
+    expect(migrateState(current)).toEqual(current);
+  });
+
+  it("rejects states from a newer release", () => {
+    expect(() => migrateState({ ...legacy, version: STATE_VERSION + 1 })).toThrow(
+      EngineError,
+    );
+    expect(() => migrateState({ ...legacy, version: 1.5 })).toThrow(
+      "Unsupported engine state version 1.5",
+    );
+  });
+});
@@ -0,0 +1,40 @@
+import { EngineError } from "./errors.js";
+import type { EngineState } from "./SemanticStateEngine.js";
+
+/** Version of the {@link EngineState} layout written by `exportState`. */
+export const STATE_VERSION = 1;
+
+/**
+ * Upgrades applied in order to older persisted states: entry `n` turns a
+ * version-`n` state into a version-`n + 1` one. Whenever `EngineState`
+ * changes incompatibly, append a migration and bump {@link STATE_VERSION}.
+ */
+const MIGRATIONS: ((state: EngineState) => EngineState)[] = [
+  // 0 → 1: states exported before versioning. Every field added since then
+  // is optional and defaulted on import, so the layout carries over as-is.
+  (state) => state,
+];
+
+/**
+ * Upgrades a persisted state to the current {@link STATE_VERSION}. States
+ * without a `version` predate versioning and are treated as version 0.
+ *
+ * @throws {EngineError} `INVALID_STATE` if the version is unknown or newer
+ *         than this release supports.
+ */
+export function migrateState(state: EngineState): EngineState {
+  let version = state.version ?? 0;
+  if (!Number.isInteger(version) || version < 0 || version > STATE_VERSION) {
+    throw new EngineError(
+      "INVALID_STATE",
+      `Unsupported engine state version ${version}; this release reads versions 0 to ${STATE_VERSION}`,
+      { version, supported: STATE_VERSION },
+    );
+  }
+  let migrated = state;
+  while (version < STATE_VERSION) {
+    migrated = MIGRATIONS[version]!(migrated);
+    version++;
+  }
+  return { ...migrated, version: STATE_VERSION };
+}
@@ -0,0 +1,77 @@
+import { describe, it, expect } from "vitest";
+import { driftEventToOtel, snapshotToOtel } from "./otel.js";
+import type { DriftEvent, Snapshot } from "./SemanticStateEngine.js";
+
+const snapshot: Snapshot = {
+  vector: [1, 0],
+  healthScore: 0.8,
+  timestamp: 1000,
+  lastDriftScore: 0.3,
+  lastSimilarity: 0.7,
+  updateCount: 5,
+  dimension: 2,
+  semanticSummary: "Stable",
+  metadata: { tenant: "acme" },
+};
+
+const event: DriftEvent = {
+  timestamp: 1_700_000_000_123,
+  driftScore: 0.6,
+  similarity: 0.4,
+  metric: "cosine",
+  threshold: 0.75,
+  reason: "similarity 0.400 < 0.75 (cosine)",
+  driftLevel: "critical",
+};
+
+describe("snapshotToOtel", () => {
+  it("maps the snapshot onto OTel log attributes", () => {
+    const record = snapshotToOtel(snapshot, "none", 2000);
+    expect(record).toEqual({
+      timeUnixNano: "2000000000",
+      severityNumber: 9,
+      severityText: "INFO",
+      body: "Stable",
+      attributes: {
+        tenant: "acme",
+        "semantic_state.health_score": 0.8,
+        "semantic_state.drift_score": 0.3,
+        "semantic_state.similarity": 0.7,
+        "semantic_state.drift_level": "none",
+        "semantic_state.update_count": 5,
+        "semantic_state.last_update_ms": 1000,
+        "semantic_state.dimension": 2,
+      },
+    });
+  });
+
+  it("derives severity from the drift level", () => {
+    expect(snapshotToOtel(snapshot, "warning", 0).severityText).toBe("WARN");
+    expect(snapshotToOtel(snapshot, "critical", 0).severityNumber).toBe(17);
+  });
+});
+
+describe("driftEventToOtel", () => {
+  it("names the record and keeps the timestamp exact", () => {
+    const record = driftEventToOtel(event);
+    expect(record.timeUnixNano).toBe("1700000000123000000");
+    expect(record.severityText).toBe("ERROR");
+    expect(record.body).toBe(event.reason);
+    expect(record.attributes["event.name"]).toBe("semantic_state.drift");
+    expect(record.attributes["semantic_state.threshold"]).toBe(0.75);
+  });
+
+  it("treats events without a level as warnings", () => {
+    const legacy: DriftEvent = { ...event };
+    delete legacy.driftLevel;
+    expect(driftEventToOtel(legacy).severityText).toBe("WARN");
+  });
+
+  it("flags drift recorded inside a suppression window", () => {
+    const record = driftEventToOtel({ ...event, suppressed: true });
+    expect(record.attributes["semantic_state.suppressed"]).toBe(true);
+    expect(driftEventToOtel(event).attributes).not.toHaveProperty(
+      "semantic_state.suppressed",
+    );
+  });
+});
@@ -0,0 +1,117 @@
+import type {
+  DriftEvent,
+  DriftLevel,
+  Snapshot,
+} from "./SemanticStateEngine.js";
+
+/** OpenTelemetry attribute value. */
+export type OtelAttributeValue = string | number | boolean;
+
+/**
+ * A log record shaped after the OpenTelemetry log data model (as in the
+ * OTLP/JSON encoding), ready to hand to an OTel logger or exporter.
+ */
+export interface OtelLogRecord {
+  /** Event time in nanoseconds since the Unix epoch, as a decimal string. */
+  timeUnixNano: string;
+
+  /** OTel severity number: 9 (INFO), 13 (WARN) or 17 (ERROR). */
+  severityNumber: number;
+
+  /** Severity name matching `severityNumber`. */
+  severityText: "INFO" | "WARN" | "ERROR";
+
+  /** Human-readable message. */
+  body: string;
+
+  /**
+   * Attributes under the `semantic_state.*` namespace, plus the engine's
+   * metadata labels under their own names.
+   */
+  attributes: Record<string, OtelAttributeValue>;
+}
+
+const SEVERITY: Record<
+  DriftLevel,
+  Pick<OtelLogRecord, "severityNumber" | "severityText">
+> = {
+  none: { severityNumber: 9, severityText: "INFO" },
+  warning: { severityNumber: 13, severityText: "WARN" },
+  critical: { severityNumber: 17, severityText: "ERROR" },
+};
+
+/**
+ * Converts a snapshot into an OTel log record whose severity follows the
+ * drift level of the most recent update.
+ *
+ * @param snapshot   The snapshot to convert.
+ * @param driftLevel Drift level of the most recent update.
+ * @param now        Unix timestamp (ms) the snapshot was taken at.
+ */
+export function snapshotToOtel(
+  snapshot: Snapshot,
+  driftLevel: DriftLevel,
+  now: number,
+): OtelLogRecord {
+  const attributes: Record<string, OtelAttributeValue> = {
+    ...snapshot.metadata,
+    "semantic_state.health_score": snapshot.healthScore,
+    "semantic_state.drift_score": snapshot.lastDriftScore,
+    "semantic_state.similarity": snapshot.lastSimilarity,
+    "semantic_state.drift_level": driftLevel,
+    "semantic_state.update_count": snapshot.updateCount,
+    "semantic_state.last_update_ms": snapshot.timestamp,
+  };
+  if (snapshot.dimension !== null) {
+    attributes["semantic_state.dimension"] = snapshot.dimension;
+  }
+  if (snapshot.uncertainty !== undefined) {
+    attributes["semantic_state.uncertainty"] = snapshot.uncertainty;
+  }
+  if (snapshot.isStale !== undefined) {
+    attributes["semantic_state.stale"] = snapshot.isStale;
+  }
+  if (snapshot.insufficientData) {
+    attributes["semantic_state.insufficient_data"] = true;
+  }
+  return {
+    timeUnixNano: toUnixNano(now),
+    ...SEVERITY[driftLevel],
+    body: snapshot.semanticSummary,
+    attributes,
+  };
+}
+
+/**
+ * Converts a recorded drift event into an OTel log record named
+ * `semantic_state.drift`, at WARN severity (ERROR for critical drift).
+ */
+export function driftEventToOtel(event: DriftEvent): OtelLogRecord {
+  const level = event.driftLevel ?? "warning";
+  const attributes: Record<string, OtelAttributeValue> = {
+    ...event.metadata,
+    "event.name": "semantic_state.drift",
+    "semantic_state.drift_score": event.driftScore,
+    "semantic_state.similarity": event.similarity,
+    "semantic_state.drift_level": level,
+    "semantic_state.metric": event.metric,
+    "semantic_state.threshold": event.threshold,
+  };
+  if (event.driftType !== undefined) {
+    attributes["semantic_state.drift_type"] = event.driftType;
+  }
+  if (event.suppressed === true) {
+    attributes["semantic_state.suppressed"] = true;
+  }
+  return {
+    timeUnixNano: toUnixNano(event.timestamp),
+    ...SEVERITY[level],
+    body: event.reason,
+    attributes,
+  };
+}
+
+/** Converts a Unix timestamp in ms into exact integer nanoseconds. */
+function toUnixNano(ms: number): string {
+  return (BigInt(Math.round(ms)) * 1_000_000n).toString();
+}
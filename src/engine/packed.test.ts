@@ -0,0 +1,62 @@
+import { describe, it, expect } from "vitest";
+import { packUpdateResult, unpackUpdateResult } from "./packed.js";
+import { EngineError } from "./errors.js";
+import type { UpdateResult } from "./SemanticStateEngine.js";
+
+const result: UpdateResult = {
+  vector: [0.6, 0.8, 0],
+  similarity: 0.42,
+  driftScore: 0.58,
+  magnitudeDrift: 0.1,
+  driftDetected: true,
+  thresholdExceeded: true,
+  inputShift: false,
+  driftLevel: "critical",
+  changePointDetected: false,
+  alpha: 0.3,
+  timestamp: 1_700_000_000_000,
+  warmingUp: false,
+  outlier: true,
+  rejected: false,
+  driftSuppressed: true,
+  attribution: [{ index: 1, contribution: 0.5 }],
+};
+
+describe("packUpdateResult / unpackUpdateResult", () => {
+  it("round-trips the packed fields", () => {
+    const buffer = packUpdateResult(result);
+    expect(buffer.byteLength).toBe((8 + 3) * 8);
+    const unpacked = unpackUpdateResult(buffer);
+    expect({ ...unpacked, vector: [...unpacked.vector] }).toEqual({
+      vector: [0.6, 0.8, 0],
+      similarity: 0.42,
+      driftScore: 0.58,
+      alpha: 0.3,
+      timestamp: 1_700_000_000_000,
+      driftDetected: true,
+      thresholdExceeded: true,
+      inputShift: false,
+      changePointDetected: false,
+      warmingUp: false,
+      outlier: true,
+      rejected: false,
+      driftSuppressed: true,
+      driftLevel: "critical",
+    });
+  });
+
+  it("exposes the vector as a view onto the buffer", () => {
+    const buffer = packUpdateResult(result);
+    const { vector } = unpackUpdateResult(buffer);
+    expect(vector.buffer).toBe(buffer);
+  });
+
+  it("rejects buffers that are not packed results", () => {
+    expect(() => unpackUpdateResult(new ArrayBuffer(12))).toThrow(EngineError);
+    expect(() => unpackUpdateResult(new Float64Array(8).buffer)).toThrow(
+      "layout version",
+    );
+    const truncated = packUpdateResult(result).slice(0, (8 + 2) * 8);
+    expect(() => unpackUpdateResult(truncated)).toThrow("declares dimension 3");
+  });
+});
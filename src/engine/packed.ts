@@ -0,0 +1,133 @@
+import { EngineError } from "./errors.js";
+import type { DriftLevel, UpdateResult } from "./SemanticStateEngine.js";
+
+/**
+ * Transferable encoding of an {@link UpdateResult}: a single `ArrayBuffer`
+ * that can be posted between threads in the transfer list (no structured
+ * clone of nested objects), decoded with {@link unpackUpdateResult}.
+ *
+ * Layout: a `Float64Array` (platform byte order) of `HEADER_SLOTS + d`
+ * values, d being the embedding's dimension — 0 when the result carries no
+ * `vector` (see `resultDetail`).
+ *
+ * | slot | content                                          |
+ * |------|--------------------------------------------------|
+ * | 0    | layout version (1)                               |
+ * | 1    | similarity                                       |
+ * | 2    | driftScore                                       |
+ * | 3    | alpha                                            |
+ * | 4    | timestamp                                        |
+ * | 5    | flag bits, see {@link PACKED_FLAGS}              |
+ * | 6    | driftLevel: 0 `"none"`, 1 `"warning"`, 2 `"critical"` |
+ * | 7    | dimension d                                      |
+ * | 8…   | the fused embedding                              |
+ *
+ * Only the fields above are carried; the optional diagnostics of an
+ * `UpdateResult` (attribution, reference similarities, …) are not.
+ */
+
+/** Bit assigned to each boolean field in slot 5 of a packed result. */
+export const PACKED_FLAGS = {
+  driftDetected: 1,
+  thresholdExceeded: 2,
+  inputShift: 4,
+  changePointDetected: 8,
+  warmingUp: 16,
+  outlier: 32,
+  rejected: 64,
+  driftSuppressed: 128,
+} as const;
+
+/** The fields of an {@link UpdateResult} carried by a packed result. */
+export interface PackedUpdateResult {
+  /** The fused embedding: a view onto the packed buffer, not a copy. */
+  vector: Float64Array;
+  similarity: number;
+  driftScore: number;
+  alpha: number;
+  timestamp: number;
+  driftDetected: boolean;
+  thresholdExceeded: boolean;
+  inputShift: boolean;
+  changePointDetected: boolean;
+  warmingUp: boolean;
+  outlier: boolean;
+  rejected: boolean;
+  driftSuppressed: boolean;
+  driftLevel: DriftLevel;
+}
+
+const LAYOUT_VERSION = 1;
+const HEADER_SLOTS = 8;
+const DRIFT_LEVELS: DriftLevel[] = ["none", "warning", "critical"];
+
+/** Encodes `result` into a transferable buffer with the layout above. */
+export function packUpdateResult(result: UpdateResult): ArrayBuffer {
+  const vector = result.vector ?? [];
+  const d = vector.length;
+  const slots = new Float64Array(HEADER_SLOTS + d);
+  let flags = 0;
+  for (const [name, bit] of Object.entries(PACKED_FLAGS)) {
+    if (result[name as keyof typeof PACKED_FLAGS]) flags |= bit;
+  }
+  slots[0] = LAYOUT_VERSION;
+  slots[1] = result.similarity;
+  slots[2] = result.driftScore;
+  slots[3] = result.alpha;
+  slots[4] = result.timestamp;
+  slots[5] = flags;
+  slots[6] = DRIFT_LEVELS.indexOf(result.driftLevel);
+  slots[7] = d;
+  slots.set(vector, HEADER_SLOTS);
+  return slots.buffer;
+}
+
+/**
+ * Decodes a buffer produced by {@link packUpdateResult}.
+ *
+ * @throws {EngineError} `INVALID_STATE` if the buffer is not a packed result.
+ */
+export function unpackUpdateResult(buffer: ArrayBuffer): PackedUpdateResult {
+  if (buffer.byteLength < HEADER_SLOTS * 8 || buffer.byteLength % 8 !== 0) {
+    throw new EngineError(
+      "INVALID_STATE",
+      `Packed result must be a whole number of at least ${HEADER_SLOTS} float64 slots, got ${buffer.byteLength} bytes`,
+      { byteLength: buffer.byteLength },
+    );
+  }
+  const slots = new Float64Array(buffer);
+  if (slots[0] !== LAYOUT_VERSION) {
+    throw new EngineError(
+      "INVALID_STATE",
+      `Unsupported packed result layout version ${slots[0]}`,
+      { version: slots[0] },
+    );
+  }
+  const d = slots[7]!;
+  if (slots.length !== HEADER_SLOTS + d) {
+    throw new EngineError(
+      "INVALID_STATE",
+      `Packed result declares dimension ${d} but holds ${slots.length - HEADER_SLOTS} values`,
+      { dimension: d, values: slots.length - HEADER_SLOTS },
+    );
+  }
+  const flags = slots[5]!;
+  const flag = (name: keyof typeof PACKED_FLAGS) =>
+    (flags & PACKED_FLAGS[name]) !== 0;
+  return {
+    vector: slots.subarray(HEADER_SLOTS),
+    similarity: slots[1]!,
+    driftScore: slots[2]!,
+    alpha: slots[3]!,
+    timestamp: slots[4]!,
+    driftDetected: flag("driftDetected"),
+    thresholdExceeded: flag("thresholdExceeded"),
+    inputShift: flag("inputShift"),
+    changePointDetected: flag("changePointDetected"),
+    warmingUp: flag("warmingUp"),
+    outlier: flag("outlier"),
+    rejected: flag("rejected"),
+    driftSuppressed: flag("driftSuppressed"),
+    driftLevel: DRIFT_LEVELS[slots[6]!] ?? "none",
+  };
+}
@@ -0,0 +1,67 @@
+import { describe, it, expect } from "vitest";
+import { formatPrometheus } from "./prometheus.js";
+import { EngineError } from "./errors.js";
+
+const snapshot = {
+  healthScore: 0.9,
+  lastDriftScore: 0.25,
+  lastSimilarity: 0.75,
+  updateCount: 12,
+  timestamp: 1000,
+};
+
+describe("formatPrometheus", () => {
+  it("renders every gauge with HELP and TYPE lines", () => {
+    const text = formatPrometheus([{ labels: {}, snapshot }], "sse", 3500);
+    expect(text).toBe(
+      [
+        "# HELP sse_health_score Semantic health score in [0, 1].",
+        "# TYPE sse_health_score gauge",
+        "sse_health_score 0.9",
+        "# HELP sse_drift_score Drift score of the most recent update.",
+        "# TYPE sse_drift_score gauge",
+        "sse_drift_score 0.25",
+        "# HELP sse_similarity Similarity of the most recent update to the drift baseline.",
+        "# TYPE sse_similarity gauge",
+        "sse_similarity 0.75",
+        "# HELP sse_update_count Number of updates fused since the last reset.",
+        "# TYPE sse_update_count gauge",
+        "sse_update_count 12",
+        "# HELP sse_seconds_since_update Seconds since the most recent update.",
+        "# TYPE sse_seconds_since_update gauge",
+        "sse_seconds_since_update 2.5",
+        "",
+      ].join("\n"),
+    );
+  });
+
+  it("groups the samples of several series under one metric", () => {
+    const text = formatPrometheus(
+      [
+        { labels: { engine: "a" }, snapshot },
+        { labels: { engine: "b" }, snapshot: { ...snapshot, healthScore: 0.4 } },
+      ],
+      "sse",
+      1000,
+    );
+    expect(text).toContain(
+      'sse_health_score{engine="a"} 0.9\nsse_health_score{engine="b"} 0.4\n',
+    );
+  });
+
+  it("escapes label values", () => {
+    const text = formatPrometheus(
+      [{ labels: { model: 'a"b\\c\nd' }, snapshot }],
+      "sse",
+      1000,
+    );
+    expect(text).toContain('sse_health_score{model="a\\"b\\\\c\\nd"} 0.9');
+  });
+
+  it("rejects invalid metric and label names", () => {
+    expect(() => formatPrometheus([], "bad-prefix", 0)).toThrow(EngineError);
+    expect(() =>
+      formatPrometheus([{ labels: { "bad-label": "x" }, snapshot }], "sse", 0),
+    ).toThrow(EngineError);
+  });
+});
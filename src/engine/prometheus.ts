@@ -0,0 +1,112 @@
+import { EngineError } from "./errors.js";
+import type { Snapshot } from "./SemanticStateEngine.js";
+
+/** One engine's snapshot and the labels identifying it, as passed to {@link formatPrometheus}. */
+export interface PrometheusSeries {
+  /** Label names and values attached to every sample of the engine. */
+  labels: Record<string, string>;
+
+  /** The engine's current snapshot. */
+  snapshot: Pick<
+    Snapshot,
+    "healthScore" | "lastDriftScore" | "lastSimilarity" | "updateCount" | "timestamp"
+  >;
+}
+
+const GAUGES: {
+  name: string;
+  help: string;
+  value: (series: PrometheusSeries, now: number) => number;
+}[] = [
+  {
+    name: "health_score",
+    help: "Semantic health score in [0, 1].",
+    value: ({ snapshot }) => snapshot.healthScore,
+  },
+  {
+    name: "drift_score",
+    help: "Drift score of the most recent update.",
+    value: ({ snapshot }) => snapshot.lastDriftScore,
+  },
+  {
+    name: "similarity",
+    help: "Similarity of the most recent update to the drift baseline.",
+    value: ({ snapshot }) => snapshot.lastSimilarity,
+  },
+  {
+    name: "update_count",
+    help: "Number of updates fused since the last reset.",
+    value: ({ snapshot }) => snapshot.updateCount,
+  },
+  {
+    name: "seconds_since_update",
+    help: "Seconds since the most recent update.",
+    value: ({ snapshot }, now) => (now - snapshot.timestamp) / 1000,
+  },
+];
+
+const METRIC_NAME = /^[a-zA-Z_:][a-zA-Z0-9_:]*$/;
+const LABEL_NAME = /^[a-zA-Z_][a-zA-Z0-9_]*$/;
+
+/**
+ * Renders the gauges of one or more engines in the Prometheus text
+ * exposition format (version 0.0.4): `<prefix>_health_score`,
+ * `<prefix>_drift_score`, `<prefix>_similarity`, `<prefix>_update_count` and
+ * `<prefix>_seconds_since_update`, one sample per series.
+ *
+ * @param series Engines to render, each with its identifying labels.
+ * @param prefix Metric name prefix, e.g. `"semantic_state"`.
+ * @param now    Unix timestamp (ms) staleness is measured against.
+ * @throws {EngineError} `INVALID_CONFIG` if `prefix` or a label name is not
+ *         a valid Prometheus name.
+ */
+export function formatPrometheus(
+  series: PrometheusSeries[],
+  prefix: string,
+  now: number,
+): string {
+  if (!METRIC_NAME.test(prefix)) {
+    throw new EngineError(
+      "INVALID_CONFIG",
+      `prefix must be a valid Prometheus metric name, got "${prefix}"`,
+      { prefix },
+    );
+  }
+  const labelSets = series.map(({ labels }) => formatLabels(labels));
+  const lines: string[] = [];
+  for (const gauge of GAUGES) {
+    const name = `${prefix}_${gauge.name}`;
+    lines.push(`# HELP ${name} ${gauge.help}`, `# TYPE ${name} gauge`);
+    series.forEach((s, i) => {
+      lines.push(`${name}${labelSets[i]!} ${formatValue(gauge.value(s, now))}`);
+    });
+  }
+  return lines.join("\n") + "\n";
+}
+
+function formatLabels(labels: Record<string, string>): string {
+  const entries = Object.entries(labels);
+  if (entries.length === 0) return "";
+  const pairs = entries.map(([name, value]) => {
+    if (!LABEL_NAME.test(name) || name.startsWith("__")) {
+      throw new EngineError(
+        "INVALID_CONFIG",
+        `"${name}" is not a valid Prometheus label name`,
+        { label: name },
+      );
+    }
+    const escaped = value
+      .replace(/\\/g, "\\\\")
+      .replace(/"/g, '\\"')
+      .replace(/\n/g, "\\n");
+    return `${name}="${escaped}"`;
+  });
+  return `{${pairs.join(",")}}`;
+}
+
+function formatValue(value: number): string {
+  if (Number.isNaN(value)) return "NaN";
+  if (value === Infinity) return "+Inf";
+  if (value === -Infinity) return "-Inf";
+  return String(value);
+}
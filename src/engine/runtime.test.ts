@@ -0,0 +1,26 @@
+import { describe, it, expect } from "vitest";
+import { clonePlain, elapsedMs } from "./runtime.js";
+
+describe("clonePlain", () => {
+  it("deep-copies nested objects and arrays", () => {
+    const value = { a: [1, { b: "x" }], c: { d: null, e: undefined } };
+    const copy = clonePlain(value);
+    expect(copy).toEqual(value);
+    expect(copy.a).not.toBe(value.a);
+    expect(copy.a[1]).not.toBe(value.a[1]);
+    expect(copy.c).not.toBe(value.c);
+  });
+
+  it("returns primitives unchanged", () => {
+    expect(clonePlain(3)).toBe(3);
+    expect(clonePlain("s")).toBe("s");
+    expect(clonePlain(null)).toBeNull();
+  });
+});
+
+describe("elapsedMs", () => {
+  it("never goes backwards", () => {
+    const a = elapsedMs();
+    expect(elapsedMs()).toBeGreaterThanOrEqual(a);
+  });
+});
@@ -0,0 +1,33 @@
+/**
+ * The few host facilities the engine needs, implemented on plain
+ * ECMAScript so the `core` entry point also runs on minimal runtimes
+ * (QuickJS, Moddable XS on microcontrollers, …) that lack the Web APIs.
+ */
+
+/**
+ * Time (ms) for latency measurements: the monotonic, high-resolution
+ * `performance.now()` where available, otherwise `Date.now()`, which is
+ * wall-clock time and can jump when the system clock is adjusted.
+ */
+export const elapsedMs: () => number =
+  typeof performance !== "undefined" && typeof performance.now === "function"
+    ? () => performance.now()
+    : () => Date.now();
+
+/**
+ * Deep copy of JSON-like data — plain objects, arrays and primitives, as in
+ * drift events and exported states. Stands in for `structuredClone`.
+ */
+export function clonePlain<T>(value: T): T {
+  if (Array.isArray(value)) {
+    return value.map((item: unknown) => clonePlain(item)) as T;
+  }
+  if (typeof value === "object" && value !== null) {
+    const copy: Record<string, unknown> = {};
+    for (const [key, item] of Object.entries(value)) {
+      copy[key] = clonePlain(item);
+    }
+    return copy as T;
+  }
+  return value;
+}
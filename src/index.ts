@@ -5,11 +5,5 @@
  * the implicit semantic intent, emotional state, or "vibe" of a user/system.
  */
 
-export { emaFusion, cosineSimilarity, normalize, add, scale } from "./math/vector.js";
-export {
-  SemanticStateEngine,
-  type EmbeddingProvider,
-  type SemanticStateEngineConfig,
-  type Snapshot,
-} from "./engine/SemanticStateEngine.js";
+export * from "./core.js";
 export { WorkerManager } from "./worker/WorkerManager.js";
@@ -0,0 +1,38 @@
+import { describe, it, expect } from "vitest";
+import { OnlineKMeans } from "./OnlineKMeans.js";
+
+describe("OnlineKMeans", () => {
+  it("founds a new cluster for each dissimilar vector", () => {
+    const kmeans = new OnlineKMeans(4, 0.8);
+    expect(kmeans.assign([1, 0])).toEqual({ cluster: 0, similarity: 1, created: true });
+    expect(kmeans.assign([0, 1]).cluster).toBe(1);
+    expect(kmeans.count).toBe(2);
+  });
+
+  it("moves a centroid to the running mean of its members", () => {
+    const kmeans = new OnlineKMeans(4, 0.5);
+    kmeans.assign([1, 0]);
+    const result = kmeans.assign([1, 1]);
+    expect(result.cluster).toBe(0);
+    expect(result.created).toBe(false);
+    expect(result.similarity).toBeCloseTo(Math.SQRT1_2);
+    expect(kmeans.getCentroids()).toEqual([[1, 0.5]]);
+    expect(kmeans.getSizes()).toEqual([2]);
+  });
+
+  it("assigns to the nearest centroid once k clusters exist", () => {
+    const kmeans = new OnlineKMeans(2, 0.99);
+    kmeans.assign([1, 0]);
+    kmeans.assign([0, 1]);
+    expect(kmeans.assign([1, 0.2])).toMatchObject({ cluster: 0, created: false });
+    expect(kmeans.count).toBe(2);
+  });
+
+  it("reset() discards all clusters", () => {
+    const kmeans = new OnlineKMeans(2, 0.8);
+    kmeans.assign([1, 0]);
+    kmeans.reset();
+    expect(kmeans.count).toBe(0);
+    expect(kmeans.getCentroids()).toEqual([]);
+  });
+});
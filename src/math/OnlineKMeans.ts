@@ -0,0 +1,92 @@
+import { cosineSimilarity } from "./vector.js";
+
+/** Outcome of assigning a vector with {@link OnlineKMeans.assign}. */
+export interface ClusterAssignment {
+  /** Index of the cluster the vector was assigned to. */
+  cluster: number;
+
+  /** Cosine similarity between the vector and the cluster's prior centroid (1 for a new cluster). */
+  similarity: number;
+
+  /** Whether the vector founded a new cluster. */
+  created: boolean;
+}
+
+/**
+ * Sequential (MacQueen) k-means over cosine similarity.
+ *
+ * Each vector joins its most similar centroid, which then moves towards it by
+ * 1/n of the difference, n being the cluster's size. While fewer than `k`
+ * clusters exist, a vector whose best similarity is below
+ * `newClusterSimilarity` founds a new cluster instead.
+ */
+export class OnlineKMeans {
+  private readonly k: number;
+  private readonly newClusterSimilarity: number;
+
+  private centroids: number[][];
+  private sizes: number[];
+
+  /**
+   * @param k                    Maximum number of clusters.
+   * @param newClusterSimilarity Similarity below which a new cluster is founded.
+   */
+  constructor(k: number, newClusterSimilarity: number) {
+    if (!Number.isInteger(k) || k < 1) {
+      throw new Error(`k must be a positive integer, got ${k}`);
+    }
+    this.k = k;
+    this.newClusterSimilarity = newClusterSimilarity;
+    this.centroids = [];
+    this.sizes = [];
+  }
+
+  /** Number of clusters founded so far. */
+  get count(): number {
+    return this.centroids.length;
+  }
+
+  /** Copies of the current centroids, in founding order. */
+  getCentroids(): number[][] {
+    return this.centroids.map((c) => [...c]);
+  }
+
+  /** Number of vectors assigned to each cluster, in founding order. */
+  getSizes(): number[] {
+    return [...this.sizes];
+  }
+
+  assign(v: number[]): ClusterAssignment {
+    let best = -1;
+    let bestSimilarity = -Infinity;
+    this.centroids.forEach((centroid, i) => {
+      const s = cosineSimilarity(centroid, v);
+      if (s > bestSimilarity) {
+        best = i;
+        bestSimilarity = s;
+      }
+    });
+
+    if (
+      best === -1 ||
+      (bestSimilarity < this.newClusterSimilarity &&
+        this.centroids.length < this.k)
+    ) {
+      this.centroids.push([...v]);
+      this.sizes.push(1);
+      return { cluster: this.centroids.length - 1, similarity: 1, created: true };
+    }
+
+    const n = ++this.sizes[best];
+    const centroid = this.centroids[best]!;
+    for (let i = 0; i < centroid.length; i++) {
+      centroid[i] = centroid[i]! + (v[i]! - centroid[i]!) / n;
+    }
+    return { cluster: best, similarity: bestSimilarity, created: false };
+  }
+
+  reset(): void {
+    this.centroids = [];
+    this.sizes = [];
+  }
+}
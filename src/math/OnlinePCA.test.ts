@@ -0,0 +1,89 @@
+import { describe, it, expect } from "vitest";
+import { OnlinePCA } from "./OnlinePCA.js";
+import { mulberry32 } from "./projection.js";
+import { dot } from "./vector.js";
+
+/**
+ * Observes `n` 8-dimensional points centred on 3·e0 whose variance lies
+ * mostly along e2 (std 1) and e5 (std 0.5), scaled by `scale`.
+ */
+function observeStream(pca: OnlinePCA, n: number, scale = 1): void {
+  const next = mulberry32(7);
+  const gaussian = () =>
+    Math.sqrt(-2 * Math.log(1 - next())) * Math.cos(2 * Math.PI * next());
+  for (let t = 0; t < n; t++) {
+    const v = Array.from({ length: 8 }, () => 0.05 * gaussian());
+    v[0] = v[0]! + 3;
+    v[2] = v[2]! + gaussian();
+    v[5] = v[5]! + 0.5 * gaussian();
+    pca.observe(v.map((x) => x * scale));
+  }
+}
+
+describe("OnlinePCA", () => {
+  it("recovers the principal axes in order of variance", () => {
+    const pca = new OnlinePCA(2);
+    observeStream(pca, 2000);
+    const [first, second] = pca.getComponents();
+    expect(Math.abs(first![2]!)).toBeGreaterThan(0.98);
+    expect(Math.abs(second![5]!)).toBeGreaterThan(0.98);
+    expect(pca.getMean()[0]).toBeCloseTo(3, 1);
+    expect(pca.count).toBe(2000);
+  });
+
+  it("keeps the components orthonormal", () => {
+    const pca = new OnlinePCA(3);
+    observeStream(pca, 100);
+    const components = pca.getComponents();
+    components.forEach((a, i) =>
+      components.forEach((b, j) =>
+        expect(dot(a, b)).toBeCloseTo(i === j ? 1 : 0, 10),
+      ),
+    );
+  });
+
+  it("converges independently of the scale of the vectors", () => {
+    const small = new OnlinePCA(1);
+    const large = new OnlinePCA(1);
+    observeStream(small, 500, 1e-3);
+    observeStream(large, 500, 1e3);
+    expect(Math.abs(small.getComponents()[0]![2]!)).toBeGreaterThan(0.98);
+    expect(Math.abs(large.getComponents()[0]![2]!)).toBeGreaterThan(0.98);
+  });
+
+  it("projects relative to the running mean", () => {
+    const pca = new OnlinePCA(2);
+    expect(pca.project([1, 2, 3])).toEqual([0, 0]);
+    observeStream(pca, 2000);
+    const [x, y] = pca.project(pca.getMean());
+    expect(x).toBeCloseTo(0);
+    expect(y).toBeCloseTo(0);
+    const along = pca.project(pca.getMean().map((m, i) => (i === 2 ? m + 2 : m)));
+    expect(Math.abs(along[0]!)).toBeCloseTo(2, 1);
+  });
+
+  it("caps the components at the dimension and rejects mismatched vectors", () => {
+    const pca = new OnlinePCA(5);
+    pca.observe([1, 0]);
+    expect(pca.getComponents()).toHaveLength(2);
+    expect(pca.dimension).toBe(2);
+    expect(pca.project([0, 0])).toHaveLength(2);
+    expect(() => pca.observe([1, 0, 0])).toThrow(/dimension mismatch/);
+    expect(() => pca.project([1])).toThrow(/dimension mismatch/);
+  });
+
+  it("starts over after reset", () => {
+    const pca = new OnlinePCA(1);
+    observeStream(pca, 10);
+    pca.reset();
+    expect(pca.count).toBe(0);
+    expect(pca.getComponents()).toEqual([]);
+    pca.observe([1, 2, 3]);
+    expect(pca.getMean()).toEqual([1, 2, 3]);
+  });
+
+  it("rejects invalid parameters", () => {
+    expect(() => new OnlinePCA(0)).toThrow(/positive integer/);
+    expect(() => new OnlinePCA(2, 0)).toThrow(/learning rate/);
+  });
+});
@@ -0,0 +1,156 @@
+import { mulberry32 } from "./projection.js";
+import { dot } from "./vector.js";
+
+/**
+ * Incremental principal component analysis by the generalized Hebbian
+ * algorithm (Sanger's extension of Oja's rule).
+ *
+ * Each observation updates a running mean, then moves every component w_j
+ * by `η · y_j · (x̃ − Σ_{i≤j} y_i w_i)`, where x̃ is the centered
+ * observation, y = W x̃ its current projection and η the learning rate
+ * divided by the running mean of ‖x̃‖², so convergence does not depend on
+ * the scale of the vectors. The components are
+ * re-orthonormalized afterwards, so they stay a valid basis, ordered by
+ * explained variance once converged. The starting basis is drawn from a
+ * seeded generator, so runs over the same stream are reproducible.
+ */
+export class OnlinePCA {
+  /** Maximum number of components extracted. */
+  readonly components: number;
+
+  private readonly learningRate: number;
+  private readonly seed: number;
+
+  private mean: number[] = [];
+  private basis: number[][] = [];
+  private spread = 0;
+  private n = 0;
+
+  /**
+   * @param components   Number of principal components k. Capped at the
+   *                     dimension of the observed vectors.
+   * @param learningRate Step size of the Hebbian update, relative to the
+   *                     spread of the observations. Smaller values converge
+   *                     more slowly onto more accurate components.
+   * @param seed         Seed for the initial basis.
+   */
+  constructor(components: number, learningRate = 0.02, seed = 0) {
+    if (!Number.isInteger(components) || components < 1) {
+      throw new Error(
+        `PCA components must be a positive integer, got ${components}`,
+      );
+    }
+    if (!(learningRate > 0 && learningRate <= 1)) {
+      throw new Error(`PCA learning rate must be in (0, 1], got ${learningRate}`);
+    }
+    this.components = components;
+    this.learningRate = learningRate;
+    this.seed = seed >>> 0;
+  }
+
+  /** Number of vectors observed so far. */
+  get count(): number {
+    return this.n;
+  }
+
+  /** Dimension of the observed vectors (0 before the first). */
+  get dimension(): number {
+    return this.mean.length;
+  }
+
+  /** Copies of the current components (unit vectors), strongest first. */
+  getComponents(): number[][] {
+    return this.basis.map((w) => [...w]);
+  }
+
+  /** Copy of the running mean of the observed vectors. */
+  getMean(): number[] {
+    return [...this.mean];
+  }
+
+  observe(v: ArrayLike<number>): void {
+    if (this.n === 0) this.initialize(v.length);
+    this.checkDimension(v);
+    this.n++;
+    const centered = new Array<number>(v.length);
+    for (let i = 0; i < v.length; i++) {
+      this.mean[i] = this.mean[i]! + (v[i]! - this.mean[i]!) / this.n;
+      centered[i] = v[i]! - this.mean[i]!;
+    }
+    this.spread += (dot(centered, centered) - this.spread) / this.n;
+    if (this.spread === 0) return;
+    const rate = this.learningRate / this.spread;
+
+    const y = this.basis.map((w) => dot(w, centered));
+    const residual = [...centered];
+    this.basis.forEach((w, j) => {
+      for (let i = 0; i < w.length; i++) {
+        residual[i] = residual[i]! - y[j]! * w[i]!;
+      }
+      for (let i = 0; i < w.length; i++) {
+        w[i] = w[i]! + rate * y[j]! * residual[i]!;
+      }
+    });
+    orthonormalize(this.basis);
+  }
+
+  /**
+   * Coordinates of `v` along each component, relative to the running mean.
+   * All zeros before anything has been observed.
+   */
+  project(v: ArrayLike<number>): number[] {
+    if (this.n === 0) return new Array<number>(this.components).fill(0);
+    this.checkDimension(v);
+    const centered = Array.from(v, (x, i) => x - this.mean[i]!);
+    return this.basis.map((w) => dot(w, centered));
+  }
+
+  reset(): void {
+    this.mean = [];
+    this.basis = [];
+    this.spread = 0;
+    this.n = 0;
+  }
+
+  private initialize(dimension: number): void {
+    const next = mulberry32(this.seed);
+    this.mean = new Array<number>(dimension).fill(0);
+    this.basis = Array.from(
+      { length: Math.min(this.components, dimension) },
+      () => Array.from({ length: dimension }, () => next() * 2 - 1),
+    );
+    orthonormalize(this.basis);
+  }
+
+  private checkDimension(v: ArrayLike<number>): void {
+    if (v.length !== this.mean.length) {
+      throw new Error(
+        `Vector dimension mismatch: expected ${this.mean.length}, got ${v.length}`,
+      );
+    }
+  }
+}
+
+/**
+ * Modified Gram–Schmidt, in place. A row that collapses onto the earlier
+ * ones is replaced by the first standard basis vector orthogonal to them.
+ */
+function orthonormalize(rows: number[][]): void {
+  rows.forEach((row, j) => {
+    for (let pass = 0; ; pass++) {
+      for (let k = 0; k < j; k++) {
+        const projection = dot(rows[k]!, row);
+        for (let i = 0; i < row.length; i++) {
+          row[i] = row[i]! - projection * rows[k]![i]!;
+        }
+      }
+      const norm = Math.sqrt(dot(row, row));
+      if (norm > 1e-12) {
+        for (let i = 0; i < row.length; i++) row[i] = row[i]! / norm;
+        return;
+      }
+      row.fill(0);
+      row[pass % row.length] = 1;
+    }
+  });
+}
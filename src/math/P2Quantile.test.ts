@@ -0,0 +1,41 @@
+import { describe, it, expect } from "vitest";
+import { P2Quantile } from "./P2Quantile.js";
+
+/** Deterministic, evenly spread values in [0, 1). */
+const spread = (count: number) =>
+  Array.from({ length: count }, (_, i) => (i * 0.6180339887) % 1);
+
+describe("P2Quantile", () => {
+  it("estimates the median of a uniform stream", () => {
+    const q = new P2Quantile(0.5);
+    spread(1001).forEach((x) => q.update(x));
+    expect(q.value).toBeCloseTo(0.5, 2);
+  });
+
+  it("estimates a high quantile of a uniform stream", () => {
+    const q = new P2Quantile(0.95);
+    spread(1000).forEach((x) => q.update(x));
+    expect(q.value).toBeCloseTo(0.95, 2);
+    expect(q.count).toBe(1000);
+  });
+
+  it("uses the exact nearest-rank quantile for fewer than five samples", () => {
+    const q = new P2Quantile(0.9);
+    expect(q.value).toBeNaN();
+    [0.1, 0.3, 0.2].forEach((x) => q.update(x));
+    expect(q.value).toBe(0.3);
+  });
+
+  it("reset() discards all observations", () => {
+    const q = new P2Quantile(0.5);
+    spread(10).forEach((x) => q.update(x));
+    q.reset();
+    expect(q.count).toBe(0);
+    expect(q.value).toBeNaN();
+  });
+
+  it("rejects quantiles outside (0, 1)", () => {
+    expect(() => new P2Quantile(0)).toThrow();
+    expect(() => new P2Quantile(1)).toThrow();
+  });
+});
@@ -0,0 +1,114 @@
+/**
+ * Streaming quantile estimator using the P² algorithm (Jain & Chlamtac, 1985).
+ *
+ * Tracks a single quantile of an unbounded stream in O(1) memory by
+ * maintaining five markers whose heights are adjusted with piecewise-parabolic
+ * interpolation as observations arrive. Until five values have been seen the
+ * estimate is the exact nearest-rank quantile of the samples.
+ */
+export class P2Quantile {
+  private readonly p: number;
+  private readonly increments: number[];
+
+  private heights: number[];
+  private positions: number[];
+  private desired: number[];
+  private seen: number;
+
+  /** @param p The quantile to track, in (0, 1). */
+  constructor(p: number) {
+    if (!(p > 0 && p < 1)) {
+      throw new Error(`Quantile must be in the range (0, 1), got ${p}`);
+    }
+    this.p = p;
+    this.increments = [0, p / 2, p, (1 + p) / 2, 1];
+    this.heights = [];
+    this.positions = [];
+    this.desired = [];
+    this.seen = 0;
+  }
+
+  /** Number of observations seen so far. */
+  get count(): number {
+    return this.seen;
+  }
+
+  /** Current estimate of the quantile, or `NaN` before any observation. */
+  get value(): number {
+    if (this.seen === 0) return NaN;
+    if (this.seen < 5) {
+      const sorted = [...this.heights].sort((a, b) => a - b);
+      const rank = Math.ceil(this.p * sorted.length) - 1;
+      return sorted[Math.max(0, rank)]!;
+    }
+    return this.heights[2]!;
+  }
+
+  update(value: number): void {
+    this.seen++;
+    if (this.seen <= 5) {
+      this.heights.push(value);
+      if (this.seen === 5) {
+        this.heights.sort((a, b) => a - b);
+        this.positions = [1, 2, 3, 4, 5];
+        this.desired = this.increments.map((dn) => 1 + 4 * dn);
+      }
+      return;
+    }
+
+    const q = this.heights;
+    const n = this.positions;
+
+    let k: number;
+    if (value < q[0]!) {
+      q[0] = value;
+      k = 0;
+    } else if (value >= q[4]!) {
+      q[4] = value;
+      k = 3;
+    } else {
+      k = 0;
+      while (value >= q[k + 1]!) k++;
+    }
+
+    for (let i = k + 1; i < 5; i++) n[i]++;
+    for (let i = 0; i < 5; i++) this.desired[i] += this.increments[i]!;
+
+    for (let i = 1; i <= 3; i++) {
+      const d = this.desired[i]! - n[i]!;
+      if ((d >= 1 && n[i + 1]! - n[i]! > 1) || (d <= -1 && n[i - 1]! - n[i]! < -1)) {
+        const step = Math.sign(d);
+        const parabolic = this.parabolic(i, step);
+        q[i] =
+          q[i - 1]! < parabolic && parabolic < q[i + 1]!
+            ? parabolic
+            : this.linear(i, step);
+        n[i] += step;
+      }
+    }
+  }
+
+  reset(): void {
+    this.heights = [];
+    this.positions = [];
+    this.desired = [];
+    this.seen = 0;
+  }
+
+  private parabolic(i: number, d: number): number {
+    const q = this.heights;
+    const n = this.positions;
+    return (
+      q[i]! +
+      (d / (n[i + 1]! - n[i - 1]!)) *
+        ((n[i]! - n[i - 1]! + d) * ((q[i + 1]! - q[i]!) / (n[i + 1]! - n[i]!)) +
+          (n[i + 1]! - n[i]! - d) * ((q[i]! - q[i - 1]!) / (n[i]! - n[i - 1]!)))
+    );
+  }
+
+  private linear(i: number, d: number): number {
+    const q = this.heights;
+    const n = this.positions;
+    return q[i]! + (d * (q[i + d]! - q[i]!)) / (n[i + d]! - n[i]!);
+  }
+}
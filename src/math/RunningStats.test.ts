@@ -0,0 +1,31 @@
+import { describe, it, expect } from "vitest";
+import { RunningStats } from "./RunningStats.js";
+
+describe("RunningStats", () => {
+  it("tracks the per-dimension mean and sample variance", () => {
+    const stats = new RunningStats();
+    [
+      [1, 10],
+      [2, 10],
+      [3, 10],
+    ].forEach((v) => stats.update(v));
+    expect(stats.count).toBe(3);
+    expect(stats.mean()).toEqual([2, 10]);
+    expect(stats.variance()).toEqual([1, 0]);
+  });
+
+  it("reports zero variance before the second vector", () => {
+    const stats = new RunningStats();
+    expect(stats.mean()).toEqual([]);
+    stats.update([5, 7]);
+    expect(stats.variance()).toEqual([0, 0]);
+  });
+
+  it("reset() discards all observations", () => {
+    const stats = new RunningStats();
+    stats.update([1, 2]);
+    stats.reset();
+    expect(stats.count).toBe(0);
+    expect(stats.mean()).toEqual([]);
+  });
+});
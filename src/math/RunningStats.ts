@@ -0,0 +1,55 @@
+/**
+ * Per-dimension running mean and variance of a stream of vectors, using
+ * Welford's numerically stable online algorithm. O(d) memory regardless of
+ * how many vectors have been seen.
+ */
+export class RunningStats {
+  private seen: number;
+  private means: number[];
+  private m2: number[];
+
+  constructor() {
+    this.seen = 0;
+    this.means = [];
+    this.m2 = [];
+  }
+
+  /** Number of vectors seen so far. */
+  get count(): number {
+    return this.seen;
+  }
+
+  update(v: ArrayLike<number>): void {
+    if (this.seen === 0) {
+      this.means = new Array<number>(v.length).fill(0);
+      this.m2 = new Array<number>(v.length).fill(0);
+    }
+    this.seen++;
+    for (let i = 0; i < v.length; i++) {
+      const delta = v[i]! - this.means[i]!;
+      this.means[i] = this.means[i]! + delta / this.seen;
+      this.m2[i] = this.m2[i]! + delta * (v[i]! - this.means[i]!);
+    }
+  }
+
+  /** Per-dimension mean; empty before any vector. */
+  mean(): number[] {
+    return [...this.means];
+  }
+
+  /**
+   * Per-dimension sample variance (n − 1 denominator); all zeros before the
+   * second vector.
+   */
+  variance(): number[] {
+    return this.seen < 2
+      ? this.m2.map(() => 0)
+      : this.m2.map((m) => m / (this.seen - 1));
+  }
+
+  reset(): void {
+    this.seen = 0;
+    this.means = [];
+    this.m2 = [];
+  }
+}
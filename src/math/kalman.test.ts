@@ -0,0 +1,36 @@
+import { describe, it, expect } from "vitest";
+import { kalmanUpdate } from "./kalman.js";
+
+describe("kalmanUpdate", () => {
+  it("moves the estimate towards the measurement by the Kalman gain", () => {
+    // P⁻ = 1 + 0 = 1, K = 1 / (1 + 1) = 0.5
+    const { estimate, variance } = kalmanUpdate([0, 0], [1, 1], [2, -2], 0, 1);
+    expect(estimate[0]).toBeCloseTo(1);
+    expect(estimate[1]).toBeCloseTo(-1);
+    expect(variance[0]).toBeCloseTo(0.5);
+  });
+
+  it("shrinks variance with every observation when process noise is 0", () => {
+    let step = { estimate: [0], variance: [1] };
+    const variances: number[] = [];
+    for (let i = 0; i < 3; i++) {
+      step = kalmanUpdate(step.estimate, step.variance, [1], 0, 1);
+      variances.push(step.variance[0]!);
+    }
+    expect(variances[0]).toBeCloseTo(1 / 2);
+    expect(variances[1]).toBeCloseTo(1 / 3);
+    expect(variances[2]).toBeCloseTo(1 / 4);
+  });
+
+  it("trusts measurements more when measurement noise is small", () => {
+    const noisy = kalmanUpdate([0], [1], [1], 0, 10).estimate[0]!;
+    const precise = kalmanUpdate([0], [1], [1], 0, 0.1).estimate[0]!;
+    expect(precise).toBeGreaterThan(noisy);
+  });
+
+  it("throws on dimension mismatch", () => {
+    expect(() => kalmanUpdate([0, 0], [1], [1, 1], 0, 1)).toThrow(
+      "Vector dimension mismatch",
+    );
+  });
+});
@@ -0,0 +1,58 @@
+/**
+ * Diagonal (per-dimension) Kalman filter step for vector state estimation.
+ *
+ * Each dimension is treated as an independent random-walk process observed
+ * with Gaussian noise, so the filter needs only a variance per dimension
+ * rather than a full covariance matrix.
+ */
+
+/** Result of a single {@link kalmanUpdate} step. */
+export interface KalmanStep {
+  /** Posterior state estimate. */
+  estimate: number[];
+
+  /** Posterior per-dimension variance. */
+  variance: number[];
+}
+
+/**
+ * Performs one predict + correct step of a diagonal Kalman filter.
+ *
+ * Per dimension i:
+ *   P⁻ = P + Q
+ *   K  = P⁻ / (P⁻ + R)
+ *   x  = x + K · (z − x)
+ *   P  = (1 − K) · P⁻
+ *
+ * @param estimate         Prior state estimate x
+ * @param variance         Prior per-dimension variance P
+ * @param measurement      Observed vector z
+ * @param processNoise     Variance Q added per step (how fast the true state moves)
+ * @param measurementNoise Observation variance R (how noisy each measurement is)
+ * @returns                Posterior estimate and variance
+ */
+export function kalmanUpdate(
+  estimate: number[],
+  variance: number[],
+  measurement: number[],
+  processNoise: number,
+  measurementNoise: number,
+): KalmanStep {
+  if (
+    estimate.length !== measurement.length ||
+    variance.length !== measurement.length
+  ) {
+    throw new Error(
+      `Vector dimension mismatch: estimate=${estimate.length}, variance=${variance.length}, measurement=${measurement.length}`,
+    );
+  }
+  const nextEstimate = new Array(estimate.length) as number[];
+  const nextVariance = new Array(estimate.length) as number[];
+  for (let i = 0; i < estimate.length; i++) {
+    const predicted = variance[i]! + processNoise;
+    const gain = predicted / (predicted + measurementNoise);
+    nextEstimate[i] = estimate[i]! + gain * (measurement[i]! - estimate[i]!);
+    nextVariance[i] = (1 - gain) * predicted;
+  }
+  return { estimate: nextEstimate, variance: nextVariance };
+}
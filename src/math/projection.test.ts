@@ -0,0 +1,36 @@
+import { describe, it, expect } from "vitest";
+import { RandomProjection } from "./projection.js";
+import { cosineSimilarity } from "./vector.js";
+
+const wave = (d: number, freq: number, phase = 0) =>
+  Array.from({ length: d }, (_, i) => Math.sin(i * freq + phase));
+
+describe("RandomProjection", () => {
+  it("maps any input dimension onto the output dimension", () => {
+    const projection = new RandomProjection(16, 3);
+    expect(projection.project(wave(10, 0.5))).toHaveLength(16);
+    expect(projection.project(wave(300, 0.5))).toHaveLength(16);
+  });
+
+  it("is deterministic for a given seed and input dimension", () => {
+    const v = wave(64, 0.3);
+    expect(new RandomProjection(16, 42).project(v)).toEqual(
+      new RandomProjection(16, 42).project(v),
+    );
+    expect(new RandomProjection(16, 42).project(v)).not.toEqual(
+      new RandomProjection(16, 43).project(v),
+    );
+  });
+
+  it("approximately preserves cosine similarity", () => {
+    const a = wave(512, 0.37);
+    const b = a.map((x, i) => x + 0.8 * Math.cos(i * 1.3));
+    const projection = new RandomProjection(128, 42);
+    const projected = cosineSimilarity(projection.project(a), projection.project(b));
+    expect(Math.abs(projected - cosineSimilarity(a, b))).toBeLessThan(0.15);
+  });
+
+  it("rejects a non-positive output dimension", () => {
+    expect(() => new RandomProjection(0)).toThrow("positive integer");
+  });
+});
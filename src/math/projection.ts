@@ -0,0 +1,85 @@
+/**
+ * Seeded sparse random projection (Achlioptas, 2003).
+ *
+ * Maps vectors of any dimension onto a fixed output dimension with a random
+ * matrix whose entries are √3 · {+1, 0, −1} with probabilities
+ * {1/6, 2/3, 1/6}, scaled by 1/√k. Distances and cosine similarities are
+ * approximately preserved (Johnson–Lindenstrauss), and the matrix for a given
+ * (seed, input dimension) pair is always the same, so projections are
+ * reproducible across sessions and machines.
+ */
+export class RandomProjection {
+  /** Dimension of every projected vector. */
+  readonly outputDimension: number;
+
+  /** Seed the projection matrices are derived from. */
+  readonly seed: number;
+
+  private readonly matrices = new Map<number, Int8Array>();
+
+  /**
+   * @param outputDimension Dimension k of the projected vectors.
+   * @param seed            Unsigned 32-bit seed for the matrix generator.
+   */
+  constructor(outputDimension: number, seed = 0) {
+    if (!Number.isInteger(outputDimension) || outputDimension < 1) {
+      throw new Error(
+        `Projection dimension must be a positive integer, got ${outputDimension}`,
+      );
+    }
+    if (!Number.isInteger(seed)) {
+      throw new Error(`Projection seed must be an integer, got ${seed}`);
+    }
+    this.outputDimension = outputDimension;
+    this.seed = seed >>> 0;
+  }
+
+  /**
+   * Projects `v` onto the output dimension. The matrix for `v.length` is
+   * generated on first use and cached.
+   */
+  project(v: ArrayLike<number>): number[] {
+    const d = v.length;
+    const k = this.outputDimension;
+    const signs = this.matrix(d);
+    const factor = Math.sqrt(3 / k);
+    const out = new Array<number>(k).fill(0);
+    for (let row = 0; row < k; row++) {
+      const offset = row * d;
+      let sum = 0;
+      for (let i = 0; i < d; i++) {
+        const s = signs[offset + i]!;
+        if (s !== 0) sum += s * v[i]!;
+      }
+      out[row] = sum * factor;
+    }
+    return out;
+  }
+
+  private matrix(inputDimension: number): Int8Array {
+    let signs = this.matrices.get(inputDimension);
+    if (signs === undefined) {
+      const next = mulberry32(
+        (this.seed ^ Math.imul(inputDimension, 0x9e3779b9)) >>> 0,
+      );
+      signs = new Int8Array(this.outputDimension * inputDimension);
+      for (let i = 0; i < signs.length; i++) {
+        const r = next();
+        signs[i] = r < 1 / 6 ? 1 : r < 1 / 3 ? -1 : 0;
+      }
+      this.matrices.set(inputDimension, signs);
+    }
+    return signs;
+  }
+}
+
+/** Small, fast, seedable PRNG returning floats in [0, 1). */
+export function mulberry32(seed: number): () => number {
+  let a = seed;
+  return () => {
+    a = (a + 0x6d2b79f5) | 0;
+    let t = Math.imul(a ^ (a >>> 15), 1 | a);
+    t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t;
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
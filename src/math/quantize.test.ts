@@ -0,0 +1,57 @@
+import { describe, it, expect } from "vitest";
+import { PackedVector } from "./quantize.js";
+
+const v = [0.5, -0.25, 0.1, -1, 0, 3.14159];
+
+describe("PackedVector", () => {
+  it("round-trips f32 at single precision", () => {
+    const packed = PackedVector.from(v, "f32");
+    expect(packed.length).toBe(v.length);
+    expect(packed.byteLength).toBe(v.length * 4);
+    expect(packed.toArray()).toEqual([...Float32Array.from(v)]);
+  });
+
+  it("round-trips f16 within half-precision error", () => {
+    const packed = PackedVector.from(v, "f16");
+    expect(packed.byteLength).toBe(v.length * 2);
+    packed.toArray().forEach((x, i) => {
+      expect(Math.abs(x - v[i]!)).toBeLessThanOrEqual(Math.abs(v[i]!) / 1024);
+    });
+    // Exactly representable values survive unchanged.
+    expect(PackedVector.from([0.5, -0.25, 1, 0], "f16").toArray()).toEqual([
+      0.5, -0.25, 1, 0,
+    ]);
+  });
+
+  it("handles f16 subnormals, overflow and ties to even", () => {
+    expect(PackedVector.from([2 ** -24], "f16").toArray()).toEqual([2 ** -24]);
+    expect(PackedVector.from([2 ** -30], "f16").toArray()).toEqual([0]);
+    expect(PackedVector.from([1e6, -1e6], "f16").toArray()).toEqual([
+      Infinity,
+      -Infinity,
+    ]);
+    // 1 + 2⁻¹¹ lies halfway between 1 and 1 + 2⁻¹⁰: rounds to the even 1.
+    expect(PackedVector.from([1 + 2 ** -11], "f16").toArray()).toEqual([1]);
+  });
+
+  it("quantizes int8 against the largest magnitude", () => {
+    const packed = PackedVector.from(v, "int8");
+    expect(packed.byteLength).toBe(v.length + 8);
+    const max = Math.max(...v.map(Math.abs));
+    packed.toArray().forEach((x, i) => {
+      expect(Math.abs(x - v[i]!)).toBeLessThanOrEqual(max / 254 + 1e-12);
+    });
+    expect(PackedVector.from([0, 0], "int8").toArray()).toEqual([0, 0]);
+  });
+
+  it("is stable when re-packed", () => {
+    for (const precision of ["f32", "f16"] as const) {
+      const once = PackedVector.from(v, precision).toArray();
+      expect(PackedVector.from(once, precision).toArray()).toEqual(once);
+    }
+    const once = PackedVector.from(v, "int8").toArray();
+    PackedVector.from(once, "int8")
+      .toArray()
+      .forEach((x, i) => expect(x).toBeCloseTo(once[i]!, 12));
+  });
+});
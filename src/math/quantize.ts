@@ -0,0 +1,122 @@
+/**
+ * Precision at which the engine retains vectors. `"f64"` keeps plain
+ * double-precision arrays; the others store a {@link PackedVector}.
+ */
+export type StoragePrecision = "f64" | "f32" | "f16" | "int8";
+
+const FLOAT = new Float32Array(1);
+const BITS = new Uint32Array(FLOAT.buffer);
+
+/**
+ * A vector stored at reduced precision and dequantized on demand.
+ *
+ * - `"f32"`: single precision, 4 bytes per value.
+ * - `"f16"`: IEEE 754 half precision (≈3 significant digits, range ±65504),
+ *   2 bytes per value.
+ * - `"int8"`: symmetric linear quantization against the vector's largest
+ *   magnitude, 1 byte per value plus one scale factor. Relative error per
+ *   value is at most 1/254 of that magnitude.
+ *
+ * Values must be finite.
+ */
+export class PackedVector {
+  /** Precision the values are stored at. */
+  readonly precision: Exclude<StoragePrecision, "f64">;
+
+  /** Number of values in the vector. */
+  readonly length: number;
+
+  private readonly data: Float32Array | Uint16Array | Int8Array;
+  private readonly scale: number;
+
+  private constructor(
+    precision: Exclude<StoragePrecision, "f64">,
+    data: Float32Array | Uint16Array | Int8Array,
+    scale: number,
+  ) {
+    this.precision = precision;
+    this.length = data.length;
+    this.data = data;
+    this.scale = scale;
+  }
+
+  /** Packs `v` at `precision`. */
+  static from(
+    v: ArrayLike<number>,
+    precision: Exclude<StoragePrecision, "f64">,
+  ): PackedVector {
+    switch (precision) {
+      case "f32":
+        return new PackedVector(precision, Float32Array.from(v), 1);
+      case "f16": {
+        const data = new Uint16Array(v.length);
+        for (let i = 0; i < v.length; i++) data[i] = toHalf(v[i]!);
+        return new PackedVector(precision, data, 1);
+      }
+      case "int8": {
+        let max = 0;
+        for (let i = 0; i < v.length; i++) max = Math.max(max, Math.abs(v[i]!));
+        const scale = max / 127;
+        const data = new Int8Array(v.length);
+        if (scale > 0) {
+          for (let i = 0; i < v.length; i++) data[i] = Math.round(v[i]! / scale);
+        }
+        return new PackedVector(precision, data, scale);
+      }
+    }
+  }
+
+  /** Bytes held by the packed values (plus the int8 scale factor). */
+  get byteLength(): number {
+    return this.data.byteLength + (this.precision === "int8" ? 8 : 0);
+  }
+
+  /** Dequantizes the vector into a new array. */
+  toArray(): number[] {
+    const out = new Array<number>(this.length);
+    const { data, scale } = this;
+    if (this.precision === "f16") {
+      for (let i = 0; i < data.length; i++) out[i] = fromHalf(data[i]!);
+    } else {
+      for (let i = 0; i < data.length; i++) out[i] = data[i]! * scale;
+    }
+    return out;
+  }
+}
+
+/** Rounds `value` to the nearest half-precision float (ties to even) and returns its bits. */
+function toHalf(value: number): number {
+  FLOAT[0] = value;
+  const x = BITS[0]!;
+  const sign = (x >>> 16) & 0x8000;
+  const exponent = ((x >>> 23) & 0xff) - 127 + 15;
+  let mantissa = x & 0x7fffff;
+
+  if (exponent >= 0x1f) return sign | 0x7c00; // Overflow: ±Infinity.
+  if (exponent <= 0) {
+    // Subnormal (or zero) in half precision.
+    if (exponent < -10) return sign;
+    mantissa |= 0x800000;
+    const shift = 14 - exponent;
+    let half = mantissa >>> shift;
+    const rest = mantissa & ((1 << shift) - 1);
+    const halfway = 1 << (shift - 1);
+    if (rest > halfway || (rest === halfway && (half & 1) === 1)) half++;
+    return sign | half;
+  }
+  let half = (exponent << 10) | (mantissa >>> 13);
+  const rest = mantissa & 0x1fff;
+  // A carry out of the mantissa correctly bumps the exponent.
+  if (rest > 0x1000 || (rest === 0x1000 && (half & 1) === 1)) half++;
+  return sign | half;
+}
+
+/** Decodes half-precision bits into a number. */
+function fromHalf(bits: number): number {
+  const sign = bits & 0x8000 ? -1 : 1;
+  const exponent = (bits >>> 10) & 0x1f;
+  const mantissa = bits & 0x3ff;
+  if (exponent === 0) return sign * mantissa * 2 ** -24;
+  if (exponent === 0x1f) return mantissa === 0 ? sign * Infinity : NaN;
+  return sign * (1 + mantissa / 1024) * 2 ** (exponent - 15);
+}
@@ -0,0 +1,44 @@
+import { describe, it, expect } from "vitest";
+import {
+  sparseCosineSimilarity,
+  sparseDot,
+  toDense,
+  topK,
+  validateSparse,
+} from "./sparse.js";
+
+const sparse = { indices: [1, 3], values: [2, 4], dimension: 5 };
+
+describe("sparse vectors", () => {
+  it("expands to a dense array, summing duplicate indices", () => {
+    expect(toDense(sparse)).toEqual([0, 2, 0, 4, 0]);
+    expect(toDense({ indices: [0, 0], values: [1, 2], dimension: 2 })).toEqual([
+      3, 0,
+    ]);
+  });
+
+  it("computes dot products and cosine similarity against dense vectors", () => {
+    expect(sparseDot(sparse, [1, 1, 1, 1, 1])).toBe(6);
+    expect(sparseCosineSimilarity(sparse, [0, 1, 0, 2, 0])).toBeCloseTo(1);
+    expect(sparseCosineSimilarity(sparse, [1, 0, 0, 0, 0])).toBe(0);
+    expect(() => sparseDot(sparse, [1, 2])).toThrow("dimension mismatch");
+  });
+
+  it("reports malformed sparse vectors", () => {
+    expect(validateSparse(sparse)).toBeNull();
+    expect(validateSparse({ ...sparse, values: [1] })).toMatch("same length");
+    expect(validateSparse({ ...sparse, indices: [1, 5] })).toMatch("out of range");
+    expect(validateSparse({ ...sparse, dimension: 0 })).toMatch("positive integer");
+  });
+
+  it("keeps the largest-magnitude entries in index order", () => {
+    expect(topK([0.1, -5, 3, 0], 2)).toEqual({
+      indices: [1, 2],
+      values: [-5, 3],
+      dimension: 4,
+    });
+    expect(topK([1, -1, 1], 2).indices).toEqual([0, 1]);
+    expect(topK([2, 1], 5)).toEqual({ indices: [0, 1], values: [2, 1], dimension: 2 });
+    expect(validateSparse(topK([0, 4, 0], 1))).toBeNull();
+  });
+});
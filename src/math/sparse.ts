@@ -0,0 +1,95 @@
+/**
+ * Sparse vector helpers, for high-dimensional embeddings that are mostly
+ * zeros (e.g. SPLADE-style lexical embeddings).
+ */
+
+/** A vector stored as its non-zero entries. */
+export interface SparseVector {
+  /** Indices of the non-zero entries, each in [0, dimension). */
+  indices: ArrayLike<number>;
+
+  /** Values of the non-zero entries, parallel to `indices`. */
+  values: ArrayLike<number>;
+
+  /** Full dimension of the vector. */
+  dimension: number;
+}
+
+/**
+ * Checks that `v` is well-formed and returns a description of the first
+ * problem, or `null` if there is none.
+ */
+export function validateSparse(v: SparseVector): string | null {
+  if (!Number.isInteger(v.dimension) || v.dimension < 1) {
+    return `dimension must be a positive integer, got ${v.dimension}`;
+  }
+  if (v.indices.length !== v.values.length) {
+    return `indices and values must have the same length, got ${v.indices.length} and ${v.values.length}`;
+  }
+  for (let i = 0; i < v.indices.length; i++) {
+    const index = v.indices[i]!;
+    if (!Number.isInteger(index) || index < 0 || index >= v.dimension) {
+      return `index ${index} is out of range for dimension ${v.dimension}`;
+    }
+  }
+  return null;
+}
+
+/**
+ * Expands a sparse vector into a dense array. Duplicate indices are summed.
+ */
+export function toDense(v: SparseVector): number[] {
+  const dense = new Array<number>(v.dimension).fill(0);
+  for (let i = 0; i < v.indices.length; i++) {
+    const index = v.indices[i]!;
+    dense[index] = dense[index]! + v.values[i]!;
+  }
+  return dense;
+}
+
+/**
+ * Dot product of a sparse and a dense vector in O(nnz) rather than O(d).
+ */
+export function sparseDot(a: SparseVector, b: ArrayLike<number>): number {
+  if (a.dimension !== b.length) {
+    throw new Error(
+      `Vector dimension mismatch: a=${a.dimension}, b=${b.length}`,
+    );
+  }
+  let sum = 0;
+  for (let i = 0; i < a.indices.length; i++) {
+    sum += a.values[i]! * b[a.indices[i]!]!;
+  }
+  return sum;
+}
+
+/**
+ * Cosine similarity between a sparse and a dense vector. Returns 0 if either
+ * has zero magnitude. Assumes `a` has no duplicate indices.
+ */
+export function sparseCosineSimilarity(
+  a: SparseVector,
+  b: ArrayLike<number>,
+): number {
+  let normA = 0;
+  for (let i = 0; i < a.values.length; i++) normA += a.values[i]! ** 2;
+  let normB = 0;
+  for (let i = 0; i < b.length; i++) normB += b[i]! ** 2;
+  if (normA === 0 || normB === 0) return 0;
+  return sparseDot(a, b) / Math.sqrt(normA * normB);
+}
+
+/**
+ * Keeps the `k` entries of `v` with the largest magnitude, in index order.
+ * Ties are broken towards lower indices.
+ */
+export function topK(
+  v: ArrayLike<number>,
+  k: number,
+): { indices: number[]; values: number[]; dimension: number } {
+  const indices = Array.from({ length: v.length }, (_, i) => i)
+    .sort((a, b) => Math.abs(v[b]!) - Math.abs(v[a]!) || a - b)
+    .slice(0, k)
+    .sort((a, b) => a - b);
+  return { indices, values: indices.map((i) => v[i]!), dimension: v.length };
+}
@@ -0,0 +1,135 @@
+import { describe, it, expect } from "vitest";
+import {
+  cosineSimilarity,
+  dot,
+  emaFusion,
+  magnitude,
+  normalize,
+} from "./vector.js";
+
+/**
+ * Seeded property checks: each property runs against RUNS random cases
+ * (reproducible from the seed printed on failure), with components drawn at
+ * magnitudes from subnormal to near-overflow.
+ */
+const RUNS = 500;
+
+function mulberry32(seed: number): () => number {
+  let a = seed;
+  return () => {
+    a = (a + 0x6d2b79f5) | 0;
+    let t = Math.imul(a ^ (a >>> 15), 1 | a);
+    t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t;
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+/** A random vector of dimension 1–16 whose components share a random scale. */
+function vector(next: () => number, dimension?: number): number[] {
+  const d = dimension ?? 1 + Math.floor(next() * 16);
+  const scale = 10 ** (Math.floor(next() * 600) - 300);
+  return Array.from({ length: d }, () => {
+    const r = next();
+    if (r < 0.05) return 0;
+    if (r < 0.1) return 5e-324 * Math.floor(next() * 10); // subnormal
+    return (next() * 2 - 1) * scale;
+  });
+}
+
+function property(name: string, check: (next: () => number) => void): void {
+  it(name, () => {
+    for (let seed = 1; seed <= RUNS; seed++) {
+      try {
+        check(mulberry32(seed));
+      } catch (err) {
+        throw new Error(`${name} failed for seed ${seed}: ${String(err)}`);
+      }
+    }
+  });
+}
+
+const nonZero = (v: number[]) => v.some((x) => x !== 0);
+
+describe("vector math properties", () => {
+  property("dot is symmetric", (next) => {
+    const a = vector(next);
+    const b = vector(next, a.length);
+    expect(dot(a, b)).toBe(dot(b, a));
+  });
+
+  property("magnitude is non-negative, finite and scale-aware", (next) => {
+    const v = vector(next);
+    const m = magnitude(v);
+    expect(m).toBeGreaterThanOrEqual(0);
+    expect(Number.isFinite(m)).toBe(true);
+    expect(m === 0).toBe(!nonZero(v));
+    const max = Math.max(...v.map(Math.abs));
+    expect(m).toBeGreaterThanOrEqual(max);
+    expect(m).toBeLessThanOrEqual(max * Math.sqrt(v.length) * (1 + 1e-12));
+  });
+
+  property("cosine similarity is symmetric and bounded", (next) => {
+    const a = vector(next);
+    const b = vector(next, a.length);
+    const s = cosineSimilarity(a, b);
+    expect(s).toBe(cosineSimilarity(b, a));
+    expect(s).toBeGreaterThanOrEqual(-1);
+    expect(s).toBeLessThanOrEqual(1);
+  });
+
+  property("a non-zero vector has similarity 1 with itself", (next) => {
+    const v = vector(next);
+    if (!nonZero(v)) return;
+    expect(cosineSimilarity(v, v)).toBeCloseTo(1, 9);
+    expect(cosineSimilarity(v, v.map((x) => -x))).toBeCloseTo(-1, 9);
+  });
+
+  property("cosine similarity ignores positive scaling", (next) => {
+    const a = vector(next);
+    const b = vector(next, a.length);
+    const k = 10 ** (Math.floor(next() * 40) - 20);
+    const scaled = a.map((x) => x * k);
+    // Skip cases where scaling itself overflowed or lost precision to subnormals.
+    const lossy = scaled.some(
+      (x, i) =>
+        !Number.isFinite(x) ||
+        (a[i] !== 0 && Math.abs(x) < 2.2250738585072014e-308),
+    );
+    if (lossy) return;
+    expect(cosineSimilarity(scaled, b)).toBeCloseTo(cosineSimilarity(a, b), 9);
+  });
+
+  property("normalize yields a unit vector in the same direction", (next) => {
+    const v = vector(next);
+    const n = normalize(v);
+    if (!nonZero(v)) {
+      expect(n.every((x) => x === 0)).toBe(true);
+      return;
+    }
+    expect(magnitude(n)).toBeCloseTo(1, 9);
+    expect(cosineSimilarity(n, v)).toBeCloseTo(1, 9);
+  });
+
+  property("EMA fusion stays between its inputs", (next) => {
+    const a = vector(next);
+    const b = vector(next, a.length);
+    const alpha = Math.max(Number.EPSILON, next());
+    const fused = emaFusion(a, b, alpha);
+    fused.forEach((x, i) => {
+      const lo = Math.min(a[i]!, b[i]!);
+      const hi = Math.max(a[i]!, b[i]!);
+      const slack = Math.max(Math.abs(lo), Math.abs(hi)) * 1e-15 + 5e-324;
+      expect(x).toBeGreaterThanOrEqual(lo - slack);
+      expect(x).toBeLessThanOrEqual(hi + slack);
+    });
+    expect(emaFusion(a, b, 1)).toEqual(a);
+  });
+
+  it("propagates NaN and infinities instead of masking them", () => {
+    expect(magnitude([NaN, 1])).toBeNaN();
+    expect(magnitude([Infinity, 1])).toBe(Infinity);
+    expect(cosineSimilarity([NaN, 1], [1, 1])).toBeNaN();
+    expect(cosineSimilarity([Infinity, 1], [1, 1])).toBeNaN();
+    expect(normalize([NaN, 1]).some(Number.isNaN)).toBe(true);
+  });
+});
@@ -1,12 +1,52 @@
 import { describe, it, expect } from "vitest";
 import {
+  dot,
+  magnitude,
   add,
   scale,
   cosineSimilarity,
   emaFusion,
   normalize,
+  mean,
+  weightedMean,
+  euclideanDistance,
+  manhattanDistance,
+  angularDistance,
+  mahalanobisDistance,
+  jsDivergence,
+  klDivergence,
+  standardize,
+  similarityMatrix,
+  median,
 } from "./vector.js";
 
+describe("dot", () => {
+  it("sums element-wise products", () => {
+    expect(dot([1, 2, 3], [4, 5, 6])).toBe(32);
+  });
+
+  it("handles lengths that are not a multiple of the unroll factor", () => {
+    const a = Array.from({ length: 11 }, (_, i) => i + 1);
+    const expected = a.reduce((sum, val) => sum + val * val, 0);
+    expect(dot(a, a)).toBe(expected);
+  });
+
+  it("returns 0 for empty vectors", () => {
+    expect(dot([], [])).toBe(0);
+  });
+
+  it("throws on dimension mismatch", () => {
+    expect(() => dot([1, 2], [1])).toThrow("Vector dimension mismatch");
+  });
+});
+
+describe("magnitude", () => {
+  it("computes the L2 norm", () => {
+    expect(magnitude([3, 4])).toBe(5);
+    expect(magnitude([0, 0, 0])).toBe(0);
+  });
+});
+
 describe("add", () => {
   it("adds two vectors element-wise", () => {
     expect(add([1, 2, 3], [4, 5, 6])).toEqual([5, 7, 9]);
@@ -69,6 +109,46 @@ describe("cosineSimilarity", () => {
   });
 });
 
+describe("euclideanDistance", () => {
+  it("computes the L2 distance", () => {
+    expect(euclideanDistance([0, 0], [3, 4])).toBeCloseTo(5);
+  });
+
+  it("returns 0 for identical vectors", () => {
+    expect(euclideanDistance([1, 2, 3], [1, 2, 3])).toBe(0);
+  });
+
+  it("throws on dimension mismatch", () => {
+    expect(() => euclideanDistance([1], [1, 2])).toThrow(
+      "Vector dimension mismatch",
+    );
+  });
+});
+
+describe("manhattanDistance", () => {
+  it("computes the L1 distance", () => {
+    expect(manhattanDistance([1, -1], [4, 3])).toBe(7);
+  });
+
+  it("throws on dimension mismatch", () => {
+    expect(() => manhattanDistance([1], [1, 2])).toThrow(
+      "Vector dimension mismatch",
+    );
+  });
+});
+
+describe("angularDistance", () => {
+  it("returns 0 for parallel, 0.5 for orthogonal, and 1 for opposite vectors", () => {
+    expect(angularDistance([1, 0], [2, 0])).toBeCloseTo(0);
+    expect(angularDistance([1, 0], [0, 1])).toBeCloseTo(0.5);
+    expect(angularDistance([1, 0], [-1, 0])).toBeCloseTo(1);
+  });
+
+  it("is scale-invariant", () => {
+    expect(angularDistance([1, 1], [5, 0])).toBeCloseTo(0.25);
+  });
+});
+
 describe("normalize", () => {
   it("produces a unit vector", () => {
     const result = normalize([3, 4]);
@@ -132,3 +212,155 @@ describe("emaFusion", () => {
     );
   });
 });
+
+describe("mean", () => {
+  it("computes the element-wise mean", () => {
+    expect(mean([[1, 2], [3, 4], [5, 6]])).toEqual([3, 4]);
+  });
+
+  it("returns the vector itself for a single input", () => {
+    expect(mean([[7, -1]])).toEqual([7, -1]);
+  });
+
+  it("throws on an empty list", () => {
+    expect(() => mean([])).toThrow("empty list of vectors");
+  });
+
+  it("throws on dimension mismatch", () => {
+    expect(() => mean([[1, 2], [1, 2, 3]])).toThrow("Vector dimension mismatch");
+  });
+});
+
+describe("median", () => {
+  it("takes the per-dimension median of an odd count", () => {
+    expect(median([[1, 9], [100, 2], [3, 5]])).toEqual([3, 5]);
+  });
+
+  it("averages the two middle values of an even count", () => {
+    expect(median([[1], [4], [2], [10]])).toEqual([3]);
+  });
+
+  it("rejects an empty list", () => {
+    expect(() => median([])).toThrow();
+  });
+});
+
+describe("weightedMean", () => {
+  it("weights vectors proportionally", () => {
+    const result = weightedMean([[0, 0], [4, 8]], [1, 3]);
+    expect(result[0]).toBeCloseTo(3);
+    expect(result[1]).toBeCloseTo(6);
+  });
+
+  it("throws when weights do not match the vector count", () => {
+    expect(() => weightedMean([[1], [2]], [1])).toThrow("Weight count mismatch");
+  });
+
+  it("throws when weights sum to zero", () => {
+    expect(() => weightedMean([[1], [2]], [0, 0])).toThrow(
+      "Weights must sum to a positive value",
+    );
+  });
+});
+
+describe("standardize", () => {
+  it("centers and scales to unit standard deviation", () => {
+    expect(standardize([1, 3])).toEqual([-1, 1]);
+    expect(standardize([2, 4, 6, 8]).reduce((a, b) => a + b, 0)).toBeCloseTo(0);
+  });
+
+  it("returns a zero vector when all components are equal", () => {
+    expect(standardize([5, 5, 5])).toEqual([0, 0, 0]);
+  });
+});
+
+describe("mahalanobisDistance", () => {
+  it("scales each dimension by its variance", () => {
+    expect(mahalanobisDistance([0, 0], [2, 3], [4, 9])).toBeCloseTo(Math.SQRT2);
+  });
+
+  it("equals the Euclidean distance under unit variance", () => {
+    expect(mahalanobisDistance([1, 2], [4, 6], [1, 1])).toBeCloseTo(5);
+  });
+
+  it("rejects a variance of the wrong dimension", () => {
+    expect(() => mahalanobisDistance([1, 2], [3, 4], [1])).toThrow();
+  });
+});
+
+describe("klDivergence", () => {
+  it("is 0 for identical distributions, independent of scale", () => {
+    expect(klDivergence([1, 2, 1], [2, 4, 2])).toBeCloseTo(0);
+  });
+
+  it("matches Σ p ln(p / q) and is asymmetric", () => {
+    const p = [0.5, 0.5];
+    const q = [0.9, 0.1];
+    expect(klDivergence(p, q)).toBeCloseTo(
+      0.5 * Math.log(0.5 / 0.9) + 0.5 * Math.log(0.5 / 0.1),
+    );
+    expect(klDivergence(q, p)).not.toBeCloseTo(klDivergence(p, q));
+  });
+
+  it("stays finite where q has no mass", () => {
+    expect(Number.isFinite(klDivergence([1, 1], [1, 0]))).toBe(true);
+  });
+});
+
+describe("jsDivergence", () => {
+  it("is 0 for identical and 1 for disjoint distributions", () => {
+    expect(jsDivergence([0.2, 0.8], [1, 4])).toBeCloseTo(0);
+    expect(jsDivergence([1, 0], [0, 1])).toBeCloseTo(1);
+  });
+
+  it("is symmetric", () => {
+    expect(jsDivergence([0.7, 0.2, 0.1], [0.1, 0.3, 0.6])).toBeCloseTo(
+      jsDivergence([0.1, 0.3, 0.6], [0.7, 0.2, 0.1]),
+    );
+  });
+
+  it("rejects negative components and empty mass", () => {
+    expect(() => jsDivergence([1, -1], [1, 1])).toThrow(
+      "Distribution component 1 is negative: -1",
+    );
+    expect(() => jsDivergence([0, 0], [1, 1])).toThrow("no mass");
+  });
+});
+
+describe("similarityMatrix", () => {
+  it("computes the symmetric pairwise cosine similarities", () => {
+    const vectors = [
+      [1, 0],
+      [0, 2],
+      [1, 1],
+    ];
+    const matrix = similarityMatrix(vectors);
+    vectors.forEach((a, i) =>
+      vectors.forEach((b, j) =>
+        expect(matrix[i]![j]).toBeCloseTo(cosineSimilarity(a, b)),
+      ),
+    );
+    expect(matrix[0]![0]).toBe(1);
+  });
+
+  it("reports 0 for zero vectors, including on the diagonal", () => {
+    expect(similarityMatrix([[0, 0], [1, 0]])).toEqual([
+      [0, 0],
+      [0, 1],
+    ]);
+  });
+});
+
+describe("typed array inputs", () => {
+  it("accepts Float32Array and Float64Array vectors", () => {
+    const a = new Float32Array([1, 0, 0]);
+    const b = new Float64Array([0, 1, 0]);
+    expect(cosineSimilarity(a, b)).toBe(0);
+    expect(dot(a, a)).toBe(1);
+    expect(euclideanDistance(a, b)).toBeCloseTo(Math.SQRT2);
+    expect(manhattanDistance(a, b)).toBe(2);
+    expect(normalize(new Float32Array([3, 4]))).toEqual([0.6, 0.8]);
+    expect(add(a, b)).toEqual([1, 1, 0]);
+    expect(emaFusion(a, b, 0.5)).toEqual([0.5, 0.5, 0]);
+  });
+});
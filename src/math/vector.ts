@@ -1,12 +1,17 @@
 /**
  * Pure vector math utilities for semantic state estimation.
  *
- * Provides vector addition, scalar multiplication, normalization,
- * cosine similarity, and EMA (Exponential Moving Average) fusion.
+ * Every function accepts plain arrays as well as typed arrays such as the
+ * `Float32Array` embeddings produced by `WorkerManager`, and returns a fresh
+ * `number[]` where it returns a vector.
+ *
+ * Provides dot products, magnitudes, vector addition, scalar multiplication,
+ * normalization, cosine similarity, distance metrics, EMA (Exponential Moving Average)
+ * fusion, and (weighted) mean fusion.
  */
 
 /** Asserts that two vectors have the same length, throwing otherwise. */
-function assertSameDimension(a: number[], b: number[]): void {
+function assertSameDimension(a: ArrayLike<number>, b: ArrayLike<number>): void {
   if (a.length !== b.length) {
     throw new Error(
       `Vector dimension mismatch: a=${a.length}, b=${b.length}`,
@@ -14,6 +19,77 @@ function assertSameDimension(a: number[], b: number[]): void {
   }
 }
 
+/**
+ * Magnitudes within [SAFE_MIN_MAGNITUDE, SAFE_MAX_MAGNITUDE] can be computed
+ * (and multiplied together) from plain sums of squares without overflowing
+ * or losing precision to subnormals; others are rescaled first.
+ */
+const SAFE_MIN_MAGNITUDE = 1e-150;
+const SAFE_MAX_MAGNITUDE = 1e150;
+
+/**
+ * The L2 norm of `v` as `max · norm`, where `max` is the largest absolute
+ * component, so extreme magnitudes neither overflow nor underflow.
+ */
+function scaledNorm(v: ArrayLike<number>): { max: number; norm: number } {
+  let max = 0;
+  for (let i = 0; i < v.length; i++) {
+    const x = Math.abs(v[i]!);
+    if (x > max || Number.isNaN(x)) max = x;
+  }
+  if (max === 0 || !Number.isFinite(max)) return { max, norm: 1 };
+  let sum = 0;
+  for (let i = 0; i < v.length; i++) {
+    sum += (v[i]! / max) ** 2;
+  }
+  return { max, norm: Math.sqrt(sum) };
+}
+
+/**
+ * Computes the dot product of two vectors.
+ *
+ * The loop is unrolled over four independent accumulators, which lets the
+ * JIT overlap the multiply-adds instead of serializing on a single sum.
+ * `benches/vector.bench.ts` compares it with a single-accumulator `reduce`
+ * at 384 and 3072 dimensions.
+ *
+ * @param a First vector
+ * @param b Second vector
+ * @returns  Σ a_i · b_i
+ */
+export function dot(a: ArrayLike<number>, b: ArrayLike<number>): number {
+  assertSameDimension(a, b);
+  const n = a.length;
+  const tail = n - (n % 4);
+  let s0 = 0;
+  let s1 = 0;
+  let s2 = 0;
+  let s3 = 0;
+  for (let i = 0; i < tail; i += 4) {
+    s0 += a[i]! * b[i]!;
+    s1 += a[i + 1]! * b[i + 1]!;
+    s2 += a[i + 2]! * b[i + 2]!;
+    s3 += a[i + 3]! * b[i + 3]!;
+  }
+  for (let i = tail; i < n; i++) {
+    s0 += a[i]! * b[i]!;
+  }
+  return s0 + s1 + (s2 + s3);
+}
+
+/**
+ * Computes the L2 norm (magnitude) of a vector.
+ *
+ * @param v Input vector
+ * @returns  ‖v‖ ≥ 0
+ */
+export function magnitude(v: ArrayLike<number>): number {
+  const m = Math.sqrt(dot(v, v));
+  if (m >= SAFE_MIN_MAGNITUDE && m <= SAFE_MAX_MAGNITUDE) return m;
+  const { max, norm } = scaledNorm(v);
+  return max * norm;
+}
+
 /**
  * Adds two vectors element-wise.
  *
@@ -21,9 +97,9 @@ function assertSameDimension(a: number[], b: number[]): void {
  * @param b Second vector
  * @returns  Element-wise sum
  */
-export function add(a: number[], b: number[]): number[] {
+export function add(a: ArrayLike<number>, b: ArrayLike<number>): number[] {
   assertSameDimension(a, b);
-  return a.map((val, i) => val + b[i]!);
+  return Array.from(a, (val, i) => val + b[i]!);
 }
 
 /**
@@ -33,8 +109,8 @@ export function add(a: number[], b: number[]): number[] {
  * @param scalar Scalar multiplier
  * @returns      Scaled vector
  */
-export function scale(v: number[], scalar: number): number[] {
-  return v.map((val) => val * scalar);
+export function scale(v: ArrayLike<number>, scalar: number): number[] {
+  return Array.from(v, (val) => val * scalar);
 }
 
 /**
@@ -43,12 +119,36 @@ export function scale(v: number[], scalar: number): number[] {
  * @param v Input vector
  * @returns  Unit vector, or zero vector if input magnitude is 0
  */
-export function normalize(v: number[]): number[] {
-  const mag = Math.sqrt(v.reduce((sum, val) => sum + val * val, 0));
+export function normalize(v: ArrayLike<number>): number[] {
+  const mag = magnitude(v);
   if (mag === 0) {
-    return v.map(() => 0);
+    return new Array<number>(v.length).fill(0);
+  }
+  if (mag >= SAFE_MIN_MAGNITUDE && mag <= SAFE_MAX_MAGNITUDE) {
+    return Array.from(v, (val) => val / mag);
   }
-  return v.map((val) => val / mag);
+  const { max, norm } = scaledNorm(v);
+  return Array.from(v, (val) => val / max / norm);
+}
+
+/**
+ * Standardizes a vector's components to zero mean and unit (population)
+ * standard deviation: (v_i − μ) / σ.
+ *
+ * @param v Input vector
+ * @returns  Standardized vector, or zero vector if all components are equal
+ */
+export function standardize(v: ArrayLike<number>): number[] {
+  let sum = 0;
+  for (let i = 0; i < v.length; i++) sum += v[i]!;
+  const mu = sum / v.length;
+  let sumSq = 0;
+  for (let i = 0; i < v.length; i++) sumSq += (v[i]! - mu) ** 2;
+  const sigma = Math.sqrt(sumSq / v.length);
+  if (sigma === 0) {
+    return new Array<number>(v.length).fill(0);
+  }
+  return Array.from(v, (val) => (val - mu) / sigma);
 }
 
 /**
@@ -56,17 +156,237 @@ export function normalize(v: number[]): number[] {
  *
  * @param a First vector
  * @param b Second vector
- * @returns  Cosine similarity in [-1, 1], or 0 if either vector has zero magnitude
+ * @returns  Cosine similarity in [-1, 1], 0 if either vector has zero
+ *           magnitude, or NaN if either contains NaN or an infinity
  */
-export function cosineSimilarity(a: number[], b: number[]): number {
+export function cosineSimilarity(
+  a: ArrayLike<number>,
+  b: ArrayLike<number>,
+): number {
   assertSameDimension(a, b);
-  const dot = a.reduce((sum, val, i) => sum + val * b[i]!, 0);
-  const magA = Math.sqrt(a.reduce((sum, val) => sum + val * val, 0));
-  const magB = Math.sqrt(b.reduce((sum, val) => sum + val * val, 0));
+  return cosineFromMagnitudes(a, b, magnitude(a), magnitude(b));
+}
+
+/**
+ * Cosine similarity given both magnitudes, clamped to [-1, 1] against
+ * rounding error. Extreme magnitudes are divided out per component so their
+ * product cannot overflow or underflow; non-finite inputs give NaN.
+ */
+function cosineFromMagnitudes(
+  a: ArrayLike<number>,
+  b: ArrayLike<number>,
+  magA: number,
+  magB: number,
+): number {
   if (magA === 0 || magB === 0) {
     return 0;
   }
-  return dot / (magA * magB);
+  let s: number;
+  if (
+    magA >= SAFE_MIN_MAGNITUDE &&
+    magA <= SAFE_MAX_MAGNITUDE &&
+    magB >= SAFE_MIN_MAGNITUDE &&
+    magB <= SAFE_MAX_MAGNITUDE
+  ) {
+    s = dot(a, b) / (magA * magB);
+  } else {
+    assertSameDimension(a, b);
+    const { max: maxA, norm: normA } = scaledNorm(a);
+    const { max: maxB, norm: normB } = scaledNorm(b);
+    s = 0;
+    for (let i = 0; i < a.length; i++) {
+      s += (a[i]! / maxA) * (b[i]! / maxB);
+    }
+    s /= normA * normB;
+  }
+  return Math.max(-1, Math.min(1, s));
+}
+
+/**
+ * Computes the Euclidean (L2) distance between two vectors.
+ *
+ * @param a First vector
+ * @param b Second vector
+ * @returns  L2 distance ≥ 0
+ */
+export function euclideanDistance(
+  a: ArrayLike<number>,
+  b: ArrayLike<number>,
+): number {
+  assertSameDimension(a, b);
+  let sum = 0;
+  for (let i = 0; i < a.length; i++) {
+    sum += (a[i]! - b[i]!) ** 2;
+  }
+  return Math.sqrt(sum);
+}
+
+/**
+ * Computes the Manhattan (L1) distance between two vectors.
+ *
+ * @param a First vector
+ * @param b Second vector
+ * @returns  L1 distance ≥ 0
+ */
+export function manhattanDistance(
+  a: ArrayLike<number>,
+  b: ArrayLike<number>,
+): number {
+  assertSameDimension(a, b);
+  let sum = 0;
+  for (let i = 0; i < a.length; i++) {
+    sum += Math.abs(a[i]! - b[i]!);
+  }
+  return sum;
+}
+
+/**
+ * Computes the Mahalanobis distance between two vectors under a diagonal
+ * covariance: sqrt(Σ (a_i − b_i)² / σ²_i). Dimensions with a large variance
+ * count for less than quiet ones.
+ *
+ * @param a        First vector
+ * @param b        Second vector
+ * @param variance Per-dimension variance σ², all > 0
+ * @returns        Mahalanobis distance ≥ 0
+ */
+export function mahalanobisDistance(
+  a: ArrayLike<number>,
+  b: ArrayLike<number>,
+  variance: ArrayLike<number>,
+): number {
+  assertSameDimension(a, b);
+  assertSameDimension(a, variance);
+  let sum = 0;
+  for (let i = 0; i < a.length; i++) {
+    sum += (a[i]! - b[i]!) ** 2 / variance[i]!;
+  }
+  return Math.sqrt(sum);
+}
+
+/**
+ * Computes the angular distance between two vectors: the angle between them
+ * divided by π.
+ *
+ * @param a First vector
+ * @param b Second vector
+ * @returns  Angular distance in [0, 1]; 0.5 if either vector has zero magnitude
+ */
+export function angularDistance(
+  a: ArrayLike<number>,
+  b: ArrayLike<number>,
+): number {
+  const similarity = Math.max(-1, Math.min(1, cosineSimilarity(a, b)));
+  return Math.acos(similarity) / Math.PI;
+}
+
+/**
+ * Scales a non-negative vector to sum to 1.
+ *
+ * @throws {Error} if a component is negative or every component is 0.
+ */
+export function toDistribution(v: ArrayLike<number>): number[] {
+  let total = 0;
+  for (let i = 0; i < v.length; i++) {
+    if (!(v[i]! >= 0)) {
+      throw new Error(`Distribution component ${i} is negative: ${v[i]}`);
+    }
+    total += v[i]!;
+  }
+  if (total === 0) throw new Error("Distribution has no mass");
+  return Array.from(v, (x) => x / total);
+}
+
+/**
+ * Computes the Kullback-Leibler divergence KL(p ‖ q) = Σ p_i ln(p_i / q_i)
+ * in nats, treating both vectors as distributions: they must be
+ * non-negative and are scaled to sum to 1 first. Components of q are
+ * floored at `epsilon`, so the divergence stays finite where q has no mass.
+ * Not symmetric.
+ *
+ * @param p       "True" distribution
+ * @param q       Approximating distribution
+ * @param epsilon Floor applied to q's components
+ * @returns       Divergence ≥ 0
+ * @throws {Error} if either vector has a negative component or no mass.
+ */
+export function klDivergence(
+  p: ArrayLike<number>,
+  q: ArrayLike<number>,
+  epsilon = 1e-10,
+): number {
+  assertSameDimension(p, q);
+  const pd = toDistribution(p);
+  const qd = toDistribution(q);
+  let sum = 0;
+  for (let i = 0; i < pd.length; i++) {
+    if (pd[i]! > 0) sum += pd[i]! * Math.log(pd[i]! / Math.max(qd[i]!, epsilon));
+  }
+  return Math.max(0, sum);
+}
+
+/**
+ * Computes the Jensen-Shannon divergence in bits: the mean KL divergence
+ * of p and q from their midpoint m = (p + q) / 2, with both vectors treated
+ * as distributions as in {@link klDivergence}. Symmetric and always finite.
+ *
+ * @param p First distribution
+ * @param q Second distribution
+ * @returns Divergence in [0, 1]: 0 for identical distributions, 1 for
+ *          distributions with disjoint support
+ * @throws {Error} if either vector has a negative component or no mass.
+ */
+export function jsDivergence(p: ArrayLike<number>, q: ArrayLike<number>): number {
+  assertSameDimension(p, q);
+  const pd = toDistribution(p);
+  const qd = toDistribution(q);
+  let sum = 0;
+  for (let i = 0; i < pd.length; i++) {
+    sum += jsTerm(pd[i]!, qd[i]!);
+  }
+  return Math.min(1, Math.max(0, sum));
+}
+
+/**
+ * One component's share of the Jensen-Shannon divergence (bits) between two
+ * distributions with masses `p` and `q` there; never negative.
+ */
+export function jsTerm(p: number, q: number): number {
+  const m = (p + q) / 2;
+  let term = 0;
+  if (p > 0) term += p * Math.log2(p / m);
+  if (q > 0) term += q * Math.log2(q / m);
+  return Math.max(0, term / 2);
+}
+
+/**
+ * Computes the pairwise cosine similarity of a list of vectors — e.g. for a
+ * clustering heat map. Each magnitude is computed once and only the upper
+ * triangle is evaluated, so it is considerably cheaper than calling
+ * {@link cosineSimilarity} for every pair.
+ *
+ * @param vectors Vectors of identical dimension
+ * @returns       Symmetric n × n matrix whose entry [i][j] is the cosine
+ *                similarity of vectors i and j (0 where either is zero)
+ */
+export function similarityMatrix(vectors: ArrayLike<number>[]): number[][] {
+  const n = vectors.length;
+  const magnitudes = vectors.map((v) => magnitude(v));
+  const matrix = vectors.map(() => new Array<number>(n).fill(0));
+  for (let i = 0; i < n; i++) {
+    const a = vectors[i]!;
+    const magA = magnitudes[i]!;
+    if (magA === 0) continue;
+    for (let j = i; j < n; j++) {
+      const magB = magnitudes[j]!;
+      if (magB === 0) continue;
+      const s =
+        i === j ? 1 : cosineFromMagnitudes(a, vectors[j]!, magA, magB);
+      matrix[i]![j] = s;
+      matrix[j]![i] = s;
+    }
+  }
+  return matrix;
 }
 
 /**
@@ -80,13 +400,87 @@ export function cosineSimilarity(a: number[], b: number[]): number {
  * @returns        Updated state vector S_t
  */
 export function emaFusion(
-  current: number[],
-  previous: number[],
+  current: ArrayLike<number>,
+  previous: ArrayLike<number>,
   alpha: number,
 ): number[] {
   assertSameDimension(current, previous);
   if (alpha <= 0 || alpha > 1) {
     throw new Error(`Alpha must be in the range (0, 1], got ${alpha}`);
   }
-  return current.map((val, i) => alpha * val + (1 - alpha) * previous[i]!);
+  const decay = 1 - alpha;
+  const result = new Array<number>(current.length);
+  for (let i = 0; i < current.length; i++) {
+    result[i] = alpha * current[i]! + decay * previous[i]!;
+  }
+  return result;
+}
+
+/**
+ * Computes the element-wise arithmetic mean of a non-empty list of vectors.
+ *
+ * @param vectors Vectors of identical dimension
+ * @returns       Mean vector
+ */
+export function mean(vectors: number[][]): number[] {
+  return weightedMean(vectors, vectors.map(() => 1));
+}
+
+/**
+ * Computes the element-wise median of a non-empty list of vectors: for an
+ * even count, the mean of the two middle values. Unlike the mean, a single
+ * extreme vector cannot move it arbitrarily far.
+ *
+ * @param vectors Vectors of identical dimension
+ * @returns       Median vector
+ */
+export function median(vectors: number[][]): number[] {
+  if (vectors.length === 0) {
+    throw new Error("Cannot compute the median of an empty list of vectors");
+  }
+  const first = vectors[0]!;
+  vectors.forEach((v) => assertSameDimension(first, v));
+  const mid = vectors.length >> 1;
+  const column = new Array<number>(vectors.length);
+  return first.map((_, i) => {
+    vectors.forEach((v, k) => {
+      column[k] = v[i]!;
+    });
+    column.sort((a, b) => a - b);
+    return vectors.length % 2 === 1
+      ? column[mid]!
+      : (column[mid - 1]! + column[mid]!) / 2;
+  });
+}
+
+/**
+ * Computes the element-wise weighted mean of a non-empty list of vectors.
+ *
+ * @param vectors Vectors of identical dimension
+ * @param weights One non-negative weight per vector; must not sum to 0
+ * @returns       Weighted mean vector
+ */
+export function weightedMean(vectors: number[][], weights: number[]): number[] {
+  if (vectors.length === 0) {
+    throw new Error("Cannot compute the mean of an empty list of vectors");
+  }
+  if (weights.length !== vectors.length) {
+    throw new Error(
+      `Weight count mismatch: vectors=${vectors.length}, weights=${weights.length}`,
+    );
+  }
+  const totalWeight = weights.reduce((sum, w) => sum + w, 0);
+  if (totalWeight <= 0) {
+    throw new Error(`Weights must sum to a positive value, got ${totalWeight}`);
+  }
+  const first = vectors[0]!;
+  const result = new Array(first.length).fill(0) as number[];
+  vectors.forEach((v, k) => {
+    assertSameDimension(first, v);
+    const w = weights[k]! / totalWeight;
+    v.forEach((val, i) => {
+      result[i] += w * val;
+    });
+  });
+  return result;
 }
@@ -37,6 +37,10 @@ describe("useSemanticState", () => {
       vector: [0.1, 0.2],
       healthScore: 0.95,
       timestamp: Date.now(),
+      lastDriftScore: 0,
+      lastSimilarity: 1,
+      updateCount: 1,
+      dimension: 2,
       semanticSummary: "stable",
     };
     const { engine } = makeEngine(initial);
@@ -52,6 +56,10 @@ describe("useSemanticState", () => {
       vector: [],
       healthScore: 0.9,
       timestamp: Date.now(),
+      lastDriftScore: 0,
+      lastSimilarity: 1,
+      updateCount: 0,
+      dimension: null,
       semanticSummary: "stable",
     };
     const { engine, emitUpdate } = makeEngine(initial);
@@ -64,6 +72,10 @@ describe("useSemanticState", () => {
       vector: [0.5],
       healthScore: 0.4,
       timestamp: Date.now(),
+      lastDriftScore: 0,
+      lastSimilarity: 1,
+      updateCount: 1,
+      dimension: 1,
       semanticSummary: "volatile",
     };
 
@@ -78,6 +90,10 @@ describe("useSemanticState", () => {
       vector: [],
       healthScore: 1,
       timestamp: Date.now(),
+      lastDriftScore: 0,
+      lastSimilarity: 1,
+      updateCount: 0,
+      dimension: null,
       semanticSummary: "stable",
     };
     const { engine } = makeEngine(initial);
@@ -41,6 +41,7 @@ function inlineWorkerPlugin(): Plugin {
 export default defineConfig({
   entry: {
     index: "src/index.ts",
+    core: "src/core.ts",
     zustand: "src/adapters/zustand.ts",
     react: "src/react/index.ts",
   },
@@ -6,6 +6,9 @@ export default defineConfig({
     globals: false,
     setupFiles: ["./vitest.setup.ts"],
     include: ["src/**/*.test.ts", "src/**/*.test.tsx"],
+    benchmark: {
+      include: ["benches/**/*.bench.ts"],
+    },
     coverage: {
       provider: "v8",
       include: ["src/**/*.ts"],